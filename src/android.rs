@@ -0,0 +1,78 @@
+use crate::{DiskInfo, HardwareProvider};
+
+/// Mount points of the emulated shared storage, backed by the `/data` partition of every device.
+const EMULATED_MOUNT_POINTS: [&str; 5] = [
+    "/storage/emulated",
+    "/sdcard",
+    "/data/media",
+    "/mnt/user",
+    "/mnt/runtime",
+];
+
+/// File systems used to emulate the shared storage.
+const EMULATED_FILE_SYSTEMS: [&str; 3] = ["sdcardfs", "esdfs", "fuse"];
+
+/// Reads a system property with `getprop`, or None if it is unset or empty.
+pub(crate) fn property(provider: &dyn HardwareProvider, name: &str) -> Option<String> {
+    provider
+        .run_command("getprop", &[name])
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Returns whether a disk is a view of the emulated shared storage, which has the same size on
+/// every device of a model.
+pub(crate) fn is_emulated_storage(disk: &DiskInfo) -> bool {
+    let mount_point = disk.mount_point.trim_end_matches('/');
+
+    EMULATED_MOUNT_POINTS
+        .iter()
+        .any(|prefix| mount_point == *prefix || mount_point.starts_with(&format!("{}/", prefix)))
+        || EMULATED_FILE_SYSTEMS.contains(&disk.file_system.to_lowercase().as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider};
+
+    fn disk(mount_point: &str, file_system: &str) -> DiskInfo {
+        DiskInfo {
+            mount_point: mount_point.to_string(),
+            file_system: file_system.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_property() {
+        let provider = FakeProvider::new(FakeData {
+            commands: [
+                (
+                    "getprop ro.product.model".to_string(),
+                    "Pixel 7\n".to_string(),
+                ),
+                ("getprop ro.boot.serialno".to_string(), "\n".to_string()),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            property(&provider, "ro.product.model"),
+            Some("Pixel 7".to_string())
+        );
+        assert_eq!(property(&provider, "ro.boot.serialno"), None);
+        assert_eq!(property(&provider, "ro.product.brand"), None);
+    }
+
+    #[test]
+    fn test_is_emulated_storage() {
+        assert!(is_emulated_storage(&disk("/storage/emulated", "sdcardfs")));
+        assert!(is_emulated_storage(&disk("/storage/emulated/0", "ext4")));
+        assert!(is_emulated_storage(&disk("/mnt/user/0/emulated", "f2fs")));
+        assert!(is_emulated_storage(&disk("/storage/1234-5678", "fuse")));
+        assert!(!is_emulated_storage(&disk("/data", "f2fs")));
+        assert!(!is_emulated_storage(&disk("/sdcardx", "ext4")));
+    }
+}
@@ -0,0 +1,223 @@
+use ::argon2::{Algorithm, Argon2, AssociatedData, Params, ParamsBuilder, Version};
+
+use crate::hash::{kdf_salt, to_hex};
+use crate::{HashAlgorithm, Identifier};
+
+/// The length of the derived key in bytes.
+const KEY_LENGTH: usize = 64;
+
+/// A struct representing the cost parameters of Argon2id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Argon2Params {
+    /// The memory used in KiB, at least 8 times the parallelism. (default: 19456)
+    pub memory_kb: u32,
+    /// The number of passes over the memory, at least 1. (default: 2)
+    pub iterations: u32,
+    /// The number of lanes, at least 1. (default: 1)
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Params {
+    /// Returns the minimum parameters recommended by OWASP for password storage.
+    fn default() -> Self {
+        Argon2Params {
+            memory_kb: 19456,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+/// Derives a key of `length` bytes with Argon2id, version 1.3.
+///
+/// The parameters are checked up front, so the `expect`s below only fire for a `data` longer than
+/// 32 bytes or a `salt` shorter than 8 bytes, which no caller passes.
+pub(crate) fn argon2id(
+    password: &[u8],
+    salt: &[u8],
+    secret: &[u8],
+    data: &[u8],
+    params: &Argon2Params,
+    length: usize,
+) -> Vec<u8> {
+    let Argon2Params {
+        memory_kb,
+        iterations,
+        parallelism,
+    } = *params;
+
+    assert!(
+        (1..=Params::MAX_P_COST).contains(&parallelism),
+        "parallelism must be between 1 and {}",
+        Params::MAX_P_COST
+    );
+    assert!(iterations > 0, "iterations must be greater than 0");
+    assert!(
+        u64::from(memory_kb) >= 8 * u64::from(parallelism),
+        "memory_kb must be at least 8 times the parallelism"
+    );
+
+    let params = ParamsBuilder::new()
+        .m_cost(memory_kb)
+        .t_cost(iterations)
+        .p_cost(parallelism)
+        .data(AssociatedData::new(data).expect("associated data too long"))
+        .output_len(length)
+        .build()
+        .expect("invalid Argon2 parameters");
+    let argon2 = Argon2::new_with_secret(secret, Algorithm::Argon2id, Version::V0x13, params)
+        .expect("secret too long");
+
+    let mut key = vec![0; length];
+    argon2
+        .hash_password_into(password, salt, &mut key)
+        .expect("Argon2 failed");
+    key
+}
+
+impl Identifier {
+    /// Applies Argon2id to the SHA3-512 hash of the Identifier and returns the hex encoded 512 bit
+    /// key, so every guess of a brute-force search costs `params.memory_kb` KiB of memory.
+    ///
+    /// This adds 100 ms or more of latency by design, with the default parameters, and should only
+    /// be used in security-critical contexts such as license binding. The salt is `uniqueid`
+    /// followed by the name of the Identifier, if it has one.
+    /// # Arguments
+    /// * `params` - The memory, iterations and parallelism of Argon2id.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{Argon2Params, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.name("LIC-2024-0042");
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// let params = Argon2Params {
+    ///     memory_kb: 1024,
+    ///     iterations: 1,
+    ///     parallelism: 1,
+    /// };
+    /// let key = identifier.build_with_argon2(params);
+    ///
+    /// assert_eq!(key.len(), 128);
+    /// assert_eq!(key, identifier.build_with_argon2(params));
+    /// ```
+    /// # Panics
+    /// Panics if the parallelism is 0 or greater than 16777215, the iterations are 0, or
+    /// `memory_kb` is less than 8 times the parallelism. Allocating `memory_kb` KiB aborts the
+    /// process if the memory is not available.
+    pub fn build_with_argon2(&self, params: Argon2Params) -> String {
+        to_hex(&argon2id(
+            &self.hash(HashAlgorithm::SHA3_512),
+            &kdf_salt(self.name.as_deref()),
+            &[],
+            &[],
+            &params,
+            KEY_LENGTH,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IdentifierType, IdentifierTypeDataList};
+
+    #[test]
+    fn test_argon2id() {
+        // RFC 9106, section 5.3
+        let params = Argon2Params {
+            memory_kb: 32,
+            iterations: 3,
+            parallelism: 4,
+        };
+
+        assert_eq!(
+            to_hex(&argon2id(
+                &[0x01; 32],
+                &[0x02; 16],
+                &[0x03; 8],
+                &[0x04; 12],
+                &params,
+                32
+            )),
+            "0d640df58d78766c08c037a34a8b53c9d01ef0452d75b65eb52520e96b01e659"
+        );
+    }
+
+    #[test]
+    fn test_build_with_argon2() {
+        let mut ram = IdentifierTypeDataList::new(IdentifierType::RAM);
+        ram.push("t", "1024");
+
+        let unnamed = Identifier {
+            data: vec![ram],
+            ..Default::default()
+        };
+        let named = Identifier {
+            name: Some("LIC-1".to_string()),
+            ..unnamed.clone()
+        };
+        let params = Argon2Params {
+            memory_kb: 64,
+            iterations: 1,
+            parallelism: 2,
+        };
+
+        assert_eq!(
+            named.build_with_argon2(params),
+            to_hex(&argon2id(
+                &named.hash(HashAlgorithm::SHA3_512),
+                b"uniqueidLIC-1",
+                &[],
+                &[],
+                &params,
+                64
+            ))
+        );
+        assert_ne!(
+            named.build_with_argon2(params),
+            unnamed.build_with_argon2(params)
+        );
+        assert_ne!(
+            unnamed.build_with_argon2(params),
+            unnamed.build_with_argon2(Argon2Params {
+                iterations: 2,
+                ..params
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "memory_kb")]
+    fn test_argon2_invalid_memory() {
+        Identifier::default().build_with_argon2(Argon2Params {
+            memory_kb: 8,
+            iterations: 1,
+            parallelism: 2,
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "parallelism")]
+    fn test_argon2_memory_overflow() {
+        // 8 times the parallelism wraps to 0 in u32
+        Identifier::default().build_with_argon2(Argon2Params {
+            memory_kb: 8,
+            iterations: 1,
+            parallelism: 1 << 29,
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "parallelism")]
+    fn test_argon2_invalid_parallelism() {
+        Identifier::default().build_with_argon2(Argon2Params {
+            memory_kb: u32::MAX,
+            iterations: 1,
+            parallelism: Params::MAX_P_COST + 1,
+        });
+    }
+}
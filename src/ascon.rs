@@ -0,0 +1,25 @@
+use ascon_hash::{AsconHash256, Digest};
+
+/// Hashes the input with Ascon-Hash256 (NIST SP 800-232) and returns the 256 bit digest.
+pub(crate) fn ascon_hash256(input: &[u8]) -> Vec<u8> {
+    AsconHash256::digest(input).to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::to_hex;
+
+    #[test]
+    fn test_ascon_hash256_kat() {
+        // NIST SP 800-232 Ascon-Hash256 known answer tests, Count = 1 and Count = 2.
+        assert_eq!(
+            to_hex(&ascon_hash256(b"")),
+            "0b3be5850f2f6b98caf29f8fdea89b64a1fa70aa249b8f839bd53baa304d92b2"
+        );
+        assert_eq!(
+            to_hex(&ascon_hash256(&[0x00])),
+            "0728621035af3ed2bca03bf6fde900f9456f5330e4b5ee23e7f6a1e70291bc80"
+        );
+    }
+}
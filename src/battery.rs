@@ -0,0 +1,232 @@
+use crate::wmi::{self, WmiRecord};
+use crate::{HardwareProvider, IdentifierOptions, IdentifierType, IdentifierTypeDataList, UNKNOWN};
+
+/// The directory holding the power supplies on Linux.
+pub(crate) const LINUX_POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+/// A single battery pack. Fields are None if they could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct BatteryInfo {
+    /// The manufacturer of the battery. (e.g. SMP)
+    pub manufacturer: Option<String>,
+    /// The model of the battery. (e.g. 5B10W13975)
+    pub model: Option<String>,
+    /// The serial number of the battery.
+    pub serial: Option<String>,
+}
+
+/// Reads the batteries from sysfs. Other power supplies, such as the AC adapter or the battery of
+/// a wireless mouse, are skipped.
+pub(crate) fn linux_batteries(provider: &dyn HardwareProvider) -> Vec<BatteryInfo> {
+    provider
+        .read_dir(LINUX_POWER_SUPPLY_DIR)
+        .into_iter()
+        .filter_map(|supply| {
+            let read = |file: &str| {
+                provider.read_to_string(&format!("{}/{}/{}", LINUX_POWER_SUPPLY_DIR, supply, file))
+            };
+
+            // Peripherals report their scope as Device, system batteries as System or not at all.
+            if read("type")?.trim() != "Battery"
+                || read("scope").is_some_and(|s| s.trim() == "Device")
+            {
+                return None;
+            }
+
+            Some(BatteryInfo {
+                manufacturer: read("manufacturer"),
+                model: read("model_name"),
+                serial: read("serial_number"),
+            })
+        })
+        .collect()
+}
+
+/// Parses the BatteryStaticData WMI instances of the root/wmi namespace.
+pub(crate) fn parse_battery_static_data(records: &[WmiRecord]) -> Vec<BatteryInfo> {
+    records
+        .iter()
+        .map(|record| BatteryInfo {
+            manufacturer: record.get("ManufactureName").cloned(),
+            model: record.get("DeviceName").cloned(),
+            serial: record.get("SerialNumber").cloned(),
+        })
+        .collect()
+}
+
+/// Parses the battery of `ioreg -rn AppleSmartBattery` output. ("key" = "value")
+pub(crate) fn parse_ioreg_battery(output: &str) -> Vec<BatteryInfo> {
+    let property = |name: &str| {
+        let key = format!("\"{}\" = ", name);
+
+        output.lines().find_map(|line| {
+            let value = line.trim().strip_prefix(&key)?;
+            Some(value.trim_matches('"').to_string())
+        })
+    };
+
+    let battery = BatteryInfo {
+        manufacturer: property("Manufacturer"),
+        model: property("DeviceName"),
+        serial: property("Serial").or_else(|| property("BatterySerialNumber")),
+    };
+
+    match battery == BatteryInfo::default() {
+        true => Vec::new(),
+        false => vec![battery],
+    }
+}
+
+/// Reads the batteries of the system.
+fn batteries(provider: &dyn HardwareProvider) -> Vec<BatteryInfo> {
+    if cfg!(target_os = "windows") {
+        parse_battery_static_data(&wmi::query_namespace(
+            provider,
+            "root/wmi",
+            "BatteryStaticData",
+            &["ManufactureName", "DeviceName", "SerialNumber"],
+        ))
+    } else if cfg!(target_os = "macos") {
+        provider
+            .run_command("ioreg", &["-rn", "AppleSmartBattery"])
+            .map(|output| parse_ioreg_battery(&output))
+            .unwrap_or_default()
+    } else {
+        linux_batteries(provider)
+    }
+}
+
+impl IdentifierTypeDataList {
+    /// Collects one block per battery with its manufacturer, model and serial number, sorted by
+    /// serial number. Without a battery nothing is collected, or a `BATTERY(none=1)` block with
+    /// `IdentifierOptions::battery_placeholder`.
+    pub(crate) fn collect_battery(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+    ) -> Vec<IdentifierTypeDataList> {
+        let value = |value: Option<String>| {
+            value
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .unwrap_or_else(|| UNKNOWN.to_string())
+        };
+
+        let mut batteries: Vec<(String, String, String)> = batteries(provider)
+            .into_iter()
+            .map(|battery| {
+                (
+                    value(battery.serial),
+                    value(battery.manufacturer),
+                    value(battery.model),
+                )
+            })
+            .collect();
+
+        batteries.sort();
+
+        if batteries.is_empty() && options.battery_placeholder {
+            let mut block = IdentifierTypeDataList::new(IdentifierType::BATTERY);
+            block.push("none", "1");
+            return vec![block];
+        }
+
+        batteries
+            .into_iter()
+            .map(|(serial, manufacturer, model)| {
+                let mut block = IdentifierTypeDataList::new(IdentifierType::BATTERY);
+                block.push("m", &manufacturer);
+                block.push("p", &model);
+                block.push("s", &serial);
+                block
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierBuilder};
+
+    fn file(path: &str, contents: &str) -> (String, Vec<u8>) {
+        (
+            format!("{}/{}", LINUX_POWER_SUPPLY_DIR, path),
+            contents.as_bytes().to_vec(),
+        )
+    }
+
+    fn battery(manufacturer: &str, model: &str, serial: &str) -> BatteryInfo {
+        BatteryInfo {
+            manufacturer: Some(manufacturer.to_string()),
+            model: Some(model.to_string()),
+            serial: Some(serial.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse_batteries() {
+        let output = "\r\n\r\nManufactureName : SMP\r\nDeviceName      : 5B10W13975\r\n\
+                      SerialNumber    : 2134\r\n\r\n";
+
+        assert_eq!(
+            parse_battery_static_data(&wmi::parse_format_list(output)),
+            vec![battery("SMP", "5B10W13975", "2134")]
+        );
+
+        let output = "+-o AppleSmartBattery  <class AppleSmartBattery>\n    {\n      \
+                      \"Manufacturer\" = \"SMP\"\n      \"DeviceName\" = \"bq20z451\"\n      \
+                      \"Serial\" = \"D865033Y2CXF9CPAW\"\n    }\n";
+
+        assert_eq!(
+            parse_ioreg_battery(output),
+            vec![battery("SMP", "bq20z451", "D865033Y2CXF9CPAW")]
+        );
+        assert!(parse_ioreg_battery("").is_empty());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_battery() {
+        let provider = FakeProvider::new(FakeData {
+            files: [
+                file("AC/type", "Mains\n"),
+                file("BAT0/type", "Battery\n"),
+                file("BAT0/manufacturer", "SMP\n"),
+                file("BAT0/model_name", "5B10W13975\n"),
+                file("BAT0/serial_number", " 2134\n"),
+                file("BAT1/type", "Battery\n"),
+                file("BAT1/manufacturer", "Sunwoda\n"),
+                file("BAT1/model_name", "01AV430\n"),
+                file("BAT1/serial_number", "1021\n"),
+                file("hidpp_battery_0/type", "Battery\n"),
+                file("hidpp_battery_0/scope", "Device\n"),
+                file("hidpp_battery_0/serial_number", "4061-c3-8d-7f-b2\n"),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::BATTERY);
+
+        assert_eq!(
+            builder.build().to_string_with(&provider, false),
+            "[BATTERY(m=Sunwoda, p=01AV430, s=1021)BATTERY(m=SMP, p=5B10W13975, s=2134)]"
+        );
+
+        let desktop = FakeProvider::default();
+        let options = IdentifierOptions {
+            battery_placeholder: true,
+            ..Default::default()
+        };
+
+        assert!(IdentifierTypeDataList::new(IdentifierType::BATTERY)
+            .collect_with(&desktop, &IdentifierOptions::default())
+            .is_empty());
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::BATTERY).build_with(&desktop, &options),
+            "BATTERY(none=1)"
+        );
+    }
+}
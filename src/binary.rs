@@ -0,0 +1,205 @@
+/// The ELF program header type of a note segment.
+const PT_NOTE: u32 = 4;
+/// The ELF note type of a GNU build-id.
+const NT_GNU_BUILD_ID: u32 = 3;
+
+/// Reads `N` bytes at the offset, if in bounds.
+fn read<const N: usize>(bytes: &[u8], offset: usize) -> Option<[u8; N]> {
+    bytes.get(offset..offset.checked_add(N)?)?.try_into().ok()
+}
+
+/// A reader for the fields of an ELF file with a given class and endianness.
+struct Elf<'a> {
+    bytes: &'a [u8],
+    is_64: bool,
+    is_big_endian: bool,
+}
+
+impl Elf<'_> {
+    fn u16(&self, offset: usize) -> Option<usize> {
+        let raw = read::<2>(self.bytes, offset)?;
+        Some(match self.is_big_endian {
+            true => u16::from_be_bytes(raw),
+            false => u16::from_le_bytes(raw),
+        } as usize)
+    }
+
+    fn u32(&self, offset: usize) -> Option<usize> {
+        let raw = read::<4>(self.bytes, offset)?;
+        Some(match self.is_big_endian {
+            true => u32::from_be_bytes(raw),
+            false => u32::from_le_bytes(raw),
+        } as usize)
+    }
+
+    fn u64(&self, offset: usize) -> Option<usize> {
+        let raw = read::<8>(self.bytes, offset)?;
+        usize::try_from(match self.is_big_endian {
+            true => u64::from_be_bytes(raw),
+            false => u64::from_le_bytes(raw),
+        })
+        .ok()
+    }
+
+    /// Reads an address-sized field (32 or 64 bits depending on the class).
+    fn word(&self, offset: usize) -> Option<usize> {
+        match self.is_64 {
+            true => self.u64(offset),
+            false => self.u32(offset),
+        }
+    }
+
+    /// Returns the (offset, size, alignment) of every PT_NOTE segment.
+    fn note_segments(&self) -> Option<Vec<(usize, usize, usize)>> {
+        let (phoff, phentsize, phnum) = match self.is_64 {
+            true => (self.u64(0x20)?, self.u16(0x36)?, self.u16(0x38)?),
+            false => (self.u32(0x1c)?, self.u16(0x2a)?, self.u16(0x2c)?),
+        };
+
+        let mut result = Vec::new();
+
+        for i in 0..phnum {
+            let header = phoff.checked_add(i.checked_mul(phentsize)?)?;
+
+            if self.u32(header)? as u32 != PT_NOTE {
+                continue;
+            }
+
+            result.push(match self.is_64 {
+                true => (
+                    self.word(header + 0x08)?,
+                    self.word(header + 0x20)?,
+                    self.word(header + 0x30)?,
+                ),
+                false => (
+                    self.word(header + 0x04)?,
+                    self.word(header + 0x10)?,
+                    self.word(header + 0x1c)?,
+                ),
+            });
+        }
+
+        Some(result)
+    }
+
+    /// Returns the descriptor of the GNU build-id note in the segment, if any.
+    fn build_id_in(&self, offset: usize, size: usize, align: usize) -> Option<Vec<u8>> {
+        // Notes are 4-byte aligned, except in segments explicitly aligned to 8 bytes.
+        let align = if align == 8 { 8 } else { 4 };
+        let pad = |n: usize| n.checked_add(align - 1).map(|n| n & !(align - 1));
+
+        let end = offset.checked_add(size)?.min(self.bytes.len());
+        let mut position = offset;
+
+        while position.checked_add(12)? <= end {
+            let name_size = self.u32(position)?;
+            let desc_size = self.u32(position + 4)?;
+            let note_type = self.u32(position + 8)? as u32;
+
+            let name = position + 12;
+            let desc = name.checked_add(pad(name_size)?)?;
+            let next = desc.checked_add(pad(desc_size)?)?;
+
+            if note_type == NT_GNU_BUILD_ID
+                && self.bytes.get(name..name.checked_add(name_size)?)? == b"GNU\0"
+            {
+                return Some(self.bytes.get(desc..desc.checked_add(desc_size)?)?.to_vec());
+            }
+
+            position = next;
+        }
+
+        None
+    }
+}
+
+/// Returns the GNU build-id of an ELF file, if the file has one.
+pub(crate) fn elf_build_id(bytes: &[u8]) -> Option<Vec<u8>> {
+    if bytes.get(..4)? != b"\x7fELF" {
+        return None;
+    }
+
+    let elf = Elf {
+        bytes,
+        is_64: match bytes.get(4)? {
+            1 => false,
+            2 => true,
+            _ => return None,
+        },
+        is_big_endian: match bytes.get(5)? {
+            1 => false,
+            2 => true,
+            _ => return None,
+        },
+    };
+
+    elf.note_segments()?
+        .into_iter()
+        .find_map(|(offset, size, align)| elf.build_id_in(offset, size, align))
+        .filter(|id| !id.is_empty())
+}
+
+/// Returns the link timestamp of the COFF header of a PE file, if the file is one.
+/// Reproducible builds store a hash of the build inputs in this field instead of a time.
+pub(crate) fn pe_timestamp(bytes: &[u8]) -> Option<u32> {
+    if bytes.get(..2)? != b"MZ" {
+        return None;
+    }
+
+    let header = u32::from_le_bytes(read(bytes, 0x3c)?) as usize;
+
+    if bytes.get(header..header.checked_add(4)?)? != b"PE\0\0" {
+        return None;
+    }
+
+    Some(u32::from_le_bytes(read(bytes, header + 8)?))
+}
+
+/// Returns the build identity of an executable: the GNU build-id of an ELF file, or the
+/// little-endian link timestamp of a PE file.
+pub(crate) fn build_identity(bytes: &[u8]) -> Option<Vec<u8>> {
+    elf_build_id(bytes).or_else(|| pe_timestamp(bytes).map(|stamp| stamp.to_le_bytes().to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Hand-crafted executables holding only the headers read by the parsers.
+    const ELF_64: &[u8] = include_bytes!("../tests/fixtures/binaries/build_id_x86_64.elf");
+    const ELF_32_BIG_ENDIAN: &[u8] = include_bytes!("../tests/fixtures/binaries/build_id_mips.elf");
+    const ELF_NO_BUILD_ID: &[u8] =
+        include_bytes!("../tests/fixtures/binaries/no_build_id_x86_64.elf");
+    const PE: &[u8] = include_bytes!("../tests/fixtures/binaries/timestamp_x86_64.exe");
+
+    #[test]
+    fn test_elf_build_id() {
+        let expected: Vec<u8> = (0x10..0x24).collect();
+
+        assert_eq!(elf_build_id(ELF_64), Some(expected.clone()));
+        assert_eq!(elf_build_id(ELF_32_BIG_ENDIAN), Some(expected));
+        assert_eq!(elf_build_id(ELF_NO_BUILD_ID), None);
+        assert_eq!(elf_build_id(PE), None);
+    }
+
+    #[test]
+    fn test_pe_timestamp() {
+        assert_eq!(pe_timestamp(PE), Some(100_000_000));
+        assert_eq!(pe_timestamp(ELF_64), None);
+        assert_eq!(
+            build_identity(PE),
+            Some(100_000_000u32.to_le_bytes().to_vec())
+        );
+    }
+
+    #[test]
+    fn test_truncated_input() {
+        for bytes in [ELF_64, ELF_32_BIG_ENDIAN, PE] {
+            for len in 0..bytes.len() {
+                let _ = build_identity(&bytes[..len]);
+            }
+        }
+
+        assert_eq!(build_identity(&ELF_64[..ELF_64.len() - 1]), None);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    HardwareProvider, IdentifierBuilder, IdentifierType, IdentifierTypeDataList, SysinfoProvider,
+    UNKNOWN,
+};
+
+/// The tag of the component added by `IdentifierBuilder::add_boot_loader_uuid`.
+pub(crate) const BOOT_LOADER_TAG: &str = "BOOT_LOADER";
+
+/// The GRUB configuration files on Linux, in the order they are tried.
+const GRUB_CONFIGS: [&str; 2] = ["/boot/grub/grub.cfg", "/boot/grub2/grub.cfg"];
+
+/// The directory holding the boot loaders of the EFI system partition on Linux.
+const EFI_DIR: &str = "/boot/efi/EFI";
+
+/// Parses the UUID of the file system GRUB loads its files from, the first one searched for in the
+/// configuration. (`search --fs-uuid --set=root <uuid>` or `search.fs_uuid <uuid> root`)
+pub(crate) fn parse_grub_cfg_uuid(config: &str) -> Option<String> {
+    config.lines().find_map(|line| {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        let uuid = match tokens.first() {
+            Some(&"search.fs_uuid") => tokens.get(1)?,
+            Some(&"search") if tokens.iter().any(|t| *t == "--fs-uuid" || *t == "-u") => {
+                tokens.iter().skip(1).rfind(|t| !t.starts_with('-'))?
+            }
+            _ => return None,
+        };
+
+        Some(uuid.to_lowercase())
+    })
+}
+
+/// Parses the GUID of the first boot loader entry of `bcdedit /enum {current} /v` output.
+/// The GUID is searched for instead of the `identifier` label, which is localized.
+pub(crate) fn parse_bcdedit_identifier(output: &str) -> Option<String> {
+    output.split_whitespace().find_map(|token| {
+        let guid = token.strip_prefix('{')?.strip_suffix('}')?;
+        let lengths: Vec<usize> = guid.split('-').map(str::len).collect();
+
+        let valid =
+            lengths == [8, 4, 4, 4, 12] && guid.chars().all(|c| c == '-' || c.is_ascii_hexdigit());
+
+        valid.then(|| guid.to_lowercase())
+    })
+}
+
+/// Reads the UUID of the boot loader, or None if it can not be read. Reading the Windows Boot
+/// Manager entry requires administrator privileges.
+pub(crate) fn boot_loader_uuid(provider: &dyn HardwareProvider) -> Option<String> {
+    if cfg!(target_os = "windows") {
+        provider
+            .run_command("bcdedit", &["/enum", "{current}", "/v"])
+            .and_then(|output| parse_bcdedit_identifier(&output))
+    } else if cfg!(target_os = "linux") {
+        let efi_configs = provider
+            .read_dir(EFI_DIR)
+            .into_iter()
+            .map(|entry| format!("{}/{}/grub.cfg", EFI_DIR, entry));
+
+        GRUB_CONFIGS
+            .iter()
+            .map(|path| path.to_string())
+            .chain(efi_configs)
+            .filter_map(|path| provider.read_to_string(&path))
+            .find_map(|config| parse_grub_cfg_uuid(&config))
+    } else {
+        None
+    }
+}
+
+impl IdentifierBuilder {
+    /// Reads the UUID of the boot loader and adds it as a custom `BOOT_LOADER(id=...)` component.
+    ///
+    /// On Linux this is the file system UUID GRUB loads its files from, read from `grub.cfg`. On
+    /// Windows it is the GUID of the current Windows Boot Manager entry. It only changes when the
+    /// boot loader is reinstalled. The id is `unknown` if it can not be read, such as on macOS.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add_boot_loader_uuid();
+    ///
+    /// assert_eq!(builder.data[0].identifier, IdentifierType::from("BOOT_LOADER"));
+    /// ```
+    pub fn add_boot_loader_uuid(&mut self) -> &mut Self {
+        self.add_boot_loader_uuid_with(&SysinfoProvider::new())
+    }
+
+    /// Reads the UUID of the boot loader from the given provider and adds it as a custom
+    /// `BOOT_LOADER(id=...)` component, see `IdentifierBuilder::add_boot_loader_uuid`.
+    /// # Arguments
+    /// * `provider` - The HardwareProvider to read the boot loader configuration from.
+    pub fn add_boot_loader_uuid_with(&mut self, provider: &dyn HardwareProvider) -> &mut Self {
+        let uuid = boot_loader_uuid(provider).unwrap_or_else(|| UNKNOWN.to_string());
+
+        let mut list =
+            IdentifierTypeDataList::new(IdentifierType::Custom(BOOT_LOADER_TAG.to_string()));
+        list.push("id", &uuid);
+
+        self.data.push(list);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider};
+
+    #[test]
+    fn test_parse_grub_cfg_uuid() {
+        let config = "insmod part_gpt\ninsmod ext2\nset root='hd0,gpt2'\n\
+                      if [ x$feature_platform_search_hint = xy ]; then\n  \
+                      search --no-floppy --fs-uuid --set=root --hint-bios=hd0,gpt2 --hint-efi=hd0,gpt2  \
+                      8D4C1B2A-6E0F-4A77-9B3E-2F1C5D7E9A01\nelse\n  \
+                      search --no-floppy --fs-uuid --set=root 8d4c1b2a-6e0f-4a77-9b3e-2f1c5d7e9a01\nfi\n";
+
+        assert_eq!(
+            parse_grub_cfg_uuid(config),
+            Some("8d4c1b2a-6e0f-4a77-9b3e-2f1c5d7e9a01".to_string())
+        );
+        assert_eq!(
+            parse_grub_cfg_uuid(
+                "search.fs_uuid 3f2a-11c4 root hd0,gpt2\nset prefix=($root)'/boot/grub'\n"
+            ),
+            Some("3f2a-11c4".to_string())
+        );
+        assert_eq!(
+            parse_grub_cfg_uuid("search --file --set=root /vmlinuz\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_bcdedit_identifier() {
+        let output = "\r\nWindows Boot Loader\r\n-------------------\r\n\
+                      identifier              {B2721D73-1DB4-4C62-BF78-C548A880142D}\r\n\
+                      device                  partition=C:\r\n\
+                      resumeobject            {9f1e2b3c-4d5e-11ee-a1b2-c3d4e5f60718}\r\n";
+
+        assert_eq!(
+            parse_bcdedit_identifier(output),
+            Some("b2721d73-1db4-4c62-bf78-c548a880142d".to_string())
+        );
+        assert_eq!(parse_bcdedit_identifier("identifier {current}\r\n"), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_add_boot_loader_uuid() {
+        let provider = FakeProvider::new(FakeData {
+            files: [(
+                "/boot/efi/EFI/debian/grub.cfg".to_string(),
+                b"search.fs_uuid 3f2a-11c4 root hd0,gpt2\n".to_vec(),
+            )]
+            .into(),
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add_boot_loader_uuid_with(&provider);
+        builder.add_boot_loader_uuid_with(&FakeProvider::default());
+
+        assert_eq!(
+            builder.build().to_string_with(&provider, false),
+            "[BOOT_LOADER(id=3f2a-11c4), BOOT_LOADER(id=unknown)]"
+        );
+    }
+}
@@ -0,0 +1,1169 @@
+use std::fmt::Display;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use sha2::{Digest, Sha256};
+use sha3::{Sha3_256, Sha3_512};
+
+use crate::hash::to_hex;
+use crate::parse::escape_name;
+use crate::{
+    is_metadata, HardwareProvider, HashAlgorithm, Identifier, IdentifierTypeDataBuilder,
+    PqAlgorithm, SysinfoProvider, UniqueIdError, FORMAT_PREFIX,
+};
+
+/// A stored hash built with `Identifier::build_with_version_prefix`. (crate_version:hash)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VersionedIdentifier {
+    crate_version: String,
+    hash: String,
+}
+
+impl VersionedIdentifier {
+    /// Returns the crate version the hash was built with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::VersionedIdentifier;
+    ///
+    /// let stored: VersionedIdentifier = "0.2.6:9f86d081".parse().unwrap();
+    ///
+    /// assert_eq!(stored.crate_version(), "0.2.6");
+    /// ```
+    pub fn crate_version(&self) -> &str {
+        &self.crate_version
+    }
+
+    /// Returns the hex encoded hash, without the version prefix.
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+}
+
+impl FromStr for VersionedIdentifier {
+    type Err = UniqueIdError;
+
+    /// Parses a stored versioned hash in the `crate_version:hash` format.
+    fn from_str(stored: &str) -> Result<Self, Self::Err> {
+        match stored.rsplit_once(':') {
+            Some((crate_version, hash)) if !crate_version.is_empty() && !hash.is_empty() => {
+                Ok(VersionedIdentifier {
+                    crate_version: crate_version.to_string(),
+                    hash: hash.to_string(),
+                })
+            }
+            _ => Err(UniqueIdError::Parse(format!(
+                "missing version prefix in {}",
+                stored
+            ))),
+        }
+    }
+}
+
+impl Display for VersionedIdentifier {
+    /// Returns the versioned hash in the stored format. (crate_version:hash)
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}:{}", self.crate_version, self.hash)
+    }
+}
+
+/// A 128 bit identifier built with `Identifier::to_compact_binary_id`.
+/// Displayed in the hyphenated UUID format, so it can be stored as a UUID key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CompactId([u8; 16]);
+
+impl CompactId {
+    /// Creates a CompactId from the bytes returned by `Identifier::to_compact_binary_id`.
+    /// # Examples
+    /// ```
+    /// use uniqueid::CompactId;
+    ///
+    /// let id = CompactId::from_compact_binary_id([0xab; 16]);
+    ///
+    /// assert_eq!(id.to_string(), "abababab-abab-abab-abab-abababababab");
+    /// ```
+    pub fn from_compact_binary_id(bytes: [u8; 16]) -> CompactId {
+        CompactId(bytes)
+    }
+
+    /// Returns the bytes of the CompactId.
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+}
+
+impl Display for CompactId {
+    /// Returns the CompactId as hyphenated lowercase hex. (8-4-4-4-12)
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let hex = to_hex(&self.0);
+
+        write!(
+            f,
+            "{}-{}-{}-{}-{}",
+            &hex[..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+    }
+}
+
+impl Identifier {
+    /// Builds the Identifier in its sorted stable form and returns the hex encoded hash.
+    ///
+    /// Components are sorted by type name, key-value pairs are sorted by key and every value is
+    /// lowercased and trimmed before hashing, so the result does not depend on the order data is
+    /// collected in. This is the recommended build method for long-lived stored fingerprints.
+    /// # Arguments
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// builder.add(IdentifierType::CPU);
+    ///
+    /// let hash = builder.build().build_sorted_stable(HashAlgorithm::SHA3_512);
+    ///
+    /// assert_eq!(hash.len(), 128);
+    /// ```
+    pub fn build_sorted_stable(&self, algorithm: HashAlgorithm) -> String {
+        to_hex(&algorithm.digest(self.to_sorted_stable_string().as_bytes()))
+    }
+
+    /// Builds the Identifier with the crate version included in the hash input and returns the
+    /// versioned hash in the `crate_version:hash` format.
+    ///
+    /// The hashed input is `crate_version:canonical_string`, so fingerprints built by crate
+    /// versions with different field formats never match by accident. The prefix can be read back
+    /// from a stored hash with `VersionedIdentifier`.
+    /// # Arguments
+    /// * `crate_version` - The version to prefix, usually `env!("CARGO_PKG_VERSION")`.
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType, VersionedIdentifier};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    ///
+    /// let stored = builder.build().build_with_version_prefix("0.2.6", HashAlgorithm::SHA3_256);
+    /// let versioned: VersionedIdentifier = stored.parse().unwrap();
+    ///
+    /// assert_eq!(versioned.crate_version(), "0.2.6");
+    /// assert_eq!(versioned.hash().len(), 64);
+    /// ```
+    pub fn build_with_version_prefix(
+        &self,
+        crate_version: &str,
+        algorithm: HashAlgorithm,
+    ) -> String {
+        let input = format!(
+            "{}:{}",
+            crate_version,
+            self.to_canonical_string_with(&SysinfoProvider::new())
+        );
+
+        format!(
+            "{}:{}",
+            crate_version,
+            to_hex(&algorithm.digest(input.as_bytes()))
+        )
+    }
+
+    /// Builds the Identifier bound to an offline license and returns the hex encoded hash.
+    ///
+    /// The hashed input is `license_id:canonical_string`, so a fingerprint issued for one license
+    /// does not validate a machine running a different license.
+    /// # Arguments
+    /// * `license_id` - The id of the license the hardware is bound to.
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// Issuing a license file on the customer's machine and validating it at startup:
+    /// ```
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::CPU);
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// // Activation: the binding is sent to the license server and stored in the signed license.
+    /// let binding = identifier.build_for_offline_license("LIC-2024-0042", HashAlgorithm::SHA3_256);
+    ///
+    /// // Startup: the binding is rebuilt from the license id and compared to the stored one.
+    /// let valid = identifier.build_for_offline_license("LIC-2024-0042", HashAlgorithm::SHA3_256);
+    /// let other = identifier.build_for_offline_license("LIC-2024-0043", HashAlgorithm::SHA3_256);
+    ///
+    /// assert_eq!(binding, valid);
+    /// assert_ne!(binding, other);
+    /// ```
+    pub fn build_for_offline_license(&self, license_id: &str, algorithm: HashAlgorithm) -> String {
+        let input = format!(
+            "{}:{}",
+            license_id,
+            self.to_canonical_string_with(&SysinfoProvider::new())
+        );
+
+        to_hex(&algorithm.digest(input.as_bytes()))
+    }
+
+    /// Builds the Identifier bound to a geographic location and returns the hex encoded SHA3-512
+    /// hash.
+    ///
+    /// The coordinates are snapped to a grid of `precision_km` cells, so GPS drift within a cell
+    /// does not change the hash. The hashed input is `geo:lat_cell,lon_cell:canonical_string`.
+    /// The location is supplied by the caller, it is never detected.
+    /// # Arguments
+    /// * `lat` - The latitude in degrees, clamped to -90..=90.
+    /// * `lon` - The longitude in degrees, wrapped to -180..180.
+    /// * `precision_km` - The size of a grid cell in km.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// // A few hundred meters apart in Berlin, and Munich.
+    /// let office = identifier.build_with_geolocation_hint(52.5200, 13.4050, 50.0);
+    /// let nearby = identifier.build_with_geolocation_hint(52.5230, 13.4010, 50.0);
+    /// let munich = identifier.build_with_geolocation_hint(48.1351, 11.5820, 50.0);
+    ///
+    /// assert_eq!(office, nearby);
+    /// assert_ne!(office, munich);
+    /// ```
+    /// # Panics
+    /// Panics if a coordinate is not finite or `precision_km` is not a positive finite number.
+    pub fn build_with_geolocation_hint(&self, lat: f64, lon: f64, precision_km: f64) -> String {
+        let (lat_cell, lon_cell) = geolocation_cell(lat, lon, precision_km);
+        let input = format!(
+            "geo:{},{}:{}",
+            lat_cell,
+            lon_cell,
+            self.to_canonical_string_with(&SysinfoProvider::new())
+        );
+
+        to_hex(&HashAlgorithm::SHA3_512.digest(input.as_bytes()))
+    }
+
+    /// Builds the Identifier with every value replaced by its SHA-256 hash and returns the hex
+    /// encoded hash of the result, so no raw hardware value is part of the hashed input.
+    ///
+    /// The hashed input is the canonical string with hex encoded SHA-256 values, such as
+    /// `[RAM(t=<sha256 of 1024>)]`. It can be logged or sent to a service in place of the raw data.
+    /// # Arguments
+    /// * `algorithm` - The HashAlgorithm the stripped Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// let hash = identifier.build_with_private_fields_stripped(HashAlgorithm::SHA3_256);
+    ///
+    /// assert_eq!(hash.len(), 64);
+    /// assert_ne!(hash, identifier.build_sorted_stable(HashAlgorithm::SHA3_256));
+    /// ```
+    pub fn build_with_private_fields_stripped(&self, algorithm: HashAlgorithm) -> String {
+        let input = self.to_private_fields_stripped_string(&SysinfoProvider::new());
+
+        to_hex(&algorithm.digest(input.as_bytes()))
+    }
+
+    /// Returns the canonical string hashed by `build_with_private_fields_stripped`.
+    pub(crate) fn to_private_fields_stripped_string(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> String {
+        let data = self
+            .data
+            .iter()
+            .flat_map(|i| i.collect_with(provider, &self.options))
+            .map(|mut block| {
+                for item in block.data.iter_mut().filter(|item| !is_metadata(&item.key)) {
+                    item.value = to_hex(&Sha256::digest(item.value.as_bytes()));
+                }
+                block
+            })
+            .collect();
+
+        // The blocks are already populated, so they are rendered without collecting again.
+        let identifier = Identifier {
+            name: self.name.clone(),
+            data,
+            options: self.options.clone(),
+            description: self.description.clone(),
+        };
+
+        identifier.to_string_with(provider, false)
+    }
+
+    /// Hashes the Identifier with a quantum-resistant algorithm and returns the hex encoded hash.
+    /// # Arguments
+    /// * `algorithm` - The PqAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType, PqAlgorithm};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// assert_eq!(identifier.build_quantum_resistant(PqAlgorithm::AsconHash256).len(), 64);
+    /// assert_eq!(identifier.build_quantum_resistant(PqAlgorithm::SHA3_512), identifier.to_string(true));
+    /// ```
+    pub fn build_quantum_resistant(&self, algorithm: PqAlgorithm) -> String {
+        let canonical = self.to_canonical_string_with(&SysinfoProvider::new());
+
+        to_hex(&algorithm.digest(canonical.as_bytes()))
+    }
+
+    /// Builds the Identifier with a proof of work and returns the hex encoded hash and the nonce.
+    ///
+    /// The nonce is the smallest number for which the hash of `canonical_string` followed by the
+    /// decimal nonce starts with `difficulty` zero bits, so about `2^difficulty` hashes are computed.
+    /// A verifier recomputes the hash from the canonical string and the nonce with a single hash.
+    /// # Arguments
+    /// * `difficulty` - The number of leading zero bits required. (8 requires one zero byte)
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    ///
+    /// let (hash, _nonce) = builder.build().build_with_proof_of_work(8, HashAlgorithm::SHA3_256);
+    ///
+    /// assert!(hash.starts_with("00"));
+    /// ```
+    pub fn build_with_proof_of_work(
+        &self,
+        difficulty: u8,
+        algorithm: HashAlgorithm,
+    ) -> (String, u64) {
+        proof_of_work(
+            &self.to_canonical_string_with(&SysinfoProvider::new()),
+            difficulty,
+            algorithm,
+        )
+    }
+
+    /// Increments the counter and builds the Identifier with the new value, returning the hex
+    /// encoded hash and the counter value, so every build on the same machine has a different hash.
+    ///
+    /// The hashed input is `counter:canonical_string`, with the decimal counter value. The counter
+    /// is incremented atomically, so it can be shared between threads.
+    /// # Arguments
+    /// * `counter` - The counter to increment. It wraps around after `u64::MAX`.
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use std::sync::atomic::AtomicU64;
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// let counter = AtomicU64::new(0);
+    /// let (first, n) = identifier.build_with_monotonic_counter(&counter, HashAlgorithm::SHA3_256);
+    /// let (second, m) = identifier.build_with_monotonic_counter(&counter, HashAlgorithm::SHA3_256);
+    ///
+    /// assert_eq!((n, m), (1, 2));
+    /// assert_ne!(first, second);
+    /// ```
+    pub fn build_with_monotonic_counter(
+        &self,
+        counter: &AtomicU64,
+        algorithm: HashAlgorithm,
+    ) -> (String, u64) {
+        let value = counter.fetch_add(1, Ordering::SeqCst).wrapping_add(1);
+        let input = format!(
+            "{}:{}",
+            value,
+            self.to_canonical_string_with(&SysinfoProvider::new())
+        );
+
+        (to_hex(&algorithm.digest(input.as_bytes())), value)
+    }
+
+    /// Hashes the Identifier and returns the first 128 bits of the hash.
+    /// # Arguments
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{CompactId, HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    ///
+    /// let bytes = builder.build().to_compact_binary_id(HashAlgorithm::SHA3_512);
+    ///
+    /// assert_eq!(CompactId::from_compact_binary_id(bytes).to_string().len(), 36);
+    /// ```
+    pub fn to_compact_binary_id(&self, algorithm: HashAlgorithm) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes.copy_from_slice(&self.hash(algorithm)[..16]);
+
+        bytes
+    }
+
+    /// Hashes the Identifier and returns exactly `bits` bits of the hash as hex.
+    /// # Arguments
+    /// * `bits` - The length of the output in bits, a multiple of 4 (one hex digit).
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Errors
+    /// Returns `UniqueIdError::InvalidLength` if `bits` is 0, not a multiple of 4 or greater than
+    /// `algorithm.output_bits()`.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// assert_eq!(identifier.build_fixed_length(128, HashAlgorithm::SHA3_256).unwrap().len(), 32);
+    /// assert!(identifier.build_fixed_length(512, HashAlgorithm::SHA3_256).is_err());
+    /// ```
+    pub fn build_fixed_length(
+        &self,
+        bits: u32,
+        algorithm: HashAlgorithm,
+    ) -> Result<String, UniqueIdError> {
+        let maximum = algorithm.output_bits();
+
+        if bits == 0 || !bits.is_multiple_of(4) || bits > maximum {
+            return Err(UniqueIdError::InvalidLength(bits, maximum));
+        }
+
+        let mut hex = to_hex(&self.hash(algorithm));
+        hex.truncate(bits as usize / 4);
+
+        Ok(hex)
+    }
+
+    /// Hashes the Identifier and returns the hex encoded hash in groups of 8 characters separated
+    /// by `-`, like a product key. (e.g. a1b2c3d4-e5f6a7b8-...)
+    /// # Arguments
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{normalize_human_readable_hash, HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// let hash = identifier.build_human_readable_hash(HashAlgorithm::SHA3_256);
+    ///
+    /// assert_eq!(hash.len(), 64 + 7);
+    /// assert_eq!(
+    ///     normalize_human_readable_hash(&hash),
+    ///     identifier.build_fixed_length(256, HashAlgorithm::SHA3_256).unwrap()
+    /// );
+    /// ```
+    pub fn build_human_readable_hash(&self, algorithm: HashAlgorithm) -> String {
+        let hex = to_hex(&self.hash(algorithm));
+
+        hex.as_bytes()
+            .chunks(HUMAN_READABLE_GROUP)
+            .map(|group| String::from_utf8_lossy(group).to_string())
+            .collect::<Vec<String>>()
+            .join("-")
+    }
+
+    /// Hashes the Identifier and returns the hash as unpadded base64url, usable as the `sub` claim
+    /// of a JWT. The claim is 43 characters long for 256 bit algorithms and 86 for 512 bit ones.
+    /// # Arguments
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{parse_from_jwt_claim, HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// let claim = identifier.build_for_jwt_claim(HashAlgorithm::SHA3_512);
+    ///
+    /// assert_eq!(claim.len(), 86);
+    /// assert_eq!(parse_from_jwt_claim(&claim).unwrap(), identifier.to_string(true));
+    /// ```
+    pub fn build_for_jwt_claim(&self, algorithm: HashAlgorithm) -> String {
+        encode_base64url(&self.hash(algorithm))
+    }
+
+    /// Hashes the Identifier and writes the hex encoded hash to the writer, without allocating the
+    /// canonical string or the hash string. The components are fed to the hasher as they are built.
+    /// # Arguments
+    /// * `writer` - The writer the hex encoded hash is written to, e.g. a socket or a file.
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Errors
+    /// Returns the error of the writer if the hash could not be written.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// let mut output = Vec::new();
+    /// identifier.build_streaming(&mut output, HashAlgorithm::SHA3_512).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(output).unwrap(), identifier.to_string(true));
+    /// ```
+    pub fn build_streaming<W: Write>(
+        &self,
+        writer: &mut W,
+        algorithm: HashAlgorithm,
+    ) -> std::io::Result<()> {
+        self.build_streaming_with(&SysinfoProvider::new(), writer, algorithm)
+    }
+
+    /// Hashes the Identifier built from the data of the given provider and writes the hex encoded
+    /// hash to the writer.
+    /// # Arguments
+    /// * `provider` - The HardwareProvider the data is collected from.
+    /// * `writer` - The writer the hex encoded hash is written to.
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Errors
+    /// Returns the error of the writer if the hash could not be written.
+    pub fn build_streaming_with<W: Write>(
+        &self,
+        provider: &dyn HardwareProvider,
+        writer: &mut W,
+        algorithm: HashAlgorithm,
+    ) -> std::io::Result<()> {
+        match algorithm {
+            HashAlgorithm::SHA3_256 => {
+                let mut hasher = Sha3_256::new();
+                self.feed_canonical(provider, &mut hasher);
+                write_hex(writer, &hasher.finalize())
+            }
+            HashAlgorithm::SHA3_512 => {
+                let mut hasher = Sha3_512::new();
+                self.feed_canonical(provider, &mut hasher);
+                write_hex(writer, &hasher.finalize())
+            }
+            // TupleHash256 absorbs the collected pairs as separate elements, not the string.
+            HashAlgorithm::TupleHash256 => write_hex(writer, &self.hash_with(provider, algorithm)),
+        }
+    }
+
+    /// Feeds the canonical string to the hasher piece by piece, as assembled by
+    /// `to_canonical_string_with`.
+    fn feed_canonical<D: Digest>(&self, provider: &dyn HardwareProvider, hasher: &mut D) {
+        hasher.update(FORMAT_PREFIX);
+        hasher.update(
+            self.options
+                .string_normalization
+                .format_version()
+                .to_string(),
+        );
+        hasher.update(":");
+        if let Some(name) = &self.name {
+            hasher.update(escape_name(name));
+        }
+        hasher.update("[");
+
+        let mut first = true;
+        for i in &self.data {
+            let component = i.build_with(provider, &self.options);
+            if component.is_empty() {
+                continue;
+            }
+
+            if !first {
+                hasher.update(", ");
+            }
+            hasher.update(component);
+            first = false;
+        }
+
+        hasher.update("]");
+    }
+
+    /// Returns the canonical string hashed by `build_sorted_stable`.
+    pub(crate) fn to_sorted_stable_string(&self) -> String {
+        let provider = SysinfoProvider::new();
+
+        let mut blocks: Vec<(String, String)> = self
+            .data
+            .iter()
+            .flat_map(|i| i.collect_with(&provider, &self.options))
+            .map(|mut block| {
+                block.data.sort_by(|a, b| a.key.cmp(&b.key));
+
+                let name = block.identifier.as_str().to_string();
+
+                let mut identifier_type = IdentifierTypeDataBuilder::new(block.identifier);
+                for item in block
+                    .data
+                    .into_iter()
+                    .filter(|item| !is_metadata(&item.key))
+                {
+                    identifier_type.add(item.key, item.value.trim().to_lowercase());
+                }
+
+                (name, identifier_type.build())
+            })
+            .collect();
+
+        blocks.sort();
+
+        let blocks: Vec<String> = blocks.into_iter().map(|(_, block)| block).collect();
+
+        format!(
+            "{}[{}]",
+            self.name.as_deref().unwrap_or_default(),
+            blocks.join(", ")
+        )
+    }
+}
+
+/// The number of hex characters in a group of `Identifier::build_human_readable_hash`.
+const HUMAN_READABLE_GROUP: usize = 8;
+
+/// Strips the separators and whitespace of a hash built with `Identifier::build_human_readable_hash`
+/// and lowercases it, so it can be compared to a hex encoded hash.
+/// # Arguments
+/// * `hash` - The hash, as typed or pasted by a user.
+/// # Examples
+/// ```
+/// use uniqueid::normalize_human_readable_hash;
+///
+/// assert_eq!(normalize_human_readable_hash(" A1B2C3D4-e5f6a7b8 \n"), "a1b2c3d4e5f6a7b8");
+/// ```
+pub fn normalize_human_readable_hash(hash: &str) -> String {
+    hash.chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// The lowercase hex digits, used by `Identifier::build_streaming`.
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Writes the bytes to the writer as lowercase hex, through a buffer on the stack.
+fn write_hex<W: Write>(writer: &mut W, bytes: &[u8]) -> std::io::Result<()> {
+    let mut buffer = [0u8; 128];
+
+    for chunk in bytes.chunks(buffer.len() / 2) {
+        for (i, byte) in chunk.iter().enumerate() {
+            buffer[2 * i] = HEX_DIGITS[(byte >> 4) as usize];
+            buffer[2 * i + 1] = HEX_DIGITS[(byte & 0x0F) as usize];
+        }
+        writer.write_all(&buffer[..chunk.len() * 2])?;
+    }
+
+    Ok(())
+}
+
+/// The unpadded base64url alphabet of RFC 4648, used by `Identifier::build_for_jwt_claim`.
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The maximum length of a claim accepted by `parse_from_jwt_claim`.
+const MAX_JWT_CLAIM: usize = 255;
+
+/// Encodes the bytes as unpadded base64url.
+fn encode_base64url(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let buffer = chunk.iter().enumerate().fold(0u32, |buffer, (i, byte)| {
+            buffer | (*byte as u32) << (16 - 8 * i)
+        });
+
+        for i in 0..=chunk.len() {
+            result.push(BASE64URL[(buffer >> (18 - 6 * i) & 0x3F) as usize] as char);
+        }
+    }
+
+    result
+}
+
+/// Validates a claim built with `Identifier::build_for_jwt_claim` and returns the hex encoded hash,
+/// as returned by `Identifier::to_string(true)` for SHA3-512.
+/// # Arguments
+/// * `claim` - The `sub` claim of the JWT.
+/// # Errors
+/// Returns `UniqueIdError::Parse` if the claim is longer than 255 characters, is not canonical
+/// unpadded base64url or does not decode to a 256 or 512 bit hash.
+/// # Examples
+/// ```
+/// use uniqueid::parse_from_jwt_claim;
+///
+/// let claim = "3q2-7wABAgMEBQYHCAkKCwwNDg8QERITFBUWFxgZGhs";
+///
+/// assert_eq!(
+///     parse_from_jwt_claim(claim).unwrap(),
+///     "deadbeef000102030405060708090a0b0c0d0e0f101112131415161718191a1b"
+/// );
+/// assert!(parse_from_jwt_claim("3q2+7w==").is_err());
+/// ```
+pub fn parse_from_jwt_claim(claim: &str) -> Result<String, UniqueIdError> {
+    if claim.len() > MAX_JWT_CLAIM {
+        return Err(UniqueIdError::Parse(format!(
+            "jwt claim longer than {} characters",
+            MAX_JWT_CLAIM
+        )));
+    }
+
+    let mut bytes = Vec::with_capacity(claim.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for c in claim.bytes() {
+        let value = BASE64URL.iter().position(|b| *b == c).ok_or_else(|| {
+            UniqueIdError::Parse(format!("invalid jwt claim character: {}", c as char))
+        })?;
+
+        buffer = (buffer << 6 | value as u32) & 0xFFFF;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    // The leftover bits of the last character are zero in a canonical encoding.
+    if buffer & ((1 << bits) - 1) != 0 {
+        return Err(UniqueIdError::Parse("non-canonical jwt claim".to_string()));
+    }
+
+    if bytes.len() != 32 && bytes.len() != 64 {
+        return Err(UniqueIdError::Parse(format!(
+            "jwt claim holds {} bytes, expected a 256 or 512 bit hash",
+            bytes.len()
+        )));
+    }
+
+    Ok(to_hex(&bytes))
+}
+
+/// The length of a degree of latitude in km, on a sphere with the mean radius of the Earth.
+const KM_PER_DEGREE: f64 = 111.195;
+
+/// Returns the grid cell of the coordinates, with cells `precision_km` high and about
+/// `precision_km` wide at the latitude of their row.
+fn geolocation_cell(lat: f64, lon: f64, precision_km: f64) -> (i64, i64) {
+    assert!(
+        lat.is_finite() && lon.is_finite(),
+        "Invalid coordinates: {}, {}",
+        lat,
+        lon
+    );
+    assert!(
+        precision_km.is_finite() && precision_km > 0.0,
+        "Invalid precision: {} km",
+        precision_km
+    );
+
+    let lat = lat.clamp(-90.0, 90.0);
+    let lon = (lon + 180.0).rem_euclid(360.0) - 180.0;
+
+    let lat_cell = (lat * KM_PER_DEGREE / precision_km).floor();
+
+    // The rows are narrower toward the poles, so the width of a column depends on the row only.
+    let row = ((lat_cell + 0.5) * precision_km / KM_PER_DEGREE).clamp(-90.0, 90.0);
+    let km_per_degree = KM_PER_DEGREE * row.to_radians().cos();
+    let lon_cell = (lon * km_per_degree / precision_km).floor();
+
+    (lat_cell as i64, lon_cell as i64)
+}
+
+/// Returns the number of leading zero bits of the bytes.
+fn leading_zero_bits(bytes: &[u8]) -> u32 {
+    let mut bits = 0;
+
+    for byte in bytes {
+        bits += byte.leading_zeros();
+
+        if *byte != 0 {
+            break;
+        }
+    }
+
+    bits
+}
+
+/// Finds the smallest nonce for which the hash of the input followed by the nonce starts with
+/// `difficulty` zero bits, and returns the hex encoded hash and the nonce.
+fn proof_of_work(input: &str, difficulty: u8, algorithm: HashAlgorithm) -> (String, u64) {
+    (0..)
+        .find_map(|nonce: u64| {
+            let hash = algorithm.digest(format!("{}{}", input, nonce).as_bytes());
+
+            (leading_zero_bits(&hash) >= difficulty as u32).then(|| (to_hex(&hash), nonce))
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IdentifierType, IdentifierTypeData, IdentifierTypeDataList};
+
+    fn list(identifier: IdentifierType, pairs: &[(&str, &str)]) -> IdentifierTypeDataList {
+        let mut list = IdentifierTypeDataList::new(identifier);
+        for (key, value) in pairs {
+            list.data.push(IdentifierTypeData::new(key, value));
+        }
+        list
+    }
+
+    #[test]
+    fn test_build_sorted_stable() {
+        let identifier = Identifier {
+            name: Some("test".to_string()),
+            data: vec![
+                list(IdentifierType::RAM, &[("t", "1024")]),
+                list(
+                    IdentifierType::CPU,
+                    &[("v", " GenuineIntel "), ("b", "Intel")],
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let reordered = Identifier {
+            name: Some("test".to_string()),
+            data: vec![
+                list(
+                    IdentifierType::CPU,
+                    &[("b", "intel"), ("v", "genuineintel")],
+                ),
+                list(IdentifierType::RAM, &[("t", "1024")]),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            identifier.to_sorted_stable_string(),
+            "test[CPU(b=intel, v=genuineintel), RAM(t=1024)]"
+        );
+        assert_eq!(
+            identifier.build_sorted_stable(HashAlgorithm::SHA3_512),
+            reordered.build_sorted_stable(HashAlgorithm::SHA3_512)
+        );
+        assert_ne!(
+            identifier.build_sorted_stable(HashAlgorithm::SHA3_512),
+            identifier.build_sorted_stable(HashAlgorithm::SHA3_256)
+        );
+    }
+
+    #[test]
+    fn test_build_with_version_prefix() {
+        let identifier = Identifier {
+            name: Some("test".to_string()),
+            data: vec![list(IdentifierType::RAM, &[("t", "1024")])],
+            ..Default::default()
+        };
+
+        let stored = identifier.build_with_version_prefix("0.2.6", HashAlgorithm::SHA3_256);
+        let versioned: VersionedIdentifier = stored.parse().unwrap();
+
+        assert_eq!(versioned.crate_version(), "0.2.6");
+        assert_eq!(
+            versioned.hash(),
+            to_hex(&HashAlgorithm::SHA3_256.digest(b"0.2.6:uid:v3:test[RAM(t=1024)]"))
+        );
+        assert_eq!(versioned.to_string(), stored);
+        assert_ne!(
+            identifier.build_with_version_prefix("0.3.0", HashAlgorithm::SHA3_256),
+            stored.replacen("0.2.6", "0.3.0", 1)
+        );
+        assert!("9f86d081".parse::<VersionedIdentifier>().is_err());
+    }
+
+    #[test]
+    fn test_compact_binary_id() {
+        let identifier = Identifier {
+            name: Some("test".to_string()),
+            data: vec![list(IdentifierType::RAM, &[("t", "1024")])],
+            ..Default::default()
+        };
+
+        let bytes = identifier.to_compact_binary_id(HashAlgorithm::SHA3_256);
+        let hash = HashAlgorithm::SHA3_256.digest(b"uid:v3:test[RAM(t=1024)]");
+        let id = CompactId::from_compact_binary_id(bytes);
+
+        assert_eq!(&bytes[..], &hash[..16]);
+        assert_eq!(id.as_bytes(), &bytes);
+        assert_eq!(id.to_string().replace('-', ""), to_hex(&hash[..16]));
+        assert_eq!(
+            id.to_string().split('-').map(str::len).collect::<Vec<_>>(),
+            vec![8, 4, 4, 4, 12]
+        );
+    }
+
+    #[test]
+    fn test_build_fixed_length() {
+        let identifier = Identifier {
+            name: Some("test".to_string()),
+            data: vec![list(IdentifierType::RAM, &[("t", "1024")])],
+            ..Default::default()
+        };
+
+        let hash = to_hex(&HashAlgorithm::SHA3_512.digest(b"uid:v3:test[RAM(t=1024)]"));
+
+        assert_eq!(
+            identifier.build_fixed_length(60, HashAlgorithm::SHA3_512),
+            Ok(hash[..15].to_string())
+        );
+        assert_eq!(
+            identifier.build_fixed_length(512, HashAlgorithm::SHA3_512),
+            Ok(hash)
+        );
+
+        for bits in [0, 63, 260] {
+            assert_eq!(
+                identifier.build_fixed_length(bits, HashAlgorithm::SHA3_256),
+                Err(UniqueIdError::InvalidLength(bits, 256))
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_human_readable_hash() {
+        let identifier = Identifier {
+            data: vec![list(IdentifierType::RAM, &[("t", "1024")])],
+            ..Default::default()
+        };
+
+        for algorithm in [HashAlgorithm::SHA3_256, HashAlgorithm::SHA3_512] {
+            let hash = identifier.build_human_readable_hash(algorithm);
+
+            assert!(hash.split('-').all(|group| group.len() == 8));
+            assert_eq!(
+                normalize_human_readable_hash(&hash.to_uppercase()),
+                to_hex(&identifier.hash(algorithm))
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_for_offline_license() {
+        let identifier = Identifier {
+            name: Some("test".to_string()),
+            data: vec![list(IdentifierType::RAM, &[("t", "1024")])],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            identifier.build_for_offline_license("LIC-1", HashAlgorithm::SHA3_256),
+            to_hex(&HashAlgorithm::SHA3_256.digest(b"LIC-1:uid:v3:test[RAM(t=1024)]"))
+        );
+        assert_ne!(
+            identifier.build_for_offline_license("LIC-1", HashAlgorithm::SHA3_256),
+            identifier.build_for_offline_license("LIC-2", HashAlgorithm::SHA3_256)
+        );
+    }
+
+    #[test]
+    fn test_build_with_monotonic_counter() {
+        let identifier = Identifier {
+            name: Some("test".to_string()),
+            data: vec![list(IdentifierType::RAM, &[("t", "1024")])],
+            ..Default::default()
+        };
+        let counter = AtomicU64::new(41);
+
+        assert_eq!(
+            identifier.build_with_monotonic_counter(&counter, HashAlgorithm::SHA3_256),
+            (
+                to_hex(&HashAlgorithm::SHA3_256.digest(b"42:uid:v3:test[RAM(t=1024)]")),
+                42
+            )
+        );
+        assert_eq!(counter.load(Ordering::SeqCst), 42);
+
+        let counter = AtomicU64::new(u64::MAX);
+
+        assert_eq!(
+            identifier
+                .build_with_monotonic_counter(&counter, HashAlgorithm::SHA3_256)
+                .1,
+            0
+        );
+    }
+
+    #[test]
+    fn test_proof_of_work() {
+        assert_eq!(leading_zero_bits(&[0x00, 0x00, 0x1f]), 19);
+        assert_eq!(leading_zero_bits(&[0x80]), 0);
+        assert_eq!(leading_zero_bits(&[0x00]), 8);
+
+        let (hash, nonce) = proof_of_work("test[RAM(t=1024)]", 12, HashAlgorithm::SHA3_256);
+        let expected =
+            HashAlgorithm::SHA3_256.digest(format!("test[RAM(t=1024)]{}", nonce).as_bytes());
+
+        assert_eq!(hash, to_hex(&expected));
+        assert!(leading_zero_bits(&expected) >= 12);
+        assert!((0..nonce).all(|smaller| {
+            let hash =
+                HashAlgorithm::SHA3_256.digest(format!("test[RAM(t=1024)]{}", smaller).as_bytes());
+            leading_zero_bits(&hash) < 12
+        }));
+
+        assert_eq!(
+            proof_of_work("test", 0, HashAlgorithm::SHA3_256),
+            (to_hex(&HashAlgorithm::SHA3_256.digest(b"test0")), 0)
+        );
+
+        let identifier = Identifier {
+            name: Some("test".to_string()),
+            data: vec![list(IdentifierType::RAM, &[("t", "1024")])],
+            ..Default::default()
+        };
+        assert_eq!(
+            identifier.build_with_proof_of_work(8, HashAlgorithm::SHA3_256),
+            proof_of_work("uid:v3:test[RAM(t=1024)]", 8, HashAlgorithm::SHA3_256)
+        );
+    }
+
+    #[test]
+    fn test_geolocation_cell() {
+        // 1 km cells: about 0.009 degrees of latitude.
+        assert_eq!(geolocation_cell(0.0, 0.0, 1.0), (0, 0));
+        assert_eq!(geolocation_cell(0.004, 0.004, 1.0), (0, 0));
+        assert_eq!(geolocation_cell(0.01, -0.004, 1.0), (1, -1));
+
+        // At 60 degrees a degree of longitude is half as long as at the equator.
+        assert_eq!(geolocation_cell(60.0, 1.5, 111.195).1, 0);
+        assert_eq!(geolocation_cell(0.0, 1.5, 111.195).1, 1);
+
+        // Out of range coordinates are clamped and wrapped.
+        assert_eq!(
+            geolocation_cell(95.0, 190.0, 10.0),
+            geolocation_cell(90.0, -170.0, 10.0)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid precision")]
+    fn test_geolocation_cell_invalid_precision() {
+        geolocation_cell(0.0, 0.0, 0.0);
+    }
+
+    #[test]
+    fn test_build_streaming() {
+        let provider = crate::FakeProvider::default();
+        let identifiers = [
+            Identifier::default(),
+            Identifier {
+                name: Some("test".to_string()),
+                data: vec![
+                    list(IdentifierType::RAM, &[("t", "1024")]),
+                    IdentifierTypeDataList::new(IdentifierType::Custom("SITE".to_string())),
+                    list(IdentifierType::CPU, &[("b", "intel"), ("c", "8")]),
+                ],
+                ..Default::default()
+            },
+        ];
+
+        for identifier in identifiers {
+            for algorithm in [
+                HashAlgorithm::SHA3_256,
+                HashAlgorithm::SHA3_512,
+                HashAlgorithm::TupleHash256,
+            ] {
+                let mut output = Vec::new();
+                identifier
+                    .build_streaming_with(&provider, &mut output, algorithm)
+                    .unwrap();
+
+                assert_eq!(
+                    String::from_utf8(output).unwrap(),
+                    to_hex(&identifier.hash_with(&provider, algorithm))
+                );
+            }
+        }
+
+        // The error of the writer is returned.
+        let mut full = [0u8; 16];
+        assert!(Identifier::default()
+            .build_streaming_with(&provider, &mut &mut full[..], HashAlgorithm::SHA3_256)
+            .is_err());
+    }
+
+    #[test]
+    fn test_jwt_claim() {
+        let identifier = Identifier {
+            name: Some("test".to_string()),
+            data: vec![list(IdentifierType::RAM, &[("t", "1024")])],
+            ..Default::default()
+        };
+
+        for algorithm in [HashAlgorithm::SHA3_256, HashAlgorithm::SHA3_512] {
+            let claim = identifier.build_for_jwt_claim(algorithm);
+
+            assert!(claim.len() <= MAX_JWT_CLAIM);
+            assert!(claim
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+            assert_eq!(
+                parse_from_jwt_claim(&claim).unwrap(),
+                to_hex(&identifier.hash(algorithm))
+            );
+        }
+
+        assert_eq!(encode_base64url(b"f"), "Zg");
+        assert_eq!(encode_base64url(b"foobar"), "Zm9vYmFy");
+        assert_eq!(encode_base64url(&[0xFB, 0xFF]), "-_8");
+
+        // Too long, padded, standard base64, non-canonical and not a hash.
+        let claim = "3q2-7wABAgMEBQYHCAkKCwwNDg8QERITFBUWFxgZGhs";
+        assert!(parse_from_jwt_claim(claim).is_ok());
+        assert!(parse_from_jwt_claim(&"A".repeat(256)).is_err());
+        assert!(parse_from_jwt_claim(&format!("{}=", claim)).is_err());
+        assert!(parse_from_jwt_claim(&claim.replace('-', "+")).is_err());
+        assert!(parse_from_jwt_claim(&claim.replace("Ghs", "Ght")).is_err());
+        assert!(parse_from_jwt_claim("Zm9vYmFy").is_err());
+    }
+
+    #[test]
+    fn test_private_fields_stripped() {
+        let provider = crate::FakeProvider::new(crate::FakeData {
+            total_memory: 1024,
+            processors: vec![crate::ProcessorInfo {
+                brand: "Intel".to_string(),
+                vendor: "GenuineIntel".to_string(),
+                frequency: 3600,
+            }],
+            ..Default::default()
+        });
+
+        let mut builder = crate::IdentifierBuilder::default();
+        builder.name("app");
+        builder.add_with_label(IdentifierType::RAM, "main");
+        builder.add(IdentifierType::CPU);
+        let identifier = builder.build();
+
+        let sha256 = |value: &str| to_hex(&Sha256::digest(value.as_bytes()));
+        let stripped = identifier.to_private_fields_stripped_string(&provider);
+
+        assert_eq!(
+            stripped,
+            format!(
+                "app[RAM(t={}), CPU(b={}, v={}, f={}, c={})]",
+                sha256("1024"),
+                sha256("intel"),
+                sha256("genuineintel"),
+                sha256("3600"),
+                sha256("1")
+            )
+        );
+        assert!(!stripped.contains("intel") && !stripped.contains("main"));
+    }
+}
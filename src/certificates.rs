@@ -0,0 +1,215 @@
+use std::collections::BTreeSet;
+
+use sha2::{Digest, Sha256};
+
+use crate::hash::{hash_value, to_hex};
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// The directory holding the installed CA certificates on Linux.
+const LINUX_CERTIFICATE_DIR: &str = "/etc/ssl/certs";
+
+/// The macOS keychains holding the system roots and the certificates installed by an administrator.
+const MACOS_KEYCHAINS: [&str; 2] = [
+    "/System/Library/Keychains/SystemRootCertificates.keychain",
+    "/Library/Keychains/System.keychain",
+];
+
+/// Decodes standard base64, ignoring whitespace. Returns None on invalid input.
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    let mut result = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for c in input.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            _ => return None,
+        };
+
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            result.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(result)
+}
+
+/// Returns the SHA-256 fingerprints of every PEM certificate in the input, as lowercase hex.
+pub(crate) fn pem_fingerprints(pem: &str) -> Vec<String> {
+    const BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+    const END: &str = "-----END CERTIFICATE-----";
+
+    pem.split(BEGIN)
+        .skip(1)
+        .filter_map(|block| decode_base64(block.split(END).next()?))
+        .map(|der| to_hex(&Sha256::digest(der)))
+        .collect()
+}
+
+/// Parses hex fingerprints printed one per line, or after a `SHA-256 hash:` label as printed by
+/// `security find-certificate -Z`, into lowercase hex.
+pub(crate) fn parse_fingerprint_lines(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let fingerprint = match line.split_once(':') {
+                Some(("SHA-256 hash", value)) => value.trim(),
+                Some(_) => return None,
+                None => line,
+            };
+
+            (fingerprint.len() == 64 && fingerprint.chars().all(|c| c.is_ascii_hexdigit()))
+                .then(|| fingerprint.to_lowercase())
+        })
+        .collect()
+}
+
+/// Reads the SHA-256 fingerprints of the installed CA certificates, without duplicates.
+fn fingerprints(provider: &dyn HardwareProvider) -> BTreeSet<String> {
+    if cfg!(target_os = "windows") {
+        let command = "Get-ChildItem Cert:\\LocalMachine\\Root | \
+                       ForEach-Object { $_.GetCertHashString('SHA256') }";
+
+        provider
+            .run_command("powershell", &["-NoProfile", "-Command", command])
+            .map(|output| parse_fingerprint_lines(&output))
+            .into_iter()
+            .flatten()
+            .collect()
+    } else if cfg!(target_os = "macos") {
+        MACOS_KEYCHAINS
+            .iter()
+            .filter_map(|keychain| {
+                provider.run_command("security", &["find-certificate", "-a", "-Z", keychain])
+            })
+            .flat_map(|output| parse_fingerprint_lines(&output))
+            .collect()
+    } else {
+        // The directory holds every certificate several times (hash links and a bundle).
+        provider
+            .read_dir(LINUX_CERTIFICATE_DIR)
+            .iter()
+            .filter_map(|file| {
+                provider.read_to_string(&format!("{}/{}", LINUX_CERTIFICATE_DIR, file))
+            })
+            .flat_map(|pem| pem_fingerprints(&pem))
+            .collect()
+    }
+}
+
+impl IdentifierTypeDataList {
+    /// Collects the number of installed CA certificates and a hash of their sorted fingerprints.
+    pub(crate) fn collect_enrolled_certificates(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        let fingerprints = fingerprints(provider);
+
+        if fingerprints.is_empty() {
+            return Vec::new();
+        }
+
+        let combined = fingerprints.iter().cloned().collect::<Vec<_>>().join("\n");
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::ENROLLED_CERTIFICATES);
+        block.push("n", &fingerprints.len().to_string());
+        block.push("h", &hash_value(combined));
+
+        vec![block]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierBuilder};
+
+    const ISRG_ROOT_X2: &str = include_str!("../tests/fixtures/certs/ISRG_Root_X2.pem");
+    const DIGICERT_GLOBAL_ROOT_G2: &str =
+        include_str!("../tests/fixtures/certs/DigiCert_Global_Root_G2.pem");
+
+    // The fingerprints printed by `openssl x509 -noout -fingerprint -sha256`.
+    const ISRG_ROOT_X2_FINGERPRINT: &str =
+        "69729b8e15a86efc177a57afb7171dfc64add28c2fca8cf1507e34453ccb1470";
+    const DIGICERT_GLOBAL_ROOT_G2_FINGERPRINT: &str =
+        "cb3ccbb76031e5e0138f8dd39a23f9de47ffc35e43c1144cea27d46a5ab1cb5f";
+
+    #[test]
+    fn test_pem_fingerprints() {
+        let bundle = format!("{}{}", ISRG_ROOT_X2, DIGICERT_GLOBAL_ROOT_G2);
+
+        assert_eq!(
+            pem_fingerprints(&bundle),
+            vec![
+                ISRG_ROOT_X2_FINGERPRINT,
+                DIGICERT_GLOBAL_ROOT_G2_FINGERPRINT
+            ]
+        );
+        assert!(pem_fingerprints("no certificates").is_empty());
+    }
+
+    #[test]
+    fn test_parse_fingerprint_lines() {
+        let security = format!(
+            "SHA-256 hash: {}\nSHA-1 hash: 0563B8630D62D75ABBC8AB1E4BDFB5A899B24D43\nkeychain: \"/Library/Keychains/System.keychain\"\n",
+            ISRG_ROOT_X2_FINGERPRINT.to_uppercase()
+        );
+
+        assert_eq!(
+            parse_fingerprint_lines(&security),
+            vec![ISRG_ROOT_X2_FINGERPRINT]
+        );
+        assert_eq!(
+            parse_fingerprint_lines(&format!("{}\r\n", DIGICERT_GLOBAL_ROOT_G2_FINGERPRINT)),
+            vec![DIGICERT_GLOBAL_ROOT_G2_FINGERPRINT]
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_enrolled_certificates() {
+        let file = |name: &str, pem: &str| {
+            (
+                format!("{}/{}", LINUX_CERTIFICATE_DIR, name),
+                pem.as_bytes().to_vec(),
+            )
+        };
+
+        let provider = FakeProvider::new(FakeData {
+            files: [
+                file("0b9bc432.0", ISRG_ROOT_X2),
+                file("ISRG_Root_X2.pem", ISRG_ROOT_X2),
+                file("DigiCert_Global_Root_G2.pem", DIGICERT_GLOBAL_ROOT_G2),
+                file(
+                    "ca-certificates.crt",
+                    &format!("{}{}", ISRG_ROOT_X2, DIGICERT_GLOBAL_ROOT_G2),
+                ),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::ENROLLED_CERTIFICATES);
+
+        let combined = format!(
+            "{}\n{}",
+            ISRG_ROOT_X2_FINGERPRINT, DIGICERT_GLOBAL_ROOT_G2_FINGERPRINT
+        );
+
+        assert_eq!(
+            builder.build().to_string_with(&provider, false),
+            format!("[ENROLLED_CERTIFICATES(n=2, h={})]", hash_value(combined))
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use crate::{HardwareProvider, IdentifierOptions, IdentifierType, IdentifierTypeDataList};
+
+/// The link-local address of the instance metadata services of AWS, Google Cloud and Azure.
+const METADATA_HOST: &str = "http://169.254.169.254";
+
+/// The lifetime requested for an IMDSv2 session token, in seconds.
+const AWS_TOKEN_TTL: &str = "21600";
+
+/// Splits a plain HTTP url into its host, port and path. (http://host:port/path)
+fn parse_url(url: &str) -> Option<(&str, u16, &str)> {
+    let rest = url.strip_prefix("http://")?;
+    let (authority, path) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, "/"),
+    };
+
+    match authority.split_once(':') {
+        Some((host, port)) => Some((host, port.parse().ok()?, path)),
+        None => Some((authority, 80, path)),
+    }
+}
+
+/// Parses an HTTP response, returning the body if the status is 200 OK.
+pub(crate) fn parse_http_response(response: &[u8]) -> Option<String> {
+    let response = String::from_utf8_lossy(response);
+    let (head, body) = response.split_once("\r\n\r\n")?;
+    let status = head.lines().next()?.split_whitespace().nth(1)?;
+
+    (status == "200").then(|| body.to_string())
+}
+
+/// Sends a plain HTTP/1.0 request, which the metadata services answer without chunked encoding,
+/// and returns the body of a 200 OK response. Nothing is returned after the timeout.
+pub(crate) fn http_request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    timeout: Duration,
+) -> Option<String> {
+    let deadline = Instant::now() + timeout;
+    let remaining = || {
+        deadline
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+    };
+
+    let (host, port, path) = parse_url(url)?;
+    let address = (host, port).to_socket_addrs().ok()?.next()?;
+
+    let mut stream = TcpStream::connect_timeout(&address, remaining()?).ok()?;
+    stream.set_write_timeout(Some(remaining()?)).ok()?;
+
+    let mut request = format!("{} {} HTTP/1.0\r\nHost: {}\r\n", method, path, host);
+    for (name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    request.push_str("Content-Length: 0\r\n\r\n");
+    stream.write_all(request.as_bytes()).ok()?;
+
+    let mut response = Vec::new();
+    let mut buffer = [0; 4096];
+    loop {
+        stream.set_read_timeout(Some(remaining()?)).ok()?;
+        match stream.read(&mut buffer).ok()? {
+            0 => break,
+            n => response.extend_from_slice(&buffer[..n]),
+        }
+    }
+
+    parse_http_response(&response)
+}
+
+/// Reads the id of an EC2 instance, with an IMDSv2 session token.
+fn aws(
+    provider: &dyn HardwareProvider,
+    remaining: &dyn Fn() -> Option<Duration>,
+) -> Option<String> {
+    let token = provider.http_request(
+        "PUT",
+        &format!("{}/latest/api/token", METADATA_HOST),
+        &[("X-aws-ec2-metadata-token-ttl-seconds", AWS_TOKEN_TTL)],
+        remaining()?,
+    )?;
+
+    provider.http_request(
+        "GET",
+        &format!("{}/latest/meta-data/instance-id", METADATA_HOST),
+        &[("X-aws-ec2-metadata-token", token.trim())],
+        remaining()?,
+    )
+}
+
+/// Reads the id of a Compute Engine instance.
+fn gcp(
+    provider: &dyn HardwareProvider,
+    remaining: &dyn Fn() -> Option<Duration>,
+) -> Option<String> {
+    provider.http_request(
+        "GET",
+        &format!("{}/computeMetadata/v1/instance/id", METADATA_HOST),
+        &[("Metadata-Flavor", "Google")],
+        remaining()?,
+    )
+}
+
+/// Reads the id of an Azure virtual machine.
+fn azure(
+    provider: &dyn HardwareProvider,
+    remaining: &dyn Fn() -> Option<Duration>,
+) -> Option<String> {
+    provider.http_request(
+        "GET",
+        &format!(
+            "{}/metadata/instance/compute/vmId?api-version=2021-02-01&format=text",
+            METADATA_HOST
+        ),
+        &[("Metadata", "true")],
+        remaining()?,
+    )
+}
+
+/// Queries the metadata services of AWS, Google Cloud and Azure in turn and returns the cloud
+/// provider and the id of the instance. Nothing is queried after the timeout.
+pub(crate) fn instance_id(
+    provider: &dyn HardwareProvider,
+    timeout: Duration,
+) -> Option<(&'static str, String)> {
+    type Source = fn(&dyn HardwareProvider, &dyn Fn() -> Option<Duration>) -> Option<String>;
+    const SOURCES: [(&str, Source); 3] = [("aws", aws), ("gcp", gcp), ("azure", azure)];
+
+    let deadline = Instant::now() + timeout;
+    let remaining = || {
+        deadline
+            .checked_duration_since(Instant::now())
+            .filter(|remaining| !remaining.is_zero())
+    };
+
+    SOURCES.iter().find_map(|(name, source)| {
+        source(provider, &remaining)
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .map(|id| (*name, id))
+    })
+}
+
+impl IdentifierTypeDataList {
+    /// Collects the cloud provider and the id of the instance from the local metadata service,
+    /// within `IdentifierOptions::cloud_timeout`, or nothing outside of a supported cloud.
+    pub(crate) fn collect_cloud(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+    ) -> Vec<IdentifierTypeDataList> {
+        match instance_id(provider, options.cloud_timeout) {
+            Some((name, id)) => {
+                let mut block = IdentifierTypeDataList::new(IdentifierType::CLOUD);
+                block.push("p", name);
+                block.push("id", &id);
+                vec![block]
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, UniqueIdError};
+
+    fn provider(responses: &[(&str, &str)]) -> FakeProvider {
+        FakeProvider::new(FakeData {
+            http: responses
+                .iter()
+                .map(|(request, body)| (request.to_string(), body.to_string()))
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_parse_http_response() {
+        assert_eq!(
+            parse_http_response(b"HTTP/1.0 200 OK\r\nContent-Type: text/plain\r\n\r\ni-0123"),
+            Some("i-0123".to_string())
+        );
+        assert_eq!(
+            parse_http_response(b"HTTP/1.1 401 Unauthorized\r\n\r\n"),
+            None
+        );
+        assert_eq!(parse_http_response(b""), None);
+        assert_eq!(
+            parse_url("http://169.254.169.254/latest/api/token"),
+            Some(("169.254.169.254", 80, "/latest/api/token"))
+        );
+        assert_eq!(
+            parse_url("http://localhost:8080"),
+            Some(("localhost", 8080, "/"))
+        );
+    }
+
+    #[test]
+    fn test_cloud() {
+        let data = IdentifierTypeDataList::new(IdentifierType::CLOUD);
+        let options = IdentifierOptions::default();
+
+        let aws = provider(&[
+            (
+                "PUT http://169.254.169.254/latest/api/token",
+                "AQAEAFq-token==",
+            ),
+            (
+                "GET http://169.254.169.254/latest/meta-data/instance-id",
+                "i-0123456789abcdef0",
+            ),
+        ]);
+        let gcp = provider(&[(
+            "GET http://169.254.169.254/computeMetadata/v1/instance/id",
+            "4520031799277581759",
+        )]);
+        let azure = provider(&[(
+            "GET http://169.254.169.254/metadata/instance/compute/vmId?api-version=2021-02-01&format=text",
+            "02aab8a4-74ef-476e-8182-f6d2ba4166a6",
+        )]);
+
+        assert_eq!(
+            data.build_with(&aws, &options),
+            "CLOUD(p=aws, id=i-0123456789abcdef0)"
+        );
+        assert_eq!(
+            data.build_with(&gcp, &options),
+            "CLOUD(p=gcp, id=4520031799277581759)"
+        );
+        assert_eq!(
+            data.build_with(&azure, &options),
+            "CLOUD(p=azure, id=02aab8a4-74ef-476e-8182-f6d2ba4166a6)"
+        );
+
+        // IMDSv1 only answers without a token, which is not supported.
+        let imdsv1 = provider(&[(
+            "GET http://169.254.169.254/latest/meta-data/instance-id",
+            "i-0123456789abcdef0",
+        )]);
+
+        for unavailable in [imdsv1, FakeProvider::default()] {
+            assert_eq!(
+                data.try_collect_with(&unavailable, &options),
+                Err(UniqueIdError::SourceUnavailable(IdentifierType::CLOUD))
+            );
+        }
+    }
+
+    #[test]
+    fn test_cloud_deadline() {
+        let options = IdentifierOptions {
+            cloud_timeout: Duration::ZERO,
+            ..Default::default()
+        };
+        let gcp = provider(&[(
+            "GET http://169.254.169.254/computeMetadata/v1/instance/id",
+            "4520031799277581759",
+        )]);
+
+        assert!(IdentifierTypeDataList::new(IdentifierType::CLOUD)
+            .collect_with(&gcp, &options)
+            .is_empty());
+
+        // Nothing listens on the discard port, so the request fails well within the deadline.
+        let start = Instant::now();
+        assert_eq!(
+            http_request(
+                "GET",
+                "http://127.0.0.1:9/",
+                &[],
+                Duration::from_millis(200)
+            ),
+            None
+        );
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+}
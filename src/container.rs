@@ -0,0 +1,375 @@
+use crate::virt::container;
+use crate::{
+    HardwareProvider, Identifier, IdentifierBuilder, IdentifierType, IdentifierTypeDataList,
+    SysinfoProvider, UniqueIdError,
+};
+
+/// The environment variable the pod UID is usually exposed in through the Kubernetes downward API.
+const POD_UID_VARIABLE: &str = "POD_UID";
+
+/// The directories of the container runtimes holding one directory per container id.
+const CONTAINER_DIRS: [&str; 3] = ["containers", "overlay-containers", "sandboxes"];
+
+/// Enum representing how an IdentifierBuilder handles running inside a container, where the
+/// hardware components describe the node shared by every container on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContainerPolicy {
+    /// Containers are not handled specially.
+    #[default]
+    Ignore,
+    /// The CONTAINER component is added, so every container has its own Identifier.
+    IncludeContainerId,
+    /// `IdentifierBuilder::try_build` fails inside a container.
+    FailIfContainerized,
+}
+
+/// The id of the current container and the UID of its Kubernetes pod.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct ContainerInfo {
+    /// The 64 hex digit id of the container.
+    pub id: Option<String>,
+    /// The UID of the pod. (e.g. 5d5a6c3e-7b8a-4c1d-9e2f-0a1b2c3d4e5f)
+    pub pod: Option<String>,
+}
+
+/// Returns true if the string is a 64 hex digit container id.
+fn is_container_id(id: &str) -> bool {
+    id.len() == 64 && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Returns true if the string is a hyphenated pod UID.
+fn is_pod_uid(uid: &str) -> bool {
+    uid.len() == 36 && uid.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+}
+
+/// Extracts the container id from the last component of a cgroup path.
+/// (docker/<id>, .../<id>, docker-<id>.scope, cri-containerd-<id>.scope, crio-<id>.scope)
+fn cgroup_container_id(path: &str) -> Option<String> {
+    let name = path.rsplit('/').next()?;
+    let name = name.strip_suffix(".scope").unwrap_or(name);
+    let id = name.rsplit('-').next()?;
+
+    is_container_id(id).then(|| id.to_lowercase())
+}
+
+/// Extracts the pod UID from a cgroup path. (pod<uid>, or kubepods-<qos>-pod<uid>.slice with the
+/// hyphens of the UID replaced by underscores)
+fn cgroup_pod_uid(path: &str) -> Option<String> {
+    path.split('/').find_map(|component| {
+        let component = component.strip_suffix(".slice").unwrap_or(component);
+        let (_, uid) = component.rsplit_once("pod")?;
+        let uid = uid.replace('_', "-");
+
+        is_pod_uid(&uid).then_some(uid)
+    })
+}
+
+/// Parses the container id and pod UID of a `/proc/self/cgroup` file, for cgroup v1 and v2.
+/// Inside a cgroup namespace, cgroup v2 shows `0::/` and nothing is found.
+pub(crate) fn parse_cgroup(cgroup: &str) -> ContainerInfo {
+    let paths: Vec<&str> = cgroup
+        .lines()
+        .filter_map(|line| line.splitn(3, ':').nth(2))
+        .collect();
+
+    ContainerInfo {
+        id: paths.iter().find_map(|path| cgroup_container_id(path)),
+        pod: paths.iter().find_map(|path| cgroup_pod_uid(path)),
+    }
+}
+
+/// Parses the container id and pod UID of a `/proc/self/mountinfo` file, from the files the
+/// runtime mounts into the container. (e.g. /var/lib/docker/containers/<id>/hostname)
+pub(crate) fn parse_mountinfo(mountinfo: &str) -> ContainerInfo {
+    let roots: Vec<Vec<&str>> = mountinfo
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(3))
+        .map(|root| root.split('/').collect())
+        .collect();
+
+    let after = |directories: &[&str], valid: fn(&str) -> bool| {
+        roots.iter().find_map(|components| {
+            components.windows(2).find_map(|pair| {
+                (directories.contains(&pair[0]) && valid(pair[1])).then(|| pair[1].to_lowercase())
+            })
+        })
+    };
+
+    ContainerInfo {
+        id: after(&CONTAINER_DIRS, is_container_id),
+        pod: after(&["pods"], is_pod_uid),
+    }
+}
+
+/// Parses a variable of a NUL separated `/proc/self/environ` file.
+fn parse_environ(environ: &str, name: &str) -> Option<String> {
+    environ
+        .split('\0')
+        .find_map(|variable| variable.strip_prefix(name)?.strip_prefix('='))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Reads the id of the current container and the UID of its pod, from the cgroup of the process
+/// and falling back to its mounts for cgroup v2 namespaces. The pod UID is read from the `POD_UID`
+/// environment variable first.
+pub(crate) fn container_info(provider: &dyn HardwareProvider) -> ContainerInfo {
+    let cgroup = provider
+        .read_to_string("/proc/self/cgroup")
+        .map(|cgroup| parse_cgroup(&cgroup))
+        .unwrap_or_default();
+    let mountinfo = provider
+        .read_to_string("/proc/self/mountinfo")
+        .map(|mountinfo| parse_mountinfo(&mountinfo))
+        .unwrap_or_default();
+    let environment = provider
+        .read_to_string("/proc/self/environ")
+        .and_then(|environ| parse_environ(&environ, POD_UID_VARIABLE));
+
+    ContainerInfo {
+        id: cgroup.id.or(mountinfo.id),
+        pod: environment.or(cgroup.pod).or(mountinfo.pod),
+    }
+}
+
+/// Returns true if the process runs inside a container.
+pub(crate) fn is_containerized(provider: &dyn HardwareProvider) -> bool {
+    container(provider).is_some() || container_info(provider) != ContainerInfo::default()
+}
+
+impl IdentifierBuilder {
+    /// Sets how running inside a container is handled. `IncludeContainerId` adds the CONTAINER
+    /// component, `FailIfContainerized` makes `try_build` fail inside a container. Any other
+    /// policy removes the CONTAINER component again.
+    /// # Arguments
+    /// * `policy` - The ContainerPolicy to use.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{ContainerPolicy, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::CPU);
+    /// builder.container_policy(ContainerPolicy::IncludeContainerId);
+    ///
+    /// assert_eq!(builder.data[1].identifier, IdentifierType::CONTAINER);
+    /// ```
+    pub fn container_policy(&mut self, policy: ContainerPolicy) -> &mut Self {
+        self.options.container_policy = policy;
+
+        let included = self
+            .data
+            .iter()
+            .any(|list| list.identifier == IdentifierType::CONTAINER);
+        if policy == ContainerPolicy::IncludeContainerId {
+            if !included {
+                self.add(IdentifierType::CONTAINER);
+            }
+        } else {
+            self.data
+                .retain(|list| list.identifier != IdentifierType::CONTAINER);
+        }
+
+        self
+    }
+
+    /// Builds the Identifier, applying the ContainerPolicy.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{ContainerPolicy, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::CPU);
+    /// builder.container_policy(ContainerPolicy::FailIfContainerized);
+    ///
+    /// match builder.try_build() {
+    ///     Ok(identifier) => println!("{}", identifier.to_string(true)),
+    ///     Err(error) => eprintln!("{}", error),
+    /// }
+    /// ```
+    /// # Errors
+    /// Returns `UniqueIdError::Containerized` with `ContainerPolicy::FailIfContainerized` if the
+    /// process runs inside a container.
+    pub fn try_build(self) -> Result<Identifier, UniqueIdError> {
+        self.try_build_with(&SysinfoProvider::new())
+    }
+
+    /// Builds the Identifier, detecting a container from the given provider, see `try_build`.
+    /// # Errors
+    /// Returns `UniqueIdError::Containerized` with `ContainerPolicy::FailIfContainerized` if the
+    /// process runs inside a container.
+    pub fn try_build_with(
+        self,
+        provider: &dyn HardwareProvider,
+    ) -> Result<Identifier, UniqueIdError> {
+        if self.options.container_policy == ContainerPolicy::FailIfContainerized
+            && is_containerized(provider)
+        {
+            return Err(UniqueIdError::Containerized);
+        }
+
+        Ok(self.build())
+    }
+}
+
+impl IdentifierTypeDataList {
+    /// Collects the id of the current container and the UID of its pod if known, `unknown` if the
+    /// container id is not found, or `none` outside of a container.
+    pub(crate) fn collect_container(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        let info = container_info(provider);
+        let mut block = IdentifierTypeDataList::new(IdentifierType::CONTAINER);
+
+        match info.id {
+            Some(id) => block.push("id", &id),
+            None if is_containerized(provider) => block.push("id", crate::UNKNOWN),
+            None => block.push("id", "none"),
+        };
+        if let Some(pod) = info.pod {
+            block.push("pod", &pod);
+        }
+
+        vec![block]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierOptions};
+
+    const CGROUP_DOCKER: &str = include_str!("../tests/fixtures/proc/cgroup_docker.txt");
+    const CGROUP_CONTAINERD: &str = include_str!("../tests/fixtures/proc/cgroup_containerd.txt");
+    const CGROUP_CRIO: &str = include_str!("../tests/fixtures/proc/cgroup_crio.txt");
+    const MOUNTINFO_DOCKER_CGROUPV2: &str =
+        include_str!("../tests/fixtures/proc/mountinfo_docker_cgroupv2.txt");
+
+    const DOCKER_ID: &str = "3f2a6f1b8c9d0e1f2a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f8091";
+
+    fn info(id: &str, pod: Option<&str>) -> ContainerInfo {
+        ContainerInfo {
+            id: Some(id.to_string()),
+            pod: pod.map(str::to_string),
+        }
+    }
+
+    fn file(path: &str, contents: &str) -> (String, Vec<u8>) {
+        (path.to_string(), contents.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_parse_cgroup() {
+        assert_eq!(parse_cgroup(CGROUP_DOCKER), info(DOCKER_ID, None));
+        assert_eq!(
+            parse_cgroup(CGROUP_CONTAINERD),
+            info(
+                "9b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c",
+                Some("5d5a6c3e-7b8a-4c1d-9e2f-0a1b2c3d4e5f")
+            )
+        );
+        assert_eq!(
+            parse_cgroup(CGROUP_CRIO),
+            info(
+                "e4d5c6b7a8f9e0d1c2b3a4f5e6d7c8b9a0f1e2d3c4b5a6f7e8d9c0b1a2f3e4d5",
+                Some("1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c5d")
+            )
+        );
+        assert_eq!(parse_cgroup("0::/\n"), ContainerInfo::default());
+        assert_eq!(
+            parse_cgroup("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            ContainerInfo::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_mountinfo() {
+        assert_eq!(
+            parse_mountinfo(MOUNTINFO_DOCKER_CGROUPV2),
+            info(DOCKER_ID, None)
+        );
+        assert_eq!(
+            parse_mountinfo(
+                "701 690 259:2 /var/lib/kubelet/pods/5d5a6c3e-7b8a-4c1d-9e2f-0a1b2c3d4e5f/etc-hosts \
+                 /etc/hosts rw,relatime - ext4 /dev/nvme0n1p2 rw\n"
+            ),
+            ContainerInfo {
+                id: None,
+                pod: Some("5d5a6c3e-7b8a-4c1d-9e2f-0a1b2c3d4e5f".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_container() {
+        let data = IdentifierTypeDataList::new(IdentifierType::CONTAINER);
+        let options = IdentifierOptions::default();
+
+        let v2 = FakeProvider::new(FakeData {
+            files: [
+                file("/proc/self/cgroup", "0::/\n"),
+                file("/proc/self/mountinfo", MOUNTINFO_DOCKER_CGROUPV2),
+                file(
+                    "/proc/self/environ",
+                    "PATH=/usr/bin\0POD_UID=0f1e2d3c-4b5a-4968-8776-655443322110\0",
+                ),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            data.build_with(&v2, &options),
+            format!(
+                "CONTAINER(id={}, pod=0f1e2d3c-4b5a-4968-8776-655443322110)",
+                DOCKER_ID
+            )
+        );
+        assert_eq!(
+            data.build_with(&FakeProvider::default(), &options),
+            "CONTAINER(id=none)"
+        );
+    }
+
+    #[test]
+    fn test_container_policy() {
+        let docker = FakeProvider::new(FakeData {
+            files: [file("/proc/self/cgroup", CGROUP_DOCKER)].into(),
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::CPU);
+        builder.container_policy(ContainerPolicy::FailIfContainerized);
+
+        assert_eq!(
+            builder.clone().try_build_with(&docker),
+            Err(UniqueIdError::Containerized)
+        );
+        assert!(builder
+            .clone()
+            .try_build_with(&FakeProvider::default())
+            .is_ok());
+
+        builder.container_policy(ContainerPolicy::IncludeContainerId);
+        builder.container_policy(ContainerPolicy::IncludeContainerId);
+
+        let identifier = builder.clone().try_build_with(&docker).unwrap();
+
+        assert_eq!(identifier.data.len(), 2);
+        assert_eq!(identifier.data[1].identifier, IdentifierType::CONTAINER);
+
+        builder.container_policy(ContainerPolicy::Ignore);
+
+        let identifier = builder.clone().try_build_with(&docker).unwrap();
+
+        assert_eq!(identifier.data.len(), 1);
+        assert_eq!(identifier.data[0].identifier, IdentifierType::CPU);
+
+        builder.container_policy(ContainerPolicy::IncludeContainerId);
+        builder.container_policy(ContainerPolicy::FailIfContainerized);
+
+        assert_eq!(builder.data.len(), 1);
+    }
+}
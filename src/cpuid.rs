@@ -0,0 +1,232 @@
+use crate::hash::hash_value;
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// The CPUID leaf returning the highest supported standard leaf.
+const STANDARD_MAX_LEAF: u32 = 0;
+/// The CPUID leaf returning the processor signature and the original feature flags.
+const SIGNATURE_LEAF: u32 = 1;
+/// The CPUID leaf returning the structured extended feature flags.
+const EXTENDED_FEATURES_LEAF: u32 = 7;
+/// The CPUID leaf returning the highest supported extended leaf.
+const EXTENDED_MAX_LEAF: u32 = 0x8000_0000;
+/// The CPUID leaf returning the physical and virtual address sizes.
+const ADDRESS_SIZES_LEAF: u32 = 0x8000_0008;
+
+/// Leaf 1 ECX bits not describing the CPU: OSXSAVE (27, set by the OS) and hypervisor (31).
+const SIGNATURE_ECX_VOLATILE: u32 = (1 << 27) | (1 << 31);
+/// Leaf 7 EDX bits added by microcode updates: MD_CLEAR (10), IBRS/IBPB (26), STIBP (27),
+/// L1D_FLUSH (28), ARCH_CAPABILITIES (29), CORE_CAPABILITIES (30) and SSBD (31).
+const EXTENDED_EDX_VOLATILE: u32 = (1 << 10) | (0x3f << 26);
+
+/// The signature and feature flags of the CPU read with CPUID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CpuSignature {
+    /// The display family. (base family plus extended family for family 0xf)
+    pub family: u32,
+    /// The display model. (including the extended model for families 0x6 and 0xf)
+    pub model: u32,
+    /// The stepping.
+    pub stepping: u32,
+    /// A short hex digest of the feature flags of leaves 1 and 7, without the volatile bits.
+    pub features: String,
+}
+
+/// Returns true if the CPU supports the given extended CPUID leaf.
+fn supports_extended_leaf(provider: &dyn HardwareProvider, leaf: u32) -> bool {
+    provider
+        .cpuid(EXTENDED_MAX_LEAF, 0)
+        .is_some_and(|max| max.eax >= leaf)
+}
+
+/// Parses the `address sizes` line of `/proc/cpuinfo`. (e.g. 39 bits physical, 48 bits virtual)
+pub(crate) fn parse_cpuinfo_address_sizes(cpuinfo: &str) -> Option<(u32, u32)> {
+    let (_, sizes) = cpuinfo
+        .lines()
+        .find(|line| line.starts_with("address sizes"))?
+        .split_once(':')?;
+
+    let mut physical = None;
+    let mut virtual_ = None;
+
+    for size in sizes.split(',') {
+        let mut parts = size.split_whitespace();
+        let bits = parts.next()?.parse().ok();
+
+        match parts.last() {
+            Some("physical") => physical = bits,
+            Some("virtual") => virtual_ = bits,
+            _ => {}
+        }
+    }
+
+    Some((physical?, virtual_?))
+}
+
+/// Returns the (physical, virtual) address sizes in bits, from CPUID leaf 0x80000008 with the
+/// `cpuid` feature on x86 and from `/proc/cpuinfo` otherwise.
+pub(crate) fn address_sizes(provider: &dyn HardwareProvider) -> Option<(u32, u32)> {
+    if supports_extended_leaf(provider, ADDRESS_SIZES_LEAF) {
+        if let Some(sizes) = provider.cpuid(ADDRESS_SIZES_LEAF, 0) {
+            return Some((sizes.eax & 0xff, (sizes.eax >> 8) & 0xff));
+        }
+    }
+
+    parse_cpuinfo_address_sizes(&provider.read_to_string("/proc/cpuinfo")?)
+}
+
+/// Reads the signature and feature flags of the CPU with CPUID leaves 1 and 7, or None without
+/// CPUID (non-x86 targets).
+///
+/// The family, model and stepping are fixed for a given CPU. The feature digest is stable across
+/// microcode updates as the bits they toggle are masked out, but it changes when the hypervisor
+/// exposes a different feature set to a virtual machine, such as after a live migration.
+pub(crate) fn cpu_signature(provider: &dyn HardwareProvider) -> Option<CpuSignature> {
+    let max_leaf = provider.cpuid(STANDARD_MAX_LEAF, 0)?.eax;
+    if max_leaf < SIGNATURE_LEAF {
+        return None;
+    }
+
+    let signature = provider.cpuid(SIGNATURE_LEAF, 0)?;
+    let base_family = (signature.eax >> 8) & 0xf;
+    let base_model = (signature.eax >> 4) & 0xf;
+
+    let family = match base_family {
+        0xf => base_family + ((signature.eax >> 20) & 0xff),
+        _ => base_family,
+    };
+    let model = match base_family {
+        0x6 | 0xf => (((signature.eax >> 16) & 0xf) << 4) + base_model,
+        _ => base_model,
+    };
+
+    let mut flags = vec![signature.ecx & !SIGNATURE_ECX_VOLATILE, signature.edx];
+
+    if max_leaf >= EXTENDED_FEATURES_LEAF {
+        if let Some(extended) = provider.cpuid(EXTENDED_FEATURES_LEAF, 0) {
+            flags.extend([
+                extended.ebx,
+                extended.ecx,
+                extended.edx & !EXTENDED_EDX_VOLATILE,
+            ]);
+        }
+    }
+
+    let bytes: Vec<u8> = flags.iter().flat_map(|flag| flag.to_le_bytes()).collect();
+
+    Some(CpuSignature {
+        family,
+        model,
+        stepping: signature.eax & 0xf,
+        features: hash_value(bytes)[..16].to_string(),
+    })
+}
+
+impl IdentifierTypeDataList {
+    /// Collects the physical and virtual address sizes of the CPU in bits.
+    pub(crate) fn collect_virtual_address_size(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        let (physical, virtual_) = match address_sizes(provider) {
+            Some(sizes) => sizes,
+            None => return Vec::new(),
+        };
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::VIRTUAL_ADDRESS_SIZE);
+        block.push("p", &physical.to_string());
+        block.push("v", &virtual_.to_string());
+
+        vec![block]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CpuidLeaf, FakeData, FakeProvider, IdentifierBuilder};
+
+    fn leaf(leaf: u32, eax: u32) -> CpuidLeaf {
+        CpuidLeaf {
+            leaf,
+            eax,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_address_sizes() {
+        // 5-level paging: 52 bits physical, 57 bits virtual.
+        let provider = FakeProvider::new(FakeData {
+            cpuid: vec![
+                leaf(EXTENDED_MAX_LEAF, ADDRESS_SIZES_LEAF),
+                leaf(ADDRESS_SIZES_LEAF, 0x3934),
+            ],
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::VIRTUAL_ADDRESS_SIZE);
+
+        assert_eq!(
+            builder.build().to_string_with(&provider, false),
+            "[VIRTUAL_ADDRESS_SIZE(p=52, v=57)]"
+        );
+
+        // Without the leaf, /proc/cpuinfo is read instead.
+        provider.update(|data| {
+            data.cpuid = vec![leaf(EXTENDED_MAX_LEAF, 0x8000_0004)];
+            data.files.insert(
+                "/proc/cpuinfo".to_string(),
+                b"processor\t: 0\naddress sizes\t: 39 bits physical, 48 bits virtual\n".to_vec(),
+            );
+        });
+
+        assert_eq!(address_sizes(&provider), Some((39, 48)));
+        assert_eq!(address_sizes(&FakeProvider::default()), None);
+    }
+
+    #[test]
+    fn test_cpu_signature() {
+        // Alder Lake: family 6, extended model 9, model 7, stepping 2.
+        let signature = |ecx: u32, edx_7: u32| {
+            FakeProvider::new(FakeData {
+                cpuid: vec![
+                    leaf(STANDARD_MAX_LEAF, 0x20),
+                    CpuidLeaf {
+                        ecx,
+                        edx: 0xbfebfbff,
+                        ..leaf(SIGNATURE_LEAF, 0x00090672)
+                    },
+                    CpuidLeaf {
+                        ebx: 0x239c27eb,
+                        edx: edx_7,
+                        ..leaf(EXTENDED_FEATURES_LEAF, 0)
+                    },
+                ],
+                ..Default::default()
+            })
+        };
+
+        let before = cpu_signature(&signature(0x7ffafbbf, 0)).unwrap();
+        // A microcode update adding IBRS/IBPB and a kernel enabling XSAVE.
+        let after = cpu_signature(&signature(0x7ffafbbf | (1 << 27), 1 << 26)).unwrap();
+
+        assert_eq!((before.family, before.model, before.stepping), (6, 0x97, 2));
+        assert_eq!(before.features.len(), 16);
+        assert_eq!(before, after);
+        assert_ne!(
+            before.features,
+            cpu_signature(&signature(0x7ffafbbe, 0)).unwrap().features
+        );
+        assert_eq!(cpu_signature(&FakeProvider::default()), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "cpuid", target_arch = "x86_64"))]
+    fn test_address_sizes_on_system() {
+        let (physical, virtual_) = address_sizes(&crate::SysinfoProvider::new()).unwrap();
+
+        assert!(physical >= 32);
+        assert!(virtual_ >= 48);
+    }
+}
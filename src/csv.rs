@@ -0,0 +1,221 @@
+use crate::{
+    HardwareProvider, Identifier, IdentifierType, IdentifierTypeDataList, SysinfoProvider,
+    UniqueIdError,
+};
+
+/// The header of the CSV rows written by `Identifier::to_csv_row`.
+const HEADER: [&str; 4] = ["name", "type", "key", "value"];
+
+/// Quotes a CSV field if it contains a comma, a quote or a line break. (RFC 4180)
+fn escape_field(field: &str) -> String {
+    match field.contains([',', '"', '\r', '\n']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+/// Splits CSV text into records of fields, unquoting quoted fields. (RFC 4180)
+fn parse_records(csv: &str) -> Result<Vec<Vec<String>>, UniqueIdError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(UniqueIdError::Parse(
+            "unclosed quoted CSV field".to_string(),
+        ));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+impl Identifier {
+    /// Builds the Identifier as CSV rows with the `name,type,key,value` columns, one row per
+    /// key-value pair, each ending with a line break. Labels are written as `_label` pairs.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.name("web-01");
+    /// builder.add(IdentifierType::RAM);
+    ///
+    /// assert!(builder.build().to_csv_row().starts_with("web-01,RAM,t,"));
+    /// ```
+    pub fn to_csv_row(&self) -> String {
+        self.to_csv_row_with(&SysinfoProvider::new())
+    }
+
+    /// Builds the Identifier as CSV rows from the data of the given provider, see `to_csv_row`.
+    pub fn to_csv_row_with(&self, provider: &dyn HardwareProvider) -> String {
+        let name = escape_field(self.name.as_deref().unwrap_or_default());
+        let mut result = String::new();
+
+        for block in self
+            .data
+            .iter()
+            .flat_map(|i| i.collect_with(provider, &self.options))
+        {
+            for item in &block.data {
+                result.push_str(&format!(
+                    "{},{},{},{}\n",
+                    name,
+                    escape_field(block.identifier.as_str()),
+                    escape_field(&item.key),
+                    escape_field(&item.value)
+                ));
+            }
+        }
+
+        result
+    }
+
+    /// Reads Identifiers back from the CSV rows written by `to_csv_row`, with or without the
+    /// `name,type,key,value` header. Consecutive rows with the same name form one Identifier, and
+    /// a new component starts when the type changes or a key repeats, such as for every disk.
+    /// # Arguments
+    /// * `csv` - The CSV rows.
+    /// # Examples
+    /// ```
+    /// use uniqueid::Identifier;
+    ///
+    /// let csv = "name,type,key,value\nweb-01,RAM,t,16384\nweb-02,RAM,t,32768\n";
+    /// let identifiers = Identifier::from_csv_rows(csv).unwrap();
+    ///
+    /// assert_eq!(identifiers.len(), 2);
+    /// assert_eq!(identifiers[1].name, Some("web-02".to_string()));
+    /// ```
+    /// # Errors
+    /// Returns `UniqueIdError::Parse` if a row does not have 4 fields or a quoted field is not
+    /// closed, and `UniqueIdError::InvalidTag` if a type is not valid.
+    pub fn from_csv_rows(csv: &str) -> Result<Vec<Identifier>, UniqueIdError> {
+        let mut records = parse_records(csv)?;
+
+        if records.first().is_some_and(|record| record == &HEADER) {
+            records.remove(0);
+        }
+
+        let mut result: Vec<Identifier> = Vec::new();
+
+        for (i, record) in records.into_iter().enumerate() {
+            let [name, identifier, key, value]: [String; 4] = record.try_into().map_err(|_| {
+                UniqueIdError::Parse(format!("CSV row {} does not have 4 fields", i + 1))
+            })?;
+            let name = (!name.is_empty()).then_some(name);
+            let identifier: IdentifierType = identifier.parse()?;
+
+            let same_name = result.last().is_some_and(|last| last.name == name);
+            if !same_name {
+                result.push(Identifier {
+                    name,
+                    ..Default::default()
+                });
+            }
+            let data = &mut result.last_mut().unwrap().data;
+
+            let same_block = data.last().is_some_and(|block| {
+                block.identifier == identifier && block.data.iter().all(|item| item.key != key)
+            });
+            if !same_block {
+                data.push(IdentifierTypeDataList::new(identifier));
+            }
+            data.last_mut().unwrap().push(key, value);
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DiskInfo, FakeData, FakeProvider, IdentifierBuilder, IdentifierOptions};
+
+    #[test]
+    fn test_csv_round_trip() {
+        let disk = |device: &str, total_space: u64| DiskInfo {
+            device: device.to_string(),
+            mount_point: "/".to_string(),
+            total_space,
+            ..Default::default()
+        };
+        let provider = FakeProvider::new(FakeData {
+            total_memory: 1024,
+            disks: vec![disk("/dev/sda1", 1024), disk("/dev/sdb1", 2048)],
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.name("web \"01\", eu");
+        builder.add_with_label(IdentifierType::RAM, "main");
+        builder.add(IdentifierType::DISK);
+        let identifier = builder.build();
+
+        let csv = identifier.to_csv_row_with(&provider);
+
+        assert_eq!(
+            csv,
+            "\"web \"\"01\"\", eu\",RAM,t,1024\n\
+             \"web \"\"01\"\", eu\",RAM,_label,main\n\
+             \"web \"\"01\"\", eu\",DISK,t,1024\n\
+             \"web \"\"01\"\", eu\",DISK,t,2048\n"
+        );
+
+        let parsed = Identifier::from_csv_rows(&format!("name,type,key,value\r\n{}", csv)).unwrap();
+
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].name, identifier.name);
+        assert_eq!(
+            parsed[0].get_raw_components_with(&provider),
+            identifier.get_raw_components_with(&provider)
+        );
+        assert_eq!(parsed[0].data.len(), 3);
+        assert_eq!(parsed[0].options, IdentifierOptions::default());
+    }
+
+    #[test]
+    fn test_from_csv_rows_errors() {
+        assert_eq!(Identifier::from_csv_rows(""), Ok(Vec::new()));
+        assert_eq!(
+            Identifier::from_csv_rows("a,RAM,t\n"),
+            Err(UniqueIdError::Parse(
+                "CSV row 1 does not have 4 fields".to_string()
+            ))
+        );
+        assert_eq!(
+            Identifier::from_csv_rows("a,RAM,t,\"1024\n"),
+            Err(UniqueIdError::Parse(
+                "unclosed quoted CSV field".to_string()
+            ))
+        );
+        assert!(matches!(
+            Identifier::from_csv_rows("a,not a tag,t,1\n"),
+            Err(UniqueIdError::InvalidTag(_))
+        ));
+    }
+}
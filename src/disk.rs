@@ -0,0 +1,794 @@
+use crate::wmi;
+use crate::{
+    is_partition_of, DiskDetail, DiskInfo, HardwareProvider, IdentifierOptions, IdentifierType,
+    IdentifierTypeDataList, UNKNOWN,
+};
+
+/// The directory holding the block devices on Linux.
+const LINUX_BLOCK_DIR: &str = "/sys/block";
+/// The directory holding the udev database on Linux.
+const LINUX_UDEV_DATA_DIR: &str = "/run/udev/data";
+
+/// The network file systems, whose shares are listed as disks by some platforms.
+const NETWORK_FILE_SYSTEMS: [&str; 12] = [
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smbfs",
+    "smb3",
+    "fuse.sshfs",
+    "sshfs",
+    "9p",
+    "afs",
+    "ceph",
+    "glusterfs",
+    "webdav",
+];
+
+/// The pseudo file systems, which are not backed by a drive. (e.g. snap packages on squashfs)
+const PSEUDO_FILE_SYSTEMS: [&str; 8] = [
+    "tmpfs", "devtmpfs", "ramfs", "squashfs", "proc", "sysfs", "autofs", "devfs",
+];
+
+/// A struct selecting the disks included in the DISK component by their file system.
+///
+/// The default policy leaves out network shares (e.g. nfs, cifs, fuse.sshfs) and pseudo file
+/// systems (e.g. tmpfs, squashfs), so mounting a share or installing a snap package does not
+/// change the Identifier. File systems are matched case-insensitively.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DiskPolicy {
+    /// The file systems never included, in addition to the network and pseudo file systems.
+    pub exclude: Vec<String>,
+    /// Whether network and pseudo file systems are included as well. (default: false)
+    pub include_network: bool,
+}
+
+impl DiskPolicy {
+    /// Adds a file system never included.
+    /// # Examples
+    /// ```
+    /// use uniqueid::DiskPolicy;
+    ///
+    /// let mut policy = DiskPolicy::default();
+    /// policy.exclude("fuse.rclone");
+    ///
+    /// assert_eq!(policy.exclude, vec!["fuse.rclone"]);
+    /// ```
+    pub fn exclude<T: Into<String>>(&mut self, file_system: T) -> &mut Self {
+        self.exclude.push(file_system.into());
+        self
+    }
+
+    /// Returns true if the file system is a network file system.
+    /// # Examples
+    /// ```
+    /// use uniqueid::DiskPolicy;
+    ///
+    /// assert!(DiskPolicy::is_network("NFS4"));
+    /// assert!(!DiskPolicy::is_network("tmpfs"));
+    /// assert!(!DiskPolicy::is_network("ext4"));
+    /// ```
+    pub fn is_network(file_system: &str) -> bool {
+        let file_system = file_system.trim().to_lowercase();
+
+        NETWORK_FILE_SYSTEMS.contains(&file_system.as_str())
+    }
+
+    /// Returns true if the file system is a pseudo file system not backed by a disk, such as
+    /// `tmpfs` or the `squashfs` images of snaps.
+    /// # Examples
+    /// ```
+    /// use uniqueid::DiskPolicy;
+    ///
+    /// assert!(DiskPolicy::is_virtual("tmpfs"));
+    /// assert!(!DiskPolicy::is_virtual("NFS4"));
+    /// assert!(!DiskPolicy::is_virtual("ext4"));
+    /// ```
+    pub fn is_virtual(file_system: &str) -> bool {
+        let file_system = file_system.trim().to_lowercase();
+
+        PSEUDO_FILE_SYSTEMS.contains(&file_system.as_str())
+    }
+
+    /// Returns true if the disk is included by the policy.
+    pub fn includes(&self, disk: &DiskInfo) -> bool {
+        let file_system = disk.file_system.trim();
+
+        if self
+            .exclude
+            .iter()
+            .any(|excluded| excluded.trim().eq_ignore_ascii_case(file_system))
+        {
+            return false;
+        }
+
+        self.include_network
+            || !(DiskPolicy::is_network(file_system) || DiskPolicy::is_virtual(file_system))
+    }
+}
+
+/// The model and serial number of the drive holding a disk, where the platform exposes them.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct DriveInfo {
+    /// The model of the drive. (e.g. Samsung SSD 980 PRO 1TB)
+    pub model: Option<String>,
+    /// The serial number of the drive.
+    pub serial: Option<String>,
+}
+
+/// Returns the trimmed value, or None if it is empty.
+fn non_empty(value: &str) -> Option<String> {
+    Some(value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+/// Returns the value of a property in a udev database entry. (E:ID_SERIAL_SHORT=...)
+fn udev_property(entry: &str, name: &str) -> Option<String> {
+    entry
+        .lines()
+        .find_map(|line| {
+            line.strip_prefix("E:")?
+                .strip_prefix(name)?
+                .strip_prefix('=')
+        })
+        .and_then(non_empty)
+}
+
+/// Reads the drive holding the device (e.g. /dev/nvme0n1p2) from sysfs, falling back to the udev
+/// database for drives which do not expose their serial number in sysfs, such as SATA drives.
+pub(crate) fn linux_drive(provider: &dyn HardwareProvider, device: &str) -> DriveInfo {
+    let name = device.trim_start_matches("/dev/");
+
+    let block = match provider
+        .read_dir(LINUX_BLOCK_DIR)
+        .into_iter()
+        .find(|block| is_partition_of(name, block))
+    {
+        Some(block) => block,
+        None => return DriveInfo::default(),
+    };
+
+    let read =
+        |file: &str| provider.read_to_string(&format!("{}/{}/{}", LINUX_BLOCK_DIR, block, file));
+    let udev = read("dev").and_then(|dev| {
+        provider.read_to_string(&format!("{}/b{}", LINUX_UDEV_DATA_DIR, dev.trim()))
+    });
+    let udev = |name: &str| udev.as_deref().and_then(|entry| udev_property(entry, name));
+
+    DriveInfo {
+        model: read("device/model")
+            .and_then(|model| non_empty(&model))
+            .or_else(|| udev("ID_MODEL")),
+        serial: read("device/serial")
+            .and_then(|serial| non_empty(&serial))
+            .or_else(|| udev("ID_SERIAL_SHORT")),
+    }
+}
+
+/// Parses the `Get-Disk` instance of the drive holding a partition.
+pub(crate) fn parse_get_disk(records: &[wmi::WmiRecord]) -> DriveInfo {
+    let field = |name: &str| {
+        records
+            .first()
+            .and_then(|record| record.get(name))
+            .and_then(|value| non_empty(value))
+    };
+
+    DriveInfo {
+        model: field("Model"),
+        serial: field("SerialNumber"),
+    }
+}
+
+/// Reads the drive holding the volume mounted at the given drive letter root. (e.g. C:\)
+fn windows_drive(provider: &dyn HardwareProvider, mount_point: &str) -> DriveInfo {
+    let letter = match mount_point.chars().next() {
+        Some(letter) if letter.is_ascii_alphabetic() => letter,
+        _ => return DriveInfo::default(),
+    };
+
+    let command = format!(
+        "Get-Partition -DriveLetter {} | Get-Disk | Format-List Model,SerialNumber",
+        letter
+    );
+
+    provider
+        .run_command("powershell", &["-NoProfile", "-Command", &command])
+        .map(|output| parse_get_disk(&wmi::parse_format_list(&output)))
+        .unwrap_or_default()
+}
+
+/// Reads the drive holding the disk. Platforms without a source yield an empty DriveInfo.
+fn drive(provider: &dyn HardwareProvider, disk: &DiskInfo) -> DriveInfo {
+    if cfg!(target_os = "windows") {
+        windows_drive(provider, &disk.mount_point)
+    } else if cfg!(target_os = "linux") {
+        linux_drive(provider, &disk.device)
+    } else {
+        DriveInfo::default()
+    }
+}
+
+/// Reads the number of the physical disk holding the volume mounted at the given drive letter
+/// root, the number `IOCTL_STORAGE_GET_DEVICE_NUMBER` returns. (e.g. C:\ -> 0)
+fn windows_disk_number(provider: &dyn HardwareProvider, mount_point: &str) -> Option<String> {
+    let letter = mount_point
+        .chars()
+        .next()
+        .filter(|letter| letter.is_ascii_alphabetic())?;
+    let command = format!("(Get-Partition -DriveLetter {}).DiskNumber", letter);
+
+    provider
+        .run_command("powershell", &["-NoProfile", "-Command", &command])
+        .map(|output| output.trim().to_string())
+        .filter(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+        .map(|number| format!("PhysicalDrive{}", number))
+}
+
+/// Returns the physical disk holding a volume, or None if the platform does not tell.
+/// (e.g. /dev/nvme0n1p2 -> /dev/nvme0n1, C:\ -> PhysicalDrive0)
+pub(crate) fn physical_disk(provider: &dyn HardwareProvider, disk: &DiskInfo) -> Option<String> {
+    if cfg!(target_os = "windows") {
+        windows_disk_number(provider, &disk.mount_point)
+    } else if cfg!(target_os = "linux") {
+        let name = disk.device.trim_start_matches("/dev/");
+
+        provider
+            .read_dir(LINUX_BLOCK_DIR)
+            .into_iter()
+            .find(|block| is_partition_of(name, block))
+            .map(|block| format!("/dev/{}", block))
+    } else {
+        None
+    }
+}
+
+/// Groups the volumes by their physical disk, returning the first volume of each disk and the
+/// summed space of its distinct volumes, sorted by total space. Volumes whose physical disk is
+/// unknown are returned on their own.
+pub(crate) fn group_by_physical_disk(
+    provider: &dyn HardwareProvider,
+    disks: Vec<DiskInfo>,
+) -> Vec<(DiskInfo, u64)> {
+    let mut groups: Vec<(String, Vec<DiskInfo>)> = Vec::new();
+
+    for disk in disks {
+        let key = physical_disk(provider, &disk).unwrap_or_else(|| disk.device.clone());
+
+        match groups.iter_mut().find(|(group, _)| *group == key) {
+            // A volume mounted more than once is counted once.
+            Some((_, volumes)) if volumes.iter().any(|v| v.device == disk.device) => {}
+            Some((_, volumes)) => volumes.push(disk),
+            None => groups.push((key, vec![disk])),
+        }
+    }
+
+    let mut result: Vec<(String, DiskInfo, u64)> = groups
+        .into_iter()
+        .map(|(key, volumes)| {
+            let total_space = volumes.iter().map(|volume| volume.total_space).sum();
+            (key, volumes.into_iter().next().unwrap(), total_space)
+        })
+        .collect();
+
+    result.sort_by(|a, b| (a.2, &a.0).cmp(&(b.2, &b.0)));
+    result
+        .into_iter()
+        .map(|(_, disk, total_space)| (disk, total_space))
+        .collect()
+}
+
+/// Returns the kind of drive holding a disk. NVMe drives are told apart by their device name on
+/// Linux, where they are reported as SSDs.
+pub(crate) fn disk_kind(disk: &DiskInfo) -> &'static str {
+    if cfg!(target_os = "linux") && disk.device.trim_start_matches("/dev/").starts_with("nvme") {
+        "nvme"
+    } else {
+        disk.kind.as_str()
+    }
+}
+
+/// Builds the DISK block of a disk with the given total space.
+///
+/// With `DiskDetail::Full` the model (or the device name if the model is unknown) and the serial
+/// number of the drive are included. The serial number is left out if it can not be read, such as
+/// without the permissions to query the drive.
+///
+/// With `IdentifierOptions::disk_mount_info` the file system (`fs`, lowercase ASCII) and the
+/// escaped mount point (`mp`) are appended.
+pub(crate) fn disk_block(
+    provider: &dyn HardwareProvider,
+    options: &IdentifierOptions,
+    disk: &DiskInfo,
+    total_space: u64,
+) -> IdentifierTypeDataList {
+    let mut block = IdentifierTypeDataList::new(IdentifierType::DISK);
+
+    if options.disk_detail == DiskDetail::Full {
+        let drive = drive(provider, disk);
+        let model = drive
+            .model
+            .or_else(|| non_empty(&disk.device))
+            .unwrap_or_else(|| UNKNOWN.to_string());
+
+        block.push("m", &model);
+
+        if let Some(serial) = drive.serial {
+            block.push("s", &serial);
+        }
+    }
+
+    block.push("t", &total_space.to_string());
+
+    if options.disk_kind {
+        block.push("k", disk_kind(disk));
+    }
+
+    if options.disk_mount_info {
+        let file_system: String = disk
+            .file_system
+            .trim()
+            .chars()
+            .filter(char::is_ascii)
+            .collect::<String>()
+            .to_ascii_lowercase();
+
+        block.push(
+            "fs",
+            &non_empty(&file_system).unwrap_or_else(|| UNKNOWN.to_string()),
+        );
+        block.push("mp", &disk.mount_point);
+    }
+
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DiskGrouping, FakeData, FakeProvider, Identifier, IdentifierBuilder, MissingDisks,
+    };
+
+    fn file(path: &str, contents: &str) -> (String, Vec<u8>) {
+        (path.to_string(), contents.as_bytes().to_vec())
+    }
+
+    fn provider() -> FakeProvider {
+        FakeProvider::new(FakeData {
+            disks: vec![
+                DiskInfo {
+                    device: "/dev/nvme0n1p2".to_string(),
+                    mount_point: "/".to_string(),
+                    total_space: 100,
+                    removable: false,
+                    ..Default::default()
+                },
+                DiskInfo {
+                    device: "/dev/sda1".to_string(),
+                    mount_point: "/home".to_string(),
+                    total_space: 200,
+                    removable: false,
+                    ..Default::default()
+                },
+                DiskInfo {
+                    device: "/dev/sdb1".to_string(),
+                    mount_point: "/mnt/data".to_string(),
+                    total_space: 400,
+                    removable: false,
+                    ..Default::default()
+                },
+            ],
+            files: [
+                file(
+                    "/sys/block/nvme0n1/device/model",
+                    "Samsung SSD 980 PRO 1TB \n",
+                ),
+                file("/sys/block/nvme0n1/device/serial", "S5GXNF0R123456A\n"),
+                file("/sys/block/sda/device/model", "WDC WD40EFRX-68N\n"),
+                file("/sys/block/sda/dev", "8:0\n"),
+                file(
+                    "/run/udev/data/b8:0",
+                    "S:disk/by-id/ata-WDC_WD40EFRX-68N_WD-WCC7K0123456\n\
+                     E:ID_MODEL=WDC_WD40EFRX-68N\nE:ID_SERIAL_SHORT=WD-WCC7K0123456\n",
+                ),
+                file("/sys/block/sdb/dev", "8:16\n"),
+            ]
+            .into(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_linux_drive() {
+        let provider = provider();
+
+        assert_eq!(
+            linux_drive(&provider, "/dev/sda1"),
+            DriveInfo {
+                model: Some("WDC WD40EFRX-68N".to_string()),
+                serial: Some("WD-WCC7K0123456".to_string()),
+            }
+        );
+        assert_eq!(linux_drive(&provider, "/dev/sdb1"), DriveInfo::default());
+        assert_eq!(linux_drive(&provider, "overlay"), DriveInfo::default());
+    }
+
+    #[test]
+    fn test_parse_get_disk() {
+        let output = "\r\n\r\nModel        : Samsung SSD 980 PRO 1TB\r\n\
+                      SerialNumber : 0025_3851_1234_5678.\r\n\r\n";
+
+        assert_eq!(
+            parse_get_disk(&wmi::parse_format_list(output)),
+            DriveInfo {
+                model: Some("Samsung SSD 980 PRO 1TB".to_string()),
+                serial: Some("0025_3851_1234_5678.".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_disk_detail() {
+        let provider = provider();
+
+        let build = |detail: DiskDetail| {
+            let mut builder = IdentifierBuilder::default();
+            builder.add(IdentifierType::DISK);
+            builder.options(IdentifierOptions {
+                disk_detail: detail,
+                ..Default::default()
+            });
+            builder.build().to_string_with(&provider, false)
+        };
+
+        assert_eq!(
+            build(DiskDetail::SizeOnly),
+            "[DISK(t=100)DISK(t=200)DISK(t=400)]"
+        );
+        assert_eq!(
+            build(DiskDetail::Full),
+            "[DISK(m=Samsung SSD 980 PRO 1TB, s=S5GXNF0R123456A, t=100)\
+             DISK(m=WDC WD40EFRX-68N, s=WD-WCC7K0123456, t=200)\
+             DISK(m=/dev/sdb1, t=400)]"
+        );
+    }
+
+    #[test]
+    fn test_disk_mount_info_round_trip() {
+        let provider = FakeProvider::new(FakeData {
+            disks: vec![
+                DiskInfo {
+                    device: "/dev/sda1".to_string(),
+                    mount_point: "/".to_string(),
+                    file_system: "EXT4".to_string(),
+                    total_space: 100,
+                    ..Default::default()
+                },
+                DiskInfo {
+                    device: "/dev/sdb1".to_string(),
+                    mount_point: "/mnt/a=b (old)".to_string(),
+                    total_space: 200,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::DISK);
+        builder.options(IdentifierOptions {
+            disk_mount_info: true,
+            ..Default::default()
+        });
+
+        let output = builder.build().to_string_with(&provider, false);
+
+        assert_eq!(
+            output,
+            "[DISK(t=100, fs=ext4, mp=/)DISK(t=200, fs=unknown, mp=/mnt/a%3Db (old))]"
+        );
+
+        let parsed: Identifier = output.parse().unwrap();
+
+        assert_eq!(parsed.data.len(), 2);
+        assert_eq!(parsed.data[1].data[2].value, "/mnt/a=b (old)");
+        assert_eq!(
+            format!("[{}{}]", parsed.data[0].build(), parsed.data[1].build()),
+            output
+        );
+    }
+
+    #[test]
+    fn test_disk_kind() {
+        let disk = |device: &str, kind: sysinfo::DiskType| DiskInfo {
+            device: device.to_string(),
+            total_space: 100,
+            kind: kind.into(),
+            ..Default::default()
+        };
+        let provider = FakeProvider::new(FakeData {
+            disks: vec![
+                disk("/dev/sda1", sysinfo::DiskType::HDD),
+                disk("/dev/sdb1", sysinfo::DiskType::SSD),
+                disk("/dev/sdc1", sysinfo::DiskType::Unknown(-1)),
+                disk("/dev/sdd1", sysinfo::DiskType::Unknown(4)),
+            ],
+            ..Default::default()
+        });
+
+        let build = |kind: bool| {
+            let mut builder = IdentifierBuilder::default();
+            builder.add(IdentifierType::DISK);
+            builder.options(IdentifierOptions {
+                disk_kind: kind,
+                ..Default::default()
+            });
+            builder.build().to_string_with(&provider, false)
+        };
+
+        assert_eq!(
+            build(false),
+            "[DISK(t=100)DISK(t=100)DISK(t=100)DISK(t=100)]"
+        );
+        assert_eq!(
+            build(true),
+            "[DISK(t=100, k=hdd)DISK(t=100, k=ssd)DISK(t=100, k=unknown)DISK(t=100, k=unknown)]"
+        );
+
+        let nvme = disk("/dev/nvme0n1p2", sysinfo::DiskType::SSD);
+        let expected = if cfg!(target_os = "linux") {
+            "nvme"
+        } else {
+            "ssd"
+        };
+
+        assert_eq!(disk_kind(&nvme), expected);
+    }
+
+    #[test]
+    fn test_disk_policy() {
+        let disk = |device: &str, file_system: &str, total_space: u64| DiskInfo {
+            device: device.to_string(),
+            mount_point: "/".to_string(),
+            file_system: file_system.to_string(),
+            total_space,
+            ..Default::default()
+        };
+        let provider = FakeProvider::new(FakeData {
+            disks: vec![
+                disk("/dev/sda1", "ext4", 100),
+                disk("nas:/export/home", "nfs4", 200),
+                disk("//nas/share", "CIFS", 300),
+                disk("user@host:/srv", "fuse.sshfs", 400),
+                disk("/dev/loop3", "squashfs", 500),
+                disk("remote:", "fuse.rclone", 600),
+                disk("/dev/sdb1", "xfs", 700),
+            ],
+            ..Default::default()
+        });
+
+        let build = |policy: DiskPolicy| {
+            let mut builder = IdentifierBuilder::default();
+            builder.add(IdentifierType::DISK);
+            builder.options(IdentifierOptions {
+                disk_policy: policy,
+                ..Default::default()
+            });
+            builder.build().to_string_with(&provider, false)
+        };
+
+        assert_eq!(
+            build(DiskPolicy::default()),
+            "[DISK(t=100)DISK(t=600)DISK(t=700)]"
+        );
+
+        let mut extended = DiskPolicy::default();
+        extended.exclude("FUSE.rclone");
+
+        assert_eq!(build(extended), "[DISK(t=100)DISK(t=700)]");
+        assert_eq!(
+            build(DiskPolicy {
+                include_network: true,
+                ..Default::default()
+            }),
+            "[DISK(t=100)DISK(t=200)DISK(t=300)DISK(t=400)DISK(t=500)DISK(t=600)DISK(t=700)]"
+        );
+    }
+
+    #[test]
+    fn test_disk_order() {
+        let disk = |device: &str, total_space: u64| DiskInfo {
+            device: device.to_string(),
+            mount_point: "/".to_string(),
+            total_space,
+            ..Default::default()
+        };
+        let disks = [
+            disk("/dev/sda1", 500),
+            disk("/dev/sdb1", 250),
+            disk("/dev/sdc1", 500),
+            disk("/dev/nvme0n1p2", 1000),
+        ];
+
+        let build = |order: [usize; 4]| {
+            let provider = FakeProvider::new(FakeData {
+                disks: order.iter().map(|i| disks[*i].clone()).collect(),
+                ..Default::default()
+            });
+            let mut builder = IdentifierBuilder::default();
+            builder.add(IdentifierType::DISK);
+            builder.options(IdentifierOptions {
+                disk_detail: DiskDetail::Full,
+                ..Default::default()
+            });
+            builder.build().to_string_with(&provider, true)
+        };
+
+        let expected = build([0, 1, 2, 3]);
+        for order in [[3, 2, 1, 0], [1, 0, 3, 2], [2, 3, 0, 1], [0, 2, 1, 3]] {
+            assert_eq!(build(order), expected);
+        }
+
+        let provider = FakeProvider::new(FakeData {
+            disks: disks.iter().rev().cloned().collect(),
+            ..Default::default()
+        });
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::DISK);
+
+        assert_eq!(
+            builder.build().to_string_with(&provider, false),
+            "[DISK(t=250)DISK(t=500)DISK(t=500)DISK(t=1000)]"
+        );
+    }
+
+    #[test]
+    fn test_missing_disks() {
+        let provider = FakeProvider::new(FakeData {
+            total_memory: 1024,
+            ..Default::default()
+        });
+        let build = |missing_disks: MissingDisks| {
+            let mut builder = IdentifierBuilder::default();
+            builder.name("test");
+            builder.add(IdentifierType::DISK);
+            builder.add(IdentifierType::RAM);
+            builder.options(IdentifierOptions {
+                missing_disks,
+                ..Default::default()
+            });
+            builder.build()
+        };
+
+        assert_eq!(
+            build(MissingDisks::Marker).to_string_with(&provider, false),
+            "test[DISK(none=1), RAM(t=1024)]"
+        );
+        assert_eq!(
+            build(MissingDisks::Error).to_string_with(&provider, false),
+            "test[RAM(t=1024)]"
+        );
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::DISK).try_collect_with(
+                &provider,
+                &IdentifierOptions {
+                    missing_disks: MissingDisks::Error,
+                    ..Default::default()
+                }
+            ),
+            Err(crate::UniqueIdError::SourceUnavailable(
+                IdentifierType::DISK
+            ))
+        );
+
+        // Booted from USB: the only disk is removable.
+        provider.update(|data| {
+            data.disks.push(DiskInfo {
+                device: "/dev/sdb1".to_string(),
+                mount_point: "/".to_string(),
+                total_space: 32,
+                removable: true,
+                ..Default::default()
+            })
+        });
+        assert_eq!(
+            build(MissingDisks::Marker).to_string_with(&provider, false),
+            "test[DISK(none=1), RAM(t=1024)]"
+        );
+
+        // A component without any block can not eat the brackets or leave a separator.
+        let identifier = build(MissingDisks::Error);
+        assert_eq!(
+            Identifier {
+                data: identifier.data[..1].to_vec(),
+                ..identifier.clone()
+            }
+            .to_string_with(&provider, false),
+            "test[]"
+        );
+        assert_eq!(
+            Identifier {
+                name: None,
+                data: identifier.data[..1].to_vec(),
+                ..identifier
+            }
+            .to_string_with(&provider, false),
+            "[]"
+        );
+        assert_eq!(Identifier::default().to_string_with(&provider, false), "[]");
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_disk_grouping() {
+        let volume = |device: &str, mount_point: &str, total_space: u64| DiskInfo {
+            device: device.to_string(),
+            mount_point: mount_point.to_string(),
+            total_space,
+            ..Default::default()
+        };
+        let volumes = |root: u64, home: u64| {
+            FakeProvider::new(FakeData {
+                disks: vec![
+                    volume("/dev/nvme0n1p2", "/", root),
+                    volume("/dev/nvme0n1p3", "/home", home),
+                    volume("/dev/nvme0n1p3", "/var/lib/docker", home),
+                    volume("/dev/sda1", "/mnt/data", 400),
+                    volume("/dev/mapper/vg-backup", "/backup", 50),
+                ],
+                files: [
+                    file("/sys/block/nvme0n1/dev", "259:0\n"),
+                    file("/sys/block/sda/dev", "8:0\n"),
+                ]
+                .into(),
+                ..Default::default()
+            })
+        };
+        let build = |grouping: DiskGrouping, provider: &FakeProvider| {
+            let mut builder = IdentifierBuilder::default();
+            builder.add(IdentifierType::DISK);
+            builder.options(IdentifierOptions {
+                disk_grouping: grouping,
+                ..Default::default()
+            });
+            builder.build().to_string_with(provider, false)
+        };
+
+        assert_eq!(
+            build(DiskGrouping::Volume, &volumes(100, 900)),
+            "[DISK(t=50)DISK(t=100)DISK(t=400)DISK(t=900)DISK(t=900)]"
+        );
+        assert_eq!(
+            build(DiskGrouping::PhysicalDisk, &volumes(100, 900)),
+            "[DISK(t=50)DISK(t=400)DISK(t=1000)]"
+        );
+
+        // Resizing the partitions of the NVMe drive keeps the grouped component.
+        assert_eq!(
+            build(DiskGrouping::PhysicalDisk, &volumes(300, 700)),
+            build(DiskGrouping::PhysicalDisk, &volumes(100, 900))
+        );
+    }
+
+    #[test]
+    fn test_windows_disk_number() {
+        let provider = FakeProvider::new(FakeData {
+            commands: [(
+                "powershell -NoProfile -Command (Get-Partition -DriveLetter D).DiskNumber"
+                    .to_string(),
+                "1\r\n".to_string(),
+            )]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            windows_disk_number(&provider, "D:\\"),
+            Some("PhysicalDrive1".to_string())
+        );
+        assert_eq!(windows_disk_number(&provider, "E:\\"), None);
+    }
+}
@@ -0,0 +1,917 @@
+use crate::machine::{normalize_machine_id, parse_ioreg_property};
+use crate::wmi::{self, WmiRecord};
+use crate::{
+    HardwareProvider, IdentifierOptions, IdentifierType, IdentifierTypeDataList, UniqueIdError,
+    UNKNOWN,
+};
+
+/// The directory exposing the SMBIOS strings on Linux.
+const LINUX_DMI_DIR: &str = "/sys/class/dmi/id";
+
+/// The value a placeholder string left by the manufacturer is replaced with.
+pub(crate) const UNPROVISIONED: &str = "unprovisioned";
+
+/// Placeholder strings manufacturers leave in unprovisioned SMBIOS fields, compared case-insensitively.
+const PLACEHOLDERS: [&str; 9] = [
+    "to be filled by o.e.m.",
+    "default string",
+    "none",
+    "not specified",
+    "not applicable",
+    "n/a",
+    "o.e.m.",
+    "system serial number",
+    "base board serial number",
+];
+
+/// System UUIDs shipped by cheap motherboards, shared by every board of the same model.
+/// The first one is also stored with its first three fields byte-swapped.
+const BOGUS_SYSTEM_UUIDS: [&str; 4] = [
+    "03000200-0400-0500-0006-000700080009",
+    "00020003-0004-0005-0006-000700080009",
+    "ffffffff-ffff-ffff-ffff-ffffffffffff",
+    "00000000-0000-0000-0000-000000000000",
+];
+
+/// A single memory slot read from the SMBIOS memory device table.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub(crate) struct MemoryModule {
+    /// The slot locator. (e.g. DIMM_A1)
+    pub locator: String,
+    /// The capacity of the installed module in MB, 0 for an empty slot.
+    pub capacity: u64,
+    /// The maximum speed of the installed module in MT/s, 0 if unknown.
+    pub speed: u64,
+    /// The manufacturer of the installed module. (e.g. Samsung)
+    pub manufacturer: Option<String>,
+    /// The part number of the installed module. (e.g. M471A2K43DB1-CWE)
+    pub part_number: Option<String>,
+    /// The serial number of the installed module.
+    pub serial: Option<String>,
+}
+
+/// The baseboard read from the SMBIOS baseboard table. Fields are None if they could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct BoardInfo {
+    /// The manufacturer of the baseboard. (e.g. ASUSTeK COMPUTER INC.)
+    pub manufacturer: Option<String>,
+    /// The product name of the baseboard. (e.g. ROG STRIX B550-F GAMING)
+    pub product: Option<String>,
+    /// The serial number of the baseboard.
+    pub serial: Option<String>,
+}
+
+/// The firmware read from the SMBIOS BIOS table. Fields are None if they could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct BiosInfo {
+    /// The vendor of the firmware. (e.g. American Megatrends Inc.)
+    pub vendor: Option<String>,
+    /// The version of the firmware. (e.g. 2803)
+    pub version: Option<String>,
+    /// The release date of the firmware as YYYY-MM-DD.
+    pub date: Option<String>,
+}
+
+/// Normalizes a SMBIOS string. Missing values become `unknown` and placeholders left by the
+/// manufacturer (blank, all zeros, `To be filled by O.E.M.`, ...) become `unprovisioned`.
+pub(crate) fn normalize_smbios_string(value: Option<&str>) -> String {
+    let value = match value {
+        Some(value) => value.trim(),
+        None => return UNKNOWN.to_string(),
+    };
+
+    let placeholder = value.is_empty()
+        || value.chars().all(|c| c == '0')
+        || PLACEHOLDERS.contains(&value.to_lowercase().as_str());
+
+    match placeholder {
+        true => UNPROVISIONED.to_string(),
+        false => value.to_string(),
+    }
+}
+
+/// Converts a SMBIOS date (MM/DD/YYYY) to YYYY-MM-DD. Other formats are returned unchanged.
+pub(crate) fn normalize_smbios_date(date: &str) -> String {
+    let date = date.trim();
+    let parts: Vec<&str> = date.split('/').collect();
+
+    match parts[..] {
+        [month, day, year] if year.len() == 4 => {
+            format!("{}-{:0>2}-{:0>2}", year, month, day)
+        }
+        _ => date.to_string(),
+    }
+}
+
+/// Splits `dmidecode` output into the records of the given title, as lists of (key, value) pairs.
+fn dmidecode_records<'a>(output: &'a str, title: &str) -> Vec<Vec<(&'a str, &'a str)>> {
+    let mut records = Vec::new();
+    let mut current: Option<Vec<(&str, &str)>> = None;
+
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            records.extend(current.take());
+
+            if line.trim() == title {
+                current = Some(Vec::new());
+            }
+            continue;
+        }
+
+        if let (Some(record), Some((key, value))) = (current.as_mut(), line.split_once(':')) {
+            record.push((key.trim(), value.trim()));
+        }
+    }
+
+    records.extend(current);
+    records
+}
+
+/// Parses a size in MB as printed by `dmidecode` and `system_profiler`. (e.g. 8 GB, 8192 MB)
+/// Returns 0 for anything else, such as `No Module Installed`.
+pub(crate) fn parse_size(size: &str) -> u64 {
+    let mut parts = size.split_whitespace();
+    let value: u64 = match parts.next().and_then(|value| value.parse().ok()) {
+        Some(value) => value,
+        None => return 0,
+    };
+
+    match parts.next() {
+        Some("kB") => value / 1024,
+        Some("GB") => value * 1024,
+        Some("TB") => value * 1024 * 1024,
+        _ => value,
+    }
+}
+
+/// Parses the memory devices of `dmidecode -t 17` output.
+pub(crate) fn parse_dmidecode_memory(output: &str) -> Vec<MemoryModule> {
+    dmidecode_records(output, "Memory Device")
+        .into_iter()
+        .map(|record| {
+            let optional = |name: &str| {
+                record
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| *value)
+            };
+            let field = |name: &str| optional(name).unwrap_or_default();
+
+            MemoryModule {
+                locator: field("Locator").to_string(),
+                capacity: parse_size(field("Size")),
+                speed: field("Speed")
+                    .split_whitespace()
+                    .next()
+                    .and_then(|speed| speed.parse().ok())
+                    .unwrap_or(0),
+                manufacturer: optional("Manufacturer").map(str::to_string),
+                part_number: optional("Part Number").map(str::to_string),
+                serial: optional("Serial Number").map(str::to_string),
+            }
+        })
+        .collect()
+}
+
+/// Parses the Win32_PhysicalMemory WMI instances.
+pub(crate) fn parse_win32_physical_memory(records: &[WmiRecord]) -> Vec<MemoryModule> {
+    records
+        .iter()
+        .map(|record| {
+            let field = |name: &str| record.get(name).map(String::as_str).unwrap_or_default();
+
+            MemoryModule {
+                locator: field("DeviceLocator").to_string(),
+                capacity: field("Capacity").parse::<u64>().unwrap_or(0) / 1024 / 1024,
+                speed: field("Speed").parse().unwrap_or(0),
+                manufacturer: record.get("Manufacturer").cloned(),
+                part_number: record.get("PartNumber").cloned(),
+                serial: record.get("SerialNumber").cloned(),
+            }
+        })
+        .collect()
+}
+
+/// Reads the memory modules of the system, or an empty list if the SMBIOS table is not readable.
+/// Reading the SMBIOS table with `dmidecode` requires root privileges on Linux.
+fn memory_modules(provider: &dyn HardwareProvider) -> Vec<MemoryModule> {
+    if cfg!(target_os = "windows") {
+        parse_win32_physical_memory(&wmi::query(
+            provider,
+            "Win32_PhysicalMemory",
+            &[
+                "DeviceLocator",
+                "Capacity",
+                "Speed",
+                "Manufacturer",
+                "PartNumber",
+                "SerialNumber",
+            ],
+        ))
+    } else {
+        provider
+            .run_command("dmidecode", &["-t", "17"])
+            .map(|output| parse_dmidecode_memory(&output))
+            .unwrap_or_default()
+    }
+}
+
+/// Reads the baseboard from sysfs. The serial number is only readable by root.
+pub(crate) fn linux_board(provider: &dyn HardwareProvider) -> BoardInfo {
+    let read = |file: &str| provider.read_to_string(&format!("{}/{}", LINUX_DMI_DIR, file));
+
+    BoardInfo {
+        manufacturer: read("board_vendor"),
+        product: read("board_name"),
+        serial: read("board_serial"),
+    }
+}
+
+/// Parses the Win32_BaseBoard WMI instance.
+pub(crate) fn parse_win32_baseboard(records: &[WmiRecord]) -> BoardInfo {
+    let field = |name: &str| records.first().and_then(|record| record.get(name).cloned());
+
+    BoardInfo {
+        manufacturer: field("Manufacturer"),
+        product: field("Product"),
+        serial: field("SerialNumber"),
+    }
+}
+
+/// Reads the firmware from sysfs.
+pub(crate) fn linux_bios(provider: &dyn HardwareProvider) -> BiosInfo {
+    let read = |file: &str| provider.read_to_string(&format!("{}/{}", LINUX_DMI_DIR, file));
+
+    BiosInfo {
+        vendor: read("bios_vendor"),
+        version: read("bios_version"),
+        date: read("bios_date").map(|date| normalize_smbios_date(&date)),
+    }
+}
+
+/// Parses the Win32_BIOS WMI instance, with the release date already formatted as YYYY-MM-DD.
+pub(crate) fn parse_win32_bios(records: &[WmiRecord]) -> BiosInfo {
+    let field = |name: &str| records.first().and_then(|record| record.get(name).cloned());
+
+    BiosInfo {
+        vendor: field("Manufacturer"),
+        version: field("SMBIOSBIOSVersion"),
+        date: field("ReleaseDate"),
+    }
+}
+
+/// Parses the boot ROM version of `system_profiler SPHardwareDataType` output.
+/// Macs do not expose a SMBIOS vendor or release date.
+pub(crate) fn parse_system_profiler_firmware(output: &str) -> BiosInfo {
+    let version = output.lines().find_map(|line| {
+        let (key, value) = line.trim().split_once(": ")?;
+
+        matches!(key, "System Firmware Version" | "Boot ROM Version").then(|| value.to_string())
+    });
+
+    BiosInfo {
+        version,
+        ..Default::default()
+    }
+}
+
+/// Reads the firmware of the system. Platforms without a firmware source yield an empty BiosInfo.
+fn bios(provider: &dyn HardwareProvider) -> BiosInfo {
+    if cfg!(target_os = "windows") {
+        // The date is formatted by PowerShell, as the default format depends on the locale.
+        parse_win32_bios(&wmi::query(
+            provider,
+            "Win32_BIOS",
+            &[
+                "Manufacturer",
+                "SMBIOSBIOSVersion",
+                "@{n='ReleaseDate';e={$_.ReleaseDate.ToString('yyyy-MM-dd')}}",
+            ],
+        ))
+    } else if cfg!(target_os = "macos") {
+        provider
+            .run_command("system_profiler", &["SPHardwareDataType"])
+            .map(|output| parse_system_profiler_firmware(&output))
+            .unwrap_or_default()
+    } else if cfg!(target_os = "linux") {
+        linux_bios(provider)
+    } else {
+        BiosInfo::default()
+    }
+}
+
+/// Returns whether a normalized system UUID is a well-known value shared by many machines.
+pub(crate) fn is_bogus_system_uuid(uuid: &str) -> bool {
+    BOGUS_SYSTEM_UUIDS.contains(&uuid)
+}
+
+/// Reads the SMBIOS system UUID, normalized to lowercase.
+/// # Errors
+/// Returns `UniqueIdError::SourceUnavailable` if the UUID cannot be read, e.g. because
+/// `/sys/class/dmi/id/product_uuid` is only readable by root, or on macOS where the
+/// IOPlatformUUID is collected by MACHINE.
+pub(crate) fn system_uuid(provider: &dyn HardwareProvider) -> Result<String, UniqueIdError> {
+    let uuid = if cfg!(target_os = "windows") {
+        wmi::query(provider, "Win32_ComputerSystemProduct", &["UUID"])
+            .into_iter()
+            .find_map(|mut record| record.remove("UUID"))
+    } else if cfg!(target_os = "macos") {
+        None
+    } else if cfg!(target_os = "freebsd") {
+        provider.run_command("kenv", &["-q", "smbios.system.uuid"])
+    } else {
+        provider.read_to_string(&format!("{}/product_uuid", LINUX_DMI_DIR))
+    };
+
+    uuid.as_deref()
+        .and_then(normalize_machine_id)
+        .ok_or(UniqueIdError::SourceUnavailable(IdentifierType::SYSUUID))
+}
+
+/// Reads the system serial number, or None if it is not readable. The serial number is only
+/// readable by root on Linux.
+fn system_serial(provider: &dyn HardwareProvider) -> Option<String> {
+    if cfg!(target_os = "windows") {
+        wmi::query(provider, "Win32_BIOS", &["SerialNumber"])
+            .into_iter()
+            .find_map(|mut record| record.remove("SerialNumber"))
+    } else if cfg!(target_os = "macos") {
+        provider
+            .run_command("ioreg", &["-rd1", "-c", "IOPlatformExpertDevice"])
+            .and_then(|output| parse_ioreg_property(&output, "IOPlatformSerialNumber"))
+    } else {
+        provider.read_to_string(&format!("{}/product_serial", LINUX_DMI_DIR))
+    }
+}
+
+/// Reads the chassis serial number of the system, or None if it is not readable. The serial
+/// number is only readable by root on Linux.
+fn chassis_serial(provider: &dyn HardwareProvider) -> Option<String> {
+    if cfg!(target_os = "windows") {
+        wmi::query(provider, "Win32_SystemEnclosure", &["SerialNumber"])
+            .first()
+            .and_then(|record| record.get("SerialNumber").cloned())
+    } else if cfg!(target_os = "macos") {
+        provider
+            .run_command("ioreg", &["-rd1", "-c", "IOPlatformExpertDevice"])
+            .and_then(|output| parse_ioreg_property(&output, "IOPlatformSerialNumber"))
+    } else {
+        provider.read_to_string(&format!("{}/chassis_serial", LINUX_DMI_DIR))
+    }
+}
+
+/// Reads the baseboard of the system. Platforms without a SMBIOS source yield an empty BoardInfo.
+fn board(provider: &dyn HardwareProvider) -> BoardInfo {
+    if cfg!(target_os = "windows") {
+        parse_win32_baseboard(&wmi::query(
+            provider,
+            "Win32_BaseBoard",
+            &["Manufacturer", "Product", "SerialNumber"],
+        ))
+    } else if cfg!(target_os = "linux") {
+        linux_board(provider)
+    } else {
+        BoardInfo::default()
+    }
+}
+
+/// Returns the per-DIMM keys of the installed memory modules, sorted by locator and numbered
+/// from 0: manufacturer (`d0m`), part number (`d0p`), speed (`d0f`) and serial number (`d0s`).
+/// Empty slots are skipped. The serial number is left out if the firmware redacts it.
+#[cfg(feature = "smbios")]
+pub(crate) fn dimm_details(provider: &dyn HardwareProvider) -> Vec<(String, String)> {
+    let mut modules = memory_modules(provider);
+    modules.sort();
+
+    let mut result = Vec::new();
+
+    for (i, module) in modules.into_iter().filter(|m| m.capacity > 0).enumerate() {
+        let value = |value: Option<String>| normalize_smbios_string(value.as_deref());
+
+        result.push((format!("d{}m", i), value(module.manufacturer)));
+        result.push((format!("d{}p", i), value(module.part_number)));
+        result.push((format!("d{}f", i), module.speed.to_string()));
+
+        let serial = value(module.serial);
+        if serial != UNPROVISIONED && !serial.eq_ignore_ascii_case(UNKNOWN) {
+            result.push((format!("d{}s", i), serial));
+        }
+    }
+
+    result
+}
+
+impl IdentifierTypeDataList {
+    /// Collects one block per memory slot, sorted by locator. Empty slots are included with a
+    /// capacity of 0, so the number of blocks is the number of slots.
+    pub(crate) fn collect_installed_ram_modules(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        let mut modules = memory_modules(provider);
+        modules.sort();
+
+        modules
+            .into_iter()
+            .map(|module| {
+                let mut block = IdentifierTypeDataList::new(IdentifierType::INSTALLED_RAM_MODULES);
+                block.push("l", &module.locator);
+                block.push("c", &module.capacity.to_string());
+                block.push("f", &module.speed.to_string());
+                block
+            })
+            .collect()
+    }
+
+    /// Collects the SMBIOS system UUID, or nothing if it cannot be read. A well-known bogus UUID
+    /// is flagged with `bogus=1`, or not collected with `IdentifierOptions::exclude_bogus_sysuuid`.
+    pub(crate) fn collect_sysuuid(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+    ) -> Vec<IdentifierTypeDataList> {
+        let uuid = match system_uuid(provider) {
+            Ok(uuid) => uuid,
+            Err(_) => return Vec::new(),
+        };
+        let bogus = is_bogus_system_uuid(&uuid);
+
+        if bogus && options.exclude_bogus_sysuuid {
+            return Vec::new();
+        }
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::SYSUUID);
+        block.push("id", &uuid);
+        if bogus {
+            block.push("bogus", "1");
+        }
+
+        vec![block]
+    }
+
+    /// Collects the serial number of the system, lowercased and trimmed, or nothing if it is not
+    /// readable. A placeholder left by the manufacturer is `unprovisioned`, so it can be told
+    /// apart from a real serial number.
+    pub(crate) fn collect_serial(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        let serial = match system_serial(provider) {
+            Some(serial) => serial,
+            None => return Vec::new(),
+        };
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::SERIAL);
+        block.push("s", &normalize_smbios_string(Some(&serial)).to_lowercase());
+
+        vec![block]
+    }
+
+    /// Collects the serial number of the chassis with `IdentifierOptions::include_chassis_serial`,
+    /// or nothing if it is not readable. A placeholder left by the manufacturer is `unprovisioned`.
+    pub(crate) fn collect_chassis_serial(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+    ) -> Vec<IdentifierTypeDataList> {
+        if !options.include_chassis_serial {
+            return Vec::new();
+        }
+
+        let serial = match chassis_serial(provider) {
+            Some(serial) => serial,
+            None => return Vec::new(),
+        };
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::CHASSIS_SERIAL);
+        block.push("s", &normalize_smbios_string(Some(&serial)));
+
+        vec![block]
+    }
+
+    /// Collects the manufacturer, product and serial number of the baseboard, or nothing if the
+    /// SMBIOS table is not readable. Unreadable fields are `unknown` and placeholder values left by
+    /// the manufacturer are `unprovisioned`, so boards without a real serial are recognizable.
+    pub(crate) fn collect_board(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        let board = board(provider);
+
+        if board == BoardInfo::default() {
+            return Vec::new();
+        }
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::BOARD);
+        block.push("m", &normalize_smbios_string(board.manufacturer.as_deref()));
+        block.push("p", &normalize_smbios_string(board.product.as_deref()));
+        block.push("s", &normalize_smbios_string(board.serial.as_deref()));
+
+        vec![block]
+    }
+
+    /// Collects the vendor, version and release date of the firmware, lowercased and trimmed.
+    /// Unreadable fields are `unknown`. If no field is readable, such as inside a container, nothing
+    /// is collected, or an empty `BIOS()` block with `IdentifierOptions::empty_bios_when_unavailable`.
+    pub(crate) fn collect_bios(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+    ) -> Vec<IdentifierTypeDataList> {
+        let bios = bios(provider);
+
+        if bios == BiosInfo::default() {
+            return match options.empty_bios_when_unavailable {
+                true => vec![IdentifierTypeDataList::new(IdentifierType::BIOS)],
+                false => Vec::new(),
+            };
+        }
+
+        let value =
+            |value: Option<String>| normalize_smbios_string(value.as_deref()).to_lowercase();
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::BIOS);
+        block.push("v", &value(bios.vendor));
+        block.push("r", &value(bios.version));
+        block.push("d", &value(bios.date));
+
+        vec![block]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierBuilder};
+
+    const DMIDECODE_MEMORY: &str = include_str!("../tests/fixtures/commands/dmidecode_memory.txt");
+    const WIN32_PHYSICAL_MEMORY: &str =
+        include_str!("../tests/fixtures/commands/win32_physicalmemory.txt");
+
+    fn module(locator: &str, capacity: u64, speed: u64) -> MemoryModule {
+        MemoryModule {
+            locator: locator.to_string(),
+            capacity,
+            speed,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_memory_modules() {
+        assert_eq!(
+            parse_dmidecode_memory(DMIDECODE_MEMORY),
+            vec![
+                MemoryModule {
+                    manufacturer: Some("Kingston".to_string()),
+                    serial: Some("1A2B3C4D".to_string()),
+                    ..module("DIMM_B1", 8192, 3200)
+                },
+                module("DIMM_A2", 0, 0),
+                MemoryModule {
+                    serial: Some("5E6F7A8B".to_string()),
+                    ..module("DIMM_A1", 8192, 3200)
+                },
+                module("DIMM_B2", 0, 0),
+            ]
+        );
+        assert_eq!(
+            parse_win32_physical_memory(&wmi::parse_format_list(WIN32_PHYSICAL_MEMORY)),
+            vec![module("DIMM1", 16384, 3200), module("DIMM0", 16384, 3200)]
+        );
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_installed_ram_modules() {
+        let provider = FakeProvider::new(FakeData {
+            commands: [("dmidecode -t 17".to_string(), DMIDECODE_MEMORY.to_string())].into(),
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::INSTALLED_RAM_MODULES);
+
+        assert_eq!(
+            builder.build().to_string_with(&provider, false),
+            "[INSTALLED_RAM_MODULES(l=DIMM_A1, c=8192, f=3200)\
+             INSTALLED_RAM_MODULES(l=DIMM_A2, c=0, f=0)\
+             INSTALLED_RAM_MODULES(l=DIMM_B1, c=8192, f=3200)\
+             INSTALLED_RAM_MODULES(l=DIMM_B2, c=0, f=0)]"
+        );
+    }
+
+    #[test]
+    fn test_normalize_smbios_string() {
+        assert_eq!(normalize_smbios_string(Some("MS-7C56\n")), "MS-7C56");
+        assert_eq!(normalize_smbios_string(None), UNKNOWN);
+
+        for placeholder in [
+            "",
+            " ",
+            "To be filled by O.E.M.",
+            "None",
+            "Default string",
+            "0000",
+        ] {
+            assert_eq!(normalize_smbios_string(Some(placeholder)), UNPROVISIONED);
+        }
+    }
+
+    #[test]
+    fn test_parse_win32_baseboard() {
+        let output = "\r\n\r\nManufacturer : ASUSTeK COMPUTER INC.\r\n\
+                      Product      : ROG STRIX B550-F GAMING\r\n\
+                      SerialNumber : 210686720601234\r\n\r\n";
+
+        assert_eq!(
+            parse_win32_baseboard(&wmi::parse_format_list(output)),
+            BoardInfo {
+                manufacturer: Some("ASUSTeK COMPUTER INC.".to_string()),
+                product: Some("ROG STRIX B550-F GAMING".to_string()),
+                serial: Some("210686720601234".to_string()),
+            }
+        );
+        assert_eq!(parse_win32_baseboard(&[]), BoardInfo::default());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_board() {
+        let file = |name: &str, contents: &str| {
+            (
+                format!("{}/{}", LINUX_DMI_DIR, name),
+                contents.as_bytes().to_vec(),
+            )
+        };
+
+        let provider = FakeProvider::new(FakeData {
+            files: [
+                file("board_vendor", "Micro-Star International Co., Ltd.\n"),
+                file("board_name", "MAG B550 TOMAHAWK (MS-7C91)\n"),
+                file("board_serial", "To be filled by O.E.M.\n"),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::BOARD);
+        let identifier = builder.build();
+
+        assert_eq!(
+            identifier.to_string_with(&provider, false),
+            "[BOARD(m=Micro-Star International Co.%2C Ltd., p=MAG B550 TOMAHAWK (MS-7C91), \
+             s=unprovisioned)]"
+        );
+
+        provider.update(|data| {
+            data.files.remove("/sys/class/dmi/id/board_serial");
+        });
+
+        assert!(identifier
+            .to_string_with(&provider, false)
+            .ends_with("s=unknown)]"));
+
+        assert!(IdentifierTypeDataList::new(IdentifierType::BOARD)
+            .collect_with(&FakeProvider::default(), &Default::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_bios() {
+        assert_eq!(normalize_smbios_date("12/03/2021\n"), "2021-12-03");
+        assert_eq!(normalize_smbios_date("1/9/2020"), "2020-01-09");
+        assert_eq!(normalize_smbios_date("2020-01-09"), "2020-01-09");
+
+        let output = "\r\n\r\nManufacturer      : American Megatrends Inc.\r\n\
+                      SMBIOSBIOSVersion : 2803\r\n\
+                      ReleaseDate       : 2021-12-03\r\n\r\n";
+
+        assert_eq!(
+            parse_win32_bios(&wmi::parse_format_list(output)),
+            BiosInfo {
+                vendor: Some("American Megatrends Inc.".to_string()),
+                version: Some("2803".to_string()),
+                date: Some("2021-12-03".to_string()),
+            }
+        );
+
+        let output = "Hardware:\n\n    Hardware Overview:\n\n      Model Name: MacBook Pro\n      \
+                      System Firmware Version: 10151.81.1\n      OS Loader Version: 10151.81.1\n";
+
+        assert_eq!(
+            parse_system_profiler_firmware(output).version,
+            Some("10151.81.1".to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_bios() {
+        let file = |name: &str, contents: &str| {
+            (
+                format!("{}/{}", LINUX_DMI_DIR, name),
+                contents.as_bytes().to_vec(),
+            )
+        };
+
+        let provider = FakeProvider::new(FakeData {
+            files: [
+                file("bios_vendor", "American Megatrends Inc.\n"),
+                file("bios_version", " 2803 \n"),
+                file("bios_date", "12/03/2021\n"),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        let block = IdentifierTypeDataList::new(IdentifierType::BIOS)
+            .build_with(&provider, &IdentifierOptions::default());
+
+        assert_eq!(
+            block,
+            "BIOS(v=american megatrends inc., r=2803, d=2021-12-03)"
+        );
+
+        let parsed: IdentifierTypeDataList = block.parse().unwrap();
+        assert_eq!(parsed.identifier, IdentifierType::BIOS);
+        assert_eq!(
+            parsed.build_with(&FakeProvider::default(), &IdentifierOptions::default()),
+            block
+        );
+
+        let unavailable = FakeProvider::default();
+        let options = IdentifierOptions {
+            empty_bios_when_unavailable: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::BIOS)
+                .try_collect_with(&unavailable, &IdentifierOptions::default()),
+            Err(crate::UniqueIdError::SourceUnavailable(
+                IdentifierType::BIOS
+            ))
+        );
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::BIOS).build_with(&unavailable, &options),
+            "BIOS()"
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "smbios", not(target_os = "windows")))]
+    fn test_ram_dimm_details() {
+        let provider = FakeProvider::new(FakeData {
+            commands: [(
+                "dmidecode -t 17".to_string(),
+                include_str!("../tests/fixtures/commands/dmidecode_memory_dimms.txt").to_string(),
+            )]
+            .into(),
+            total_memory: 41943040,
+            ..Default::default()
+        });
+
+        let options = IdentifierOptions {
+            ram_dimm_details: true,
+            ..Default::default()
+        };
+
+        // DIMM 0 has a zeroed serial and DIMM 3 no SMBIOS strings at all, both redacted.
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::RAM).build_with(&provider, &options),
+            "RAM(t=41943040, d0m=Samsung, d0p=M471A2K43DB1-CWE, d0f=3200, \
+             d1m=Micron Technology, d1p=8ATF2G64HZ-3G2E2, d1f=3200, d1s=2A9C41F7, \
+             d2m=unprovisioned, d2p=unprovisioned, d2f=2666)"
+        );
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::RAM)
+                .build_with(&provider, &IdentifierOptions::default()),
+            "RAM(t=41943040)"
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_chassis_serial() {
+        let provider = FakeProvider::new(FakeData {
+            files: [(
+                format!("{}/chassis_serial", LINUX_DMI_DIR),
+                b"CZC1234XYZ\n".to_vec(),
+            )]
+            .into(),
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::CHASSIS_SERIAL);
+
+        // Never collected without the explicit opt-in.
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::CHASSIS_SERIAL)
+                .try_collect_with(&provider, &IdentifierOptions::default()),
+            Err(crate::UniqueIdError::SourceUnavailable(
+                IdentifierType::CHASSIS_SERIAL
+            ))
+        );
+
+        builder.include_chassis_serial(true);
+
+        assert_eq!(
+            builder.build().to_string_with(&provider, false),
+            "[CHASSIS_SERIAL(s=CZC1234XYZ)]"
+        );
+    }
+
+    #[test]
+    fn test_is_bogus_system_uuid() {
+        assert!(is_bogus_system_uuid("03000200-0400-0500-0006-000700080009"));
+        assert!(is_bogus_system_uuid("00000000-0000-0000-0000-000000000000"));
+        assert!(is_bogus_system_uuid(
+            &normalize_machine_id("FFFFFFFF-FFFF-FFFF-FFFF-FFFFFFFFFFFF").unwrap()
+        ));
+        assert!(!is_bogus_system_uuid(
+            "4c4c4544-0042-3510-8052-b4c04f564e32"
+        ));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_sysuuid() {
+        let uuid = |uuid: &str| {
+            FakeProvider::new(FakeData {
+                files: [(
+                    format!("{}/product_uuid", LINUX_DMI_DIR),
+                    format!("{}\n", uuid).into_bytes(),
+                )]
+                .into(),
+                ..Default::default()
+            })
+        };
+        let data = IdentifierTypeDataList::new(IdentifierType::SYSUUID);
+        let mut options = IdentifierOptions::default();
+
+        assert_eq!(
+            data.build_with(&uuid("4C4C4544-0042-3510-8052-B4C04F564E32"), &options),
+            "SYSUUID(id=4c4c4544-0042-3510-8052-b4c04f564e32)"
+        );
+        assert_eq!(
+            data.build_with(&uuid("03000200-0400-0500-0006-000700080009"), &options),
+            "SYSUUID(id=03000200-0400-0500-0006-000700080009, bogus=1)"
+        );
+
+        // Unreadable without root.
+        assert_eq!(
+            system_uuid(&FakeProvider::default()),
+            Err(UniqueIdError::SourceUnavailable(IdentifierType::SYSUUID))
+        );
+
+        options.exclude_bogus_sysuuid = true;
+
+        assert_eq!(
+            data.try_collect_with(&uuid("FFFFFFFF-FFFF-FFFF-FFFF-FFFFFFFFFFFF"), &options),
+            Err(UniqueIdError::SourceUnavailable(IdentifierType::SYSUUID))
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_serial() {
+        let serial = |serial: &str| {
+            FakeProvider::new(FakeData {
+                files: [(
+                    format!("{}/product_serial", LINUX_DMI_DIR),
+                    serial.as_bytes().to_vec(),
+                )]
+                .into(),
+                ..Default::default()
+            })
+        };
+        let data = IdentifierTypeDataList::new(IdentifierType::SERIAL);
+        let options = IdentifierOptions::default();
+
+        assert_eq!(
+            data.build_with(&serial(" 5CD1234XYZ \n"), &options),
+            "SERIAL(s=5cd1234xyz)"
+        );
+        assert_eq!(
+            data.build_with(&serial("System Serial Number\n"), &options),
+            "SERIAL(s=unprovisioned)"
+        );
+        assert_eq!(
+            data.build_with(&serial("Default string\n"), &options),
+            "SERIAL(s=unprovisioned)"
+        );
+        assert_eq!(
+            data.build_with(&serial("\n"), &options),
+            "SERIAL(s=unprovisioned)"
+        );
+
+        // Unreadable without root.
+        assert_eq!(
+            data.try_collect_with(&FakeProvider::default(), &options),
+            Err(UniqueIdError::SourceUnavailable(IdentifierType::SERIAL))
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use crate::registry;
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// The file the NVIDIA kernel module reports its version in on Linux.
+const LINUX_NVIDIA_VERSION_FILE: &str = "/proc/driver/nvidia/version";
+
+/// The registry key holding one subkey per display adapter driver on Windows.
+const WINDOWS_DISPLAY_CLASS_KEY: &str =
+    "HKLM\\SYSTEM\\CurrentControlSet\\Control\\Class\\{4d36e968-e325-11ce-bfc1-08002be10318}";
+
+/// A single installed graphics driver.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct DriverInfo {
+    /// The name of the driver or its provider. (e.g. nvidia, mesa, Advanced Micro Devices, Inc.)
+    pub name: String,
+    /// The version of the driver. (e.g. 535.129.03)
+    pub version: String,
+}
+
+/// Returns true if the token is a dotted version number. (e.g. 535.129.03)
+fn is_version(token: &str) -> bool {
+    token.contains('.') && token.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
+
+/// Parses the version of the NVIDIA kernel module from `/proc/driver/nvidia/version`.
+/// (e.g. NVRM version: NVIDIA UNIX x86_64 Kernel Module  535.129.03  Thu Oct 19 18:56:32 UTC 2023)
+pub(crate) fn parse_nvidia_version(contents: &str) -> Option<String> {
+    contents
+        .lines()
+        .find(|line| line.starts_with("NVRM version:"))?
+        .split_whitespace()
+        .find(|token| is_version(token))
+        .map(str::to_string)
+}
+
+/// Parses the Mesa version from `glxinfo -B` output.
+/// (e.g. OpenGL version string: 4.6 (Compatibility Profile) Mesa 23.2.1-1ubuntu3.1~22.04.2)
+pub(crate) fn parse_glxinfo_mesa_version(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let (_, version) = line.split_once(" Mesa ")?;
+
+        version.split_whitespace().next().map(str::to_string)
+    })
+}
+
+/// Parses the display adapter drivers of the registry class key.
+pub(crate) fn parse_display_class_drivers(
+    keys: &std::collections::BTreeMap<String, registry::RegistryKey>,
+) -> Vec<DriverInfo> {
+    keys.values()
+        .filter_map(|key| {
+            Some(DriverInfo {
+                name: key.get("ProviderName").cloned().unwrap_or_default(),
+                version: key.get("DriverVersion")?.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Reads the installed graphics drivers of the system.
+fn drivers(provider: &dyn HardwareProvider) -> Vec<DriverInfo> {
+    if cfg!(target_os = "windows") {
+        let mut keys = registry::query(provider, WINDOWS_DISPLAY_CLASS_KEY, "DriverVersion", true);
+
+        for (path, values) in
+            registry::query(provider, WINDOWS_DISPLAY_CLASS_KEY, "ProviderName", true)
+        {
+            keys.entry(path).or_default().extend(values);
+        }
+
+        parse_display_class_drivers(&keys)
+    } else {
+        let nvidia = provider
+            .read_to_string(LINUX_NVIDIA_VERSION_FILE)
+            .and_then(|contents| parse_nvidia_version(&contents))
+            .map(|version| DriverInfo {
+                name: "nvidia".to_string(),
+                version,
+            });
+
+        let mesa = provider
+            .run_command("glxinfo", &["-B"])
+            .and_then(|output| parse_glxinfo_mesa_version(&output))
+            .map(|version| DriverInfo {
+                name: "mesa".to_string(),
+                version,
+            });
+
+        nvidia.into_iter().chain(mesa).collect()
+    }
+}
+
+impl IdentifierTypeDataList {
+    /// Collects one block per installed graphics driver, sorted and without duplicates.
+    pub(crate) fn collect_graphics_driver_version(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        let mut drivers: Vec<DriverInfo> = drivers(provider)
+            .into_iter()
+            .map(|driver| DriverInfo {
+                name: driver.name.trim().to_lowercase(),
+                version: driver.version.trim().to_string(),
+            })
+            .collect();
+
+        drivers.sort();
+        drivers.dedup();
+
+        drivers
+            .into_iter()
+            .map(|driver| {
+                let mut block =
+                    IdentifierTypeDataList::new(IdentifierType::GRAPHICS_DRIVER_VERSION);
+                block.push("n", &driver.name);
+                block.push("v", &driver.version);
+                block
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierBuilder};
+
+    #[test]
+    fn test_parse_driver_versions() {
+        let nvidia = "NVRM version: NVIDIA UNIX x86_64 Kernel Module  535.129.03  \
+                      Thu Oct 19 18:56:32 UTC 2023\nGCC version:  gcc version 12.3.0\n";
+
+        assert_eq!(parse_nvidia_version(nvidia), Some("535.129.03".to_string()));
+        assert_eq!(parse_nvidia_version(""), None);
+
+        let glxinfo = "name of display: :0\nExtended renderer info (GLX_MESA_query_renderer):\n    \
+                       Vendor: AMD (0x1002)\n\
+                       OpenGL version string: 4.6 (Compatibility Profile) Mesa 23.2.1-1ubuntu3.1~22.04.2\n";
+
+        assert_eq!(
+            parse_glxinfo_mesa_version(glxinfo),
+            Some("23.2.1-1ubuntu3.1~22.04.2".to_string())
+        );
+
+        let keys = registry::parse_query(
+            "\r\nHKEY_LOCAL_MACHINE\\SYSTEM\\Class\\0000\r\n    \
+             DriverVersion    REG_SZ    31.0.15.3623\r\n    \
+             ProviderName    REG_SZ    NVIDIA\r\n\r\n\
+             HKEY_LOCAL_MACHINE\\SYSTEM\\Class\\Properties\r\n",
+        );
+
+        assert_eq!(
+            parse_display_class_drivers(&keys),
+            vec![DriverInfo {
+                name: "NVIDIA".to_string(),
+                version: "31.0.15.3623".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn test_graphics_driver_version() {
+        let provider = FakeProvider::new(FakeData {
+            files: [(
+                LINUX_NVIDIA_VERSION_FILE.to_string(),
+                b"NVRM version: NVIDIA UNIX x86_64 Kernel Module  535.129.03  Thu Oct 19 2023\n"
+                    .to_vec(),
+            )]
+            .into(),
+            commands: [(
+                "glxinfo -B".to_string(),
+                "OpenGL version string: 4.6.0 NVIDIA 535.129.03\n".to_string(),
+            )]
+            .into(),
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::GRAPHICS_DRIVER_VERSION);
+
+        assert_eq!(
+            builder.build().to_string_with(&provider, false),
+            "[GRAPHICS_DRIVER_VERSION(n=nvidia, v=535.129.03)]"
+        );
+    }
+}
@@ -0,0 +1,373 @@
+use crate::{HashAlgorithm, Identifier};
+
+/// The emoji used by `Identifier::build_emoji_hash`, one per byte value.
+///
+/// Every emoji is a single code point from Unicode 6.0 with emoji presentation by default, so it is
+/// supported by every platform. People, skin tones, country flags and look-alikes (e.g. moon phases,
+/// clock faces) are left out, as are emoji sharing a spoken name (e.g. a red and a green apple), so
+/// the emoji can be read out over the phone. The order is part of the output format and must not
+/// change.
+const EMOJI: [char; 256] = [
+    '🌀', '🌂', '🌃', '🌈', '🌊', '🌋', '🌍', '🌟', '🌰', '🌱', '🌲', '🌳', '🌴', '🌵', '🌷', '🌸',
+    '🌹', '🌺', '🌻', '🌽', '🌾', '🌿', '🍀', '🍁', '🍄', '🍅', '🍆', '🍇', '🍉', '🍊', '🍋', '🍌',
+    '🍍', '🍎', '🍐', '🍑', '🍒', '🍓', '🍔', '🍕', '🍖', '🍗', '🍛', '🍜', '🍝', '🍞', '🍟', '🍠',
+    '🍡', '🍢', '🍣', '🍤', '🍦', '🍩', '🍪', '🍫', '🍬', '🍭', '🍯', '🍱', '🍳', '🍵', '🍷', '🍸',
+    '🍹', '🍺', '🍼', '🎀', '🎁', '🎂', '🎃', '🎄', '🎆', '🎈', '🎉', '🎒', '🎓', '🎠', '🎡', '🎢',
+    '🎣', '🎤', '🎧', '🎨', '🎩', '🎪', '🎬', '🎭', '🎮', '🎯', '🎰', '🎱', '🎲', '🎳', '🎵', '🎷',
+    '🎸', '🎹', '🎺', '🎻', '🎾', '🎿', '🏀', '🏁', '🏆', '🏈', '🏠', '🏢', '🏥', '🏦', '🏧', '🏨',
+    '🏪', '🏭', '🏮', '🏰', '🐂', '🐅', '🐆', '🐉', '🐊', '🐋', '🐌', '🐍', '🐎', '🐐', '🐑', '🐓',
+    '🐗', '🐘', '🐙', '🐚', '🐛', '🐜', '🐝', '🐞', '🐟', '🐡', '🐢', '🐤', '🐦', '🐧', '🐨', '🐩',
+    '🐪', '🐬', '🐭', '🐮', '🐰', '🐱', '🐵', '🐶', '🐷', '🐸', '🐹', '🐺', '🐻', '🐼', '👑', '👓',
+    '👔', '👕', '👖', '👗', '👘', '👙', '👜', '👟', '👠', '👢', '👣', '👻', '👽', '💀', '💄', '💈',
+    '💉', '💊', '💌', '💍', '💎', '💐', '💙', '💡', '💣', '💧', '💩', '💪', '💬', '💯', '💰', '💳',
+    '💵', '💺', '💻', '💼', '💾', '📁', '📅', '📈', '📋', '📌', '📎', '📏', '📚', '📜', '📝', '📟',
+    '📠', '📡', '📢', '📦', '📮', '📰', '📱', '📷', '📺', '📻', '🔋', '🔌', '🔍', '🔑', '🔒', '🔔',
+    '🔖', '🔗', '🔥', '🔦', '🔧', '🔨', '🔩', '🔪', '🔬', '🔭', '🔮', '🗻', '🗼', '🗽', '🗿', '🚀',
+    '🚁', '🚂', '🚌', '🚑', '🚒', '🚕', '🚗', '🚚', '🚜', '🚢', '🚤', '🚦', '🚧', '🚨', '🚪', '🚲',
+];
+
+/// The EFF large Diceware word list, as published at
+/// <https://www.eff.org/files/2016/07/18/eff_large_wordlist.txt> under CC BY 3.0 US.
+/// Each line holds the five dice rolls and the word, separated by a tab.
+const EFF_LARGE_WORDLIST: &str = include_str!("eff_large_wordlist.txt");
+
+/// The number of words of the EFF large word list, 6^5.
+const DICEWARE_WORDS: u32 = 7776;
+
+/// Returns the words of the EFF large word list, in the order of their dice rolls.
+fn diceware_words() -> Vec<&'static str> {
+    EFF_LARGE_WORDLIST
+        .lines()
+        .filter_map(|line| line.split('\t').nth(1))
+        .collect()
+}
+
+/// Returns the number of leading hash bytes encoded by `num_words` Diceware words, which carry
+/// log2(7776) ≈ 12.9 bits each.
+fn diceware_bytes(num_words: usize) -> usize {
+    (num_words as f64 * f64::from(DICEWARE_WORDS).log2() / 8.0).ceil() as usize
+}
+
+impl Identifier {
+    /// Hashes the Identifier and returns the hash encoded as z-base-32.
+    /// z-base-32 avoids easily confused characters, which makes it suitable for codes read out by humans.
+    /// # Arguments
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    ///
+    /// let code = builder.build().build_zbase32(HashAlgorithm::SHA3_256);
+    ///
+    /// assert_eq!(code.len(), 52);
+    /// ```
+    #[cfg(feature = "zbase32")]
+    pub fn build_zbase32(&self, algorithm: HashAlgorithm) -> String {
+        zbase32::encode_full_bytes(&self.hash(algorithm))
+    }
+
+    /// Hashes the Identifier and returns the first 128 bits of the hash as 16 emoji, one per byte.
+    /// Meant for visual comparison, such as two people reading out the emoji of their machines.
+    /// # Arguments
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    ///
+    /// let emoji = builder.build().build_emoji_hash(HashAlgorithm::SHA3_512);
+    ///
+    /// assert_eq!(emoji.chars().count(), 16);
+    /// ```
+    pub fn build_emoji_hash(&self, algorithm: HashAlgorithm) -> String {
+        self.hash(algorithm)[..16]
+            .iter()
+            .map(|byte| EMOJI[*byte as usize])
+            .collect()
+    }
+
+    /// Hashes the Identifier and returns the leading bits of the hash as words of the EFF large
+    /// Diceware list, e.g. for activation codes read out over the phone.
+    ///
+    /// Each word carries about 12.9 bits: the leading `ceil(num_words * 12.9 / 8)` bytes of the hash
+    /// are read as a big-endian number and written in base 7776, most significant word first.
+    /// 8 words represent the first 104 bits of the hash.
+    /// # Arguments
+    /// * `num_words` - The number of words.
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    ///
+    /// let words = builder.build().build_diceware(8, HashAlgorithm::SHA3_512);
+    ///
+    /// assert_eq!(words.len(), 8);
+    /// ```
+    /// # Panics
+    /// Panics if the hash of the algorithm is too short for `num_words` words, more than 19 words
+    /// for a 256 bit hash or 39 words for a 512 bit hash.
+    pub fn build_diceware(&self, num_words: usize, algorithm: HashAlgorithm) -> Vec<String> {
+        let hash = self.hash(algorithm);
+        let bytes = diceware_bytes(num_words);
+        assert!(
+            bytes <= hash.len(),
+            "a {} bit hash is too short for {} Diceware words",
+            hash.len() * 8,
+            num_words
+        );
+
+        // Long division of the big-endian number by 7776, one word per division.
+        let mut number = hash[..bytes].to_vec();
+        let mut indices = Vec::with_capacity(num_words);
+        for _ in 0..num_words {
+            let mut remainder = 0;
+            for byte in number.iter_mut() {
+                let value = (remainder << 8) | u32::from(*byte);
+                *byte = (value / DICEWARE_WORDS) as u8;
+                remainder = value % DICEWARE_WORDS;
+            }
+            indices.push(remainder as usize);
+        }
+
+        let words = diceware_words();
+        indices
+            .into_iter()
+            .rev()
+            .map(|index| words[index].to_string())
+            .collect()
+    }
+
+    /// Hashes the Identifier and returns the first 128 bits of the hash as a BIP-39 mnemonic.
+    /// BIP-39 appends a 4 bit checksum, so the mnemonic is always 12 English words. See
+    /// `build_bip39_mnemonic_24` for a 24 word mnemonic of the first 256 bits.
+    /// # Arguments
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    ///
+    /// let words = builder.build().build_bip39_mnemonic(HashAlgorithm::SHA3_512);
+    ///
+    /// assert_eq!(words.len(), 12);
+    /// ```
+    #[cfg(feature = "bip39")]
+    pub fn build_bip39_mnemonic(&self, algorithm: HashAlgorithm) -> Vec<String> {
+        self.bip39_words(algorithm, 16)
+    }
+
+    /// Hashes the Identifier and returns the first 256 bits of the hash as a BIP-39 mnemonic.
+    /// BIP-39 appends an 8 bit checksum, so the mnemonic is always 24 English words.
+    /// # Arguments
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    ///
+    /// let words = builder.build().build_bip39_mnemonic_24(HashAlgorithm::SHA3_512);
+    ///
+    /// assert_eq!(words.len(), 24);
+    /// ```
+    #[cfg(feature = "bip39")]
+    pub fn build_bip39_mnemonic_24(&self, algorithm: HashAlgorithm) -> Vec<String> {
+        self.bip39_words(algorithm, 32)
+    }
+
+    /// Encodes the leading `entropy_bytes` bytes of the hash as BIP-39 English words.
+    #[cfg(feature = "bip39")]
+    fn bip39_words(&self, algorithm: HashAlgorithm, entropy_bytes: usize) -> Vec<String> {
+        let hash = self.hash(algorithm);
+        let mnemonic = bip39::Mnemonic::from_entropy(&hash[..entropy_bytes])
+            .expect("16 and 32 bytes are valid BIP-39 entropy lengths");
+
+        mnemonic.words().map(|word| word.to_string()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(unused_imports)]
+    use super::*;
+    use crate::{IdentifierType, IdentifierTypeData, IdentifierTypeDataList};
+
+    fn identifier() -> Identifier {
+        let mut list = IdentifierTypeDataList::new(IdentifierType::RAM);
+        list.data.push(IdentifierTypeData::new("t", "1024"));
+
+        Identifier {
+            name: Some("test".to_string()),
+            data: vec![list],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_emoji_have_no_duplicates() {
+        let mut unique = EMOJI.to_vec();
+        unique.sort();
+        unique.dedup();
+
+        assert_eq!(unique.len(), 256);
+        assert_eq!((EMOJI[0], EMOJI[255]), ('\u{1F300}', '\u{1F6B2}'));
+
+        // Only one emoji of each group sharing a spoken name.
+        for group in [
+            "🐅🐯",
+            "🐉🐲",
+            "🐎🐴",
+            "🍎🍏",
+            "💔💙💚",
+            "🎆🎇",
+            "🎉🎊",
+            "🐣🐤",
+            "📷📹🎥",
+            "🎩👒",
+            "📞📱",
+            "🚓🚗",
+            "🌟🌠",
+            "🍰🎂",
+            "🍦🍨",
+            "👽👾",
+            "🐟🐠",
+            "📖📚",
+            "🍁🍃",
+            "🍈🍉",
+            "🍴🔪",
+            "🎌🏁",
+            "📮📯",
+        ] {
+            assert_eq!(
+                group.chars().filter(|c| EMOJI.contains(c)).count(),
+                1,
+                "{}",
+                group
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_emoji_hash() {
+        let identifier = identifier();
+        let emoji: Vec<char> = identifier
+            .build_emoji_hash(HashAlgorithm::SHA3_256)
+            .chars()
+            .collect();
+
+        assert_eq!(emoji.len(), 16);
+        assert_eq!(
+            emoji[0],
+            EMOJI[identifier.hash(HashAlgorithm::SHA3_256)[0] as usize]
+        );
+    }
+
+    #[test]
+    fn test_build_diceware() {
+        let words = diceware_words();
+        assert_eq!(words.len(), DICEWARE_WORDS as usize);
+        assert_eq!((words[0], words[7775]), ("abacus", "zoom"));
+
+        let identifier = identifier();
+        let diceware = identifier.build_diceware(8, HashAlgorithm::SHA3_512);
+
+        assert_eq!(diceware.len(), 8);
+        assert!(diceware.iter().all(|word| words.contains(&word.as_str())));
+        assert_eq!(
+            diceware,
+            identifier.build_diceware(8, HashAlgorithm::SHA3_512)
+        );
+        assert_ne!(
+            diceware,
+            identifier.build_diceware(8, HashAlgorithm::SHA3_256)
+        );
+
+        // 8 words are the first 13 bytes (104 bits) written in base 7776.
+        assert_eq!(diceware_bytes(8), 13);
+        let hash = identifier.hash(HashAlgorithm::SHA3_512);
+        let mut number = hash[..13]
+            .iter()
+            .fold(0u128, |number, byte| number << 8 | u128::from(*byte));
+        let mut expected = Vec::new();
+        for _ in 0..8 {
+            expected.insert(0, words[(number % 7776) as usize]);
+            number /= 7776;
+        }
+        assert_eq!(diceware, expected);
+
+        assert_eq!(
+            identifier.build_diceware(39, HashAlgorithm::SHA3_512).len(),
+            39
+        );
+        assert!(identifier
+            .build_diceware(0, HashAlgorithm::SHA3_512)
+            .is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "too short")]
+    fn test_build_diceware_too_many_words() {
+        identifier().build_diceware(20, HashAlgorithm::SHA3_256);
+    }
+
+    #[test]
+    #[cfg(feature = "zbase32")]
+    fn test_build_zbase32() {
+        let identifier = identifier();
+        let code = identifier.build_zbase32(HashAlgorithm::SHA3_512);
+
+        assert_eq!(
+            zbase32::decode_full_bytes_str(&code).unwrap(),
+            identifier.hash(HashAlgorithm::SHA3_512)
+        );
+        assert!(code.chars().all(|c| !"0lv2".contains(c)));
+    }
+
+    #[test]
+    #[cfg(feature = "bip39")]
+    fn test_build_bip39_mnemonic() {
+        let identifier = identifier();
+        let words = identifier.build_bip39_mnemonic(HashAlgorithm::SHA3_512);
+
+        assert_eq!(words.len(), 12);
+
+        let mnemonic = bip39::Mnemonic::parse(words.join(" ")).unwrap();
+        assert_eq!(
+            mnemonic.to_entropy(),
+            identifier.hash(HashAlgorithm::SHA3_512)[..16].to_vec()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "bip39")]
+    fn test_build_bip39_mnemonic_24() {
+        let identifier = identifier();
+
+        for algorithm in [HashAlgorithm::SHA3_256, HashAlgorithm::SHA3_512] {
+            let words = identifier.build_bip39_mnemonic_24(algorithm);
+
+            assert_eq!(words.len(), 24);
+
+            let mnemonic = bip39::Mnemonic::parse(words.join(" ")).unwrap();
+            assert_eq!(
+                mnemonic.to_entropy(),
+                identifier.hash(algorithm)[..32].to_vec()
+            );
+            assert_eq!(
+                words[..11],
+                identifier.build_bip39_mnemonic(algorithm)[..11]
+            );
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// The file holding the number of bits of entropy available in the kernel pool on Linux.
+const LINUX_ENTROPY_FILE: &str = "/proc/sys/kernel/random/entropy_avail";
+
+/// Reads the number of bits of entropy available in the kernel pool. Only Linux exposes it.
+fn entropy_available(provider: &dyn HardwareProvider) -> Option<u32> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    provider
+        .read_to_string(LINUX_ENTROPY_FILE)
+        .and_then(|entropy| entropy.trim().parse().ok())
+}
+
+impl IdentifierTypeDataList {
+    /// Collects the available entropy of the kernel pool in bits, or nothing if it is not readable.
+    pub(crate) fn collect_system_entropy(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        match entropy_available(provider) {
+            Some(entropy) => {
+                let mut block = IdentifierTypeDataList::new(IdentifierType::SYSTEM_ENTROPY);
+                block.push("e", &entropy.to_string());
+                vec![block]
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierOptions, UniqueIdError};
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_system_entropy() {
+        let data = IdentifierTypeDataList::new(IdentifierType::SYSTEM_ENTROPY);
+        let options = IdentifierOptions::default();
+
+        let provider = FakeProvider::new(FakeData {
+            files: [(LINUX_ENTROPY_FILE.to_string(), b"3754\n".to_vec())].into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            data.build_with(&provider, &options),
+            "SYSTEM_ENTROPY(e=3754)"
+        );
+        assert_eq!(
+            data.try_collect_with(&FakeProvider::default(), &options),
+            Err(UniqueIdError::SourceUnavailable(
+                IdentifierType::SYSTEM_ENTROPY
+            ))
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+use crate::IdentifierType;
+
+/// Enum representing the errors returned by uniqueid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UniqueIdError {
+    /// Two Identifiers have different names. (left, right)
+    NameConflict(String, String),
+    /// Two Identifiers both contain different data for the same IdentifierType.
+    ComponentConflict(IdentifierType),
+    /// Two Identifiers were collected with different IdentifierOptions.
+    OptionsConflict,
+    /// A custom IdentifierType tag is not valid.
+    InvalidTag(String),
+    /// A string could not be parsed. (reason)
+    Parse(String),
+    /// The data of an IdentifierType could not be collected from the system.
+    SourceUnavailable(IdentifierType),
+    /// A requested output length in bits is not supported by the HashAlgorithm. (bits, maximum)
+    InvalidLength(u32, u32),
+    /// More builds were requested than allowed by `Identifier::build_with_rate_limit`.
+    RateLimited,
+    /// The process runs inside a container with `ContainerPolicy::FailIfContainerized`.
+    Containerized,
+    /// No signature could be made with the PKCS#11 token in the slot. (slot)
+    TokenUnavailable(u64),
+}
+
+impl Display for UniqueIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UniqueIdError::NameConflict(left, right) => {
+                write!(f, "conflicting identifier names: {} and {}", left, right)
+            }
+            UniqueIdError::ComponentConflict(identifier) => {
+                write!(f, "conflicting {} components", identifier.as_str())
+            }
+            UniqueIdError::OptionsConflict => write!(f, "conflicting identifier options"),
+            UniqueIdError::InvalidTag(tag) => write!(f, "invalid identifier type tag: {}", tag),
+            UniqueIdError::Parse(reason) => write!(f, "could not parse identifier: {}", reason),
+            UniqueIdError::SourceUnavailable(identifier) => {
+                write!(f, "could not collect {} data", identifier.as_str())
+            }
+            UniqueIdError::InvalidLength(bits, maximum) => write!(
+                f,
+                "invalid output length: {} bits, expected a multiple of 4 between 4 and {}",
+                bits, maximum
+            ),
+            UniqueIdError::RateLimited => write!(f, "identifier build rate limit exceeded"),
+            UniqueIdError::Containerized => write!(f, "running inside a container"),
+            UniqueIdError::TokenUnavailable(slot) => {
+                write!(f, "could not sign with the pkcs#11 token in slot {}", slot)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UniqueIdError {}
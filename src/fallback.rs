@@ -0,0 +1,142 @@
+use crate::{
+    HardwareProvider, Identifier, IdentifierType, IdentifierTypeDataList, SysinfoProvider,
+};
+
+/// A struct describing how `Identifier::build_with_optional_fallback` built its hash.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct FallbackInfo {
+    /// Whether the fallback IdentifierTypes were used instead of the requested ones.
+    pub used_fallback: bool,
+    /// The requested IdentifierTypes whose data could not be collected.
+    pub unavailable: Vec<IdentifierType>,
+}
+
+impl Identifier {
+    /// Builds the Identifier from the given IdentifierTypes, falling back to another set of
+    /// IdentifierTypes if the data of any of them cannot be collected. Returns the hash, hashed
+    /// like `to_string(true)`, with a FallbackInfo telling which set was used.
+    ///
+    /// The name and options of the Identifier are used, its data is ignored.
+    /// # Arguments
+    /// * `types` - The preferred IdentifierTypes.
+    /// * `fallback` - The IdentifierTypes used if any of the preferred ones is unavailable.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{Identifier, IdentifierType};
+    ///
+    /// let (hash, info) = Identifier::new("app").build_with_optional_fallback(
+    ///     &[IdentifierType::CPU, IdentifierType::RAM, IdentifierType::DISK],
+    ///     &[IdentifierType::CPU, IdentifierType::RAM],
+    /// );
+    ///
+    /// assert_eq!(hash.len(), 128);
+    /// assert_eq!(info.used_fallback, !info.unavailable.is_empty());
+    /// ```
+    pub fn build_with_optional_fallback(
+        &self,
+        types: &[IdentifierType],
+        fallback: &[IdentifierType],
+    ) -> (String, FallbackInfo) {
+        self.build_with_optional_fallback_from(&SysinfoProvider::new(), types, fallback)
+    }
+
+    /// Same as `build_with_optional_fallback`, collecting the data from the given provider.
+    pub(crate) fn build_with_optional_fallback_from(
+        &self,
+        provider: &dyn HardwareProvider,
+        types: &[IdentifierType],
+        fallback: &[IdentifierType],
+    ) -> (String, FallbackInfo) {
+        let mut info = FallbackInfo::default();
+        let mut data = Vec::new();
+
+        for identifier in types {
+            match IdentifierTypeDataList::new(identifier.clone())
+                .try_collect_with(provider, &self.options)
+            {
+                Ok(blocks) => data.extend(blocks),
+                Err(_) => info.unavailable.push(identifier.clone()),
+            }
+        }
+
+        if !info.unavailable.is_empty() {
+            info.used_fallback = true;
+            data = fallback
+                .iter()
+                .flat_map(|identifier| {
+                    IdentifierTypeDataList::new(identifier.clone())
+                        .collect_with(provider, &self.options)
+                })
+                .collect();
+        }
+
+        // The blocks are already populated, so they are rendered without collecting again.
+        let identifier = Identifier {
+            name: self.name.clone(),
+            data,
+            options: self.options.clone(),
+            description: self.description.clone(),
+        };
+
+        (identifier.to_string_with(provider, true), info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, MissingDisks, ProcessorInfo};
+
+    #[test]
+    fn test_build_with_optional_fallback() {
+        let provider = FakeProvider::new(FakeData {
+            processors: vec![ProcessorInfo {
+                brand: "Intel".to_string(),
+                vendor: "GenuineIntel".to_string(),
+                frequency: 2208,
+            }],
+            total_memory: 1024,
+            ..Default::default()
+        });
+
+        let mut identifier = Identifier::new("test");
+        identifier.options.missing_disks = MissingDisks::Error;
+        let types = [
+            IdentifierType::CPU,
+            IdentifierType::RAM,
+            IdentifierType::DISK,
+        ];
+        let fallback = [IdentifierType::CPU, IdentifierType::RAM];
+
+        let (hash, info) =
+            identifier.build_with_optional_fallback_from(&provider, &types, &fallback);
+
+        let mut expected = Identifier::new("test");
+        expected.data = fallback.clone().map(IdentifierTypeDataList::new).to_vec();
+
+        assert_eq!(
+            info,
+            FallbackInfo {
+                used_fallback: true,
+                unavailable: vec![IdentifierType::DISK],
+            }
+        );
+        assert_eq!(hash, expected.to_string_with(&provider, true));
+
+        provider.update(|data| {
+            data.disks.push(crate::DiskInfo {
+                mount_point: "/".to_string(),
+                total_space: 1,
+                removable: false,
+                ..Default::default()
+            })
+        });
+
+        let (hash, info) =
+            identifier.build_with_optional_fallback_from(&provider, &types, &fallback);
+        expected.data = types.clone().map(IdentifierTypeDataList::new).to_vec();
+
+        assert_eq!(info, FallbackInfo::default());
+        assert_eq!(hash, expected.to_string_with(&provider, true));
+    }
+}
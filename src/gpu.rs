@@ -0,0 +1,304 @@
+use crate::dmi::parse_size;
+use crate::wmi::{self, WmiRecord};
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// A single graphics adapter.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct GpuInfo {
+    /// The PCI vendor id as 4 lowercase hex digits. (e.g. 10de)
+    pub vendor: String,
+    /// The PCI device id as 4 lowercase hex digits. (e.g. 2484)
+    pub device: String,
+    /// The name of the adapter. (e.g. GA104 [GeForce RTX 3070])
+    pub name: String,
+    /// The dedicated video memory in MB, 0 if unknown or shared.
+    pub vram: u64,
+}
+
+/// Normalizes a hex id to 4 lowercase digits without prefix. (e.g. 0x10DE -> 10de)
+fn normalize_id(id: &str) -> String {
+    let id = id.trim().trim_start_matches("0x").to_lowercase();
+
+    format!("{:0>4}", id)
+}
+
+/// Returns the quoted fields of a `lspci -mm` line.
+fn quoted_fields(line: &str) -> Vec<&str> {
+    line.split('"').skip(1).step_by(2).collect()
+}
+
+/// Reads the graphics adapters from the DRM devices in sysfs, naming them with `lspci`.
+pub(crate) fn linux_gpus(provider: &dyn HardwareProvider) -> Vec<GpuInfo> {
+    let is_card = |entry: &String| {
+        entry
+            .strip_prefix("card")
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    };
+
+    provider
+        .read_dir("/sys/class/drm")
+        .iter()
+        .filter(|entry| is_card(entry))
+        .filter_map(|card| {
+            let device = format!("/sys/class/drm/{}/device", card);
+            let read = |file: &str| provider.read_to_string(&format!("{}/{}", device, file));
+
+            let slot = read("uevent").and_then(|uevent| {
+                uevent
+                    .lines()
+                    .find_map(|line| line.strip_prefix("PCI_SLOT_NAME="))
+                    .map(|slot| slot.trim().to_string())
+            });
+            let name = slot
+                .and_then(|slot| provider.run_command("lspci", &["-mm", "-s", &slot]))
+                .and_then(|output| quoted_fields(&output).get(2).map(|name| name.to_string()))
+                .unwrap_or_default();
+
+            Some(GpuInfo {
+                vendor: normalize_id(&read("vendor")?),
+                device: normalize_id(&read("device")?),
+                name,
+                vram: read("mem_info_vram_total")
+                    .and_then(|vram| vram.trim().parse::<u64>().ok())
+                    .map_or(0, |vram| vram / 1024 / 1024),
+            })
+        })
+        .collect()
+}
+
+/// Parses the Win32_VideoController WMI instances.
+pub(crate) fn parse_win32_video_controller(records: &[WmiRecord]) -> Vec<GpuInfo> {
+    records
+        .iter()
+        .map(|record| {
+            let field = |name: &str| record.get(name).map(String::as_str).unwrap_or_default();
+            let pnp_id = |prefix: &str| {
+                field("PNPDeviceID")
+                    .split(['\\', '&'])
+                    .find_map(|part| part.strip_prefix(prefix))
+                    .map(normalize_id)
+                    .unwrap_or_default()
+            };
+
+            GpuInfo {
+                vendor: pnp_id("VEN_"),
+                device: pnp_id("DEV_"),
+                name: field("Name").to_string(),
+                vram: field("AdapterRAM").parse::<u64>().unwrap_or(0) / 1024 / 1024,
+            }
+        })
+        .collect()
+}
+
+/// Parses the output of `system_profiler SPDisplaysDataType`.
+pub(crate) fn parse_system_profiler_displays(output: &str) -> Vec<GpuInfo> {
+    let mut result: Vec<GpuInfo> = Vec::new();
+
+    for line in output.lines() {
+        let (key, value) = match line.trim().split_once(": ") {
+            Some(pair) => pair,
+            None => continue,
+        };
+
+        if key == "Chipset Model" {
+            result.push(GpuInfo {
+                vendor: String::new(),
+                device: String::new(),
+                name: value.to_string(),
+                vram: 0,
+            });
+            continue;
+        }
+
+        let gpu = match result.last_mut() {
+            Some(gpu) => gpu,
+            None => continue,
+        };
+
+        match key {
+            // e.g. Vendor: AMD (0x1002)
+            "Vendor" => {
+                if let Some(id) = value.rsplit_once("(0x").map(|(_, id)| id) {
+                    gpu.vendor = normalize_id(id.trim_end_matches(')'));
+                }
+            }
+            "Device ID" => gpu.device = normalize_id(value),
+            _ if key.starts_with("VRAM") => gpu.vram = parse_size(value),
+            _ => {}
+        }
+    }
+
+    result
+}
+
+/// Reads the graphics adapters of the system.
+fn gpus(provider: &dyn HardwareProvider) -> Vec<GpuInfo> {
+    if cfg!(target_os = "windows") {
+        parse_win32_video_controller(&wmi::query(
+            provider,
+            "Win32_VideoController",
+            &["Name", "AdapterRAM", "PNPDeviceID"],
+        ))
+    } else if cfg!(target_os = "macos") {
+        provider
+            .run_command("system_profiler", &["SPDisplaysDataType"])
+            .map(|output| parse_system_profiler_displays(&output))
+            .unwrap_or_default()
+    } else {
+        linux_gpus(provider)
+    }
+}
+
+impl IdentifierTypeDataList {
+    /// Collects one block per graphics adapter, sorted by vendor and device id.
+    /// A system without graphics adapters yields a single empty `GPU()` block.
+    pub(crate) fn collect_gpu(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        let mut gpus = gpus(provider);
+        gpus.sort();
+
+        if gpus.is_empty() {
+            return vec![IdentifierTypeDataList::new(IdentifierType::GPU)];
+        }
+
+        gpus.into_iter()
+            .map(|gpu| {
+                let mut block = IdentifierTypeDataList::new(IdentifierType::GPU);
+                block.push("n", &gpu.name.to_lowercase());
+                block.push("v", &gpu.vendor);
+                block.push("d", &gpu.device);
+                block.push("m", &gpu.vram.to_string());
+                block
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierBuilder};
+
+    fn gpu(vendor: &str, device: &str, name: &str, vram: u64) -> GpuInfo {
+        GpuInfo {
+            vendor: vendor.to_string(),
+            device: device.to_string(),
+            name: name.to_string(),
+            vram,
+        }
+    }
+
+    fn file(path: &str, contents: &str) -> (String, Vec<u8>) {
+        (path.to_string(), contents.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_linux_gpus() {
+        let provider = FakeProvider::new(FakeData {
+            files: [
+                file("/sys/class/drm/card1/device/vendor", "0x1002\n"),
+                file("/sys/class/drm/card1/device/device", "0x73bf\n"),
+                file("/sys/class/drm/card1/device/mem_info_vram_total", "17163091968\n"),
+                file(
+                    "/sys/class/drm/card1/device/uevent",
+                    "DRIVER=amdgpu\nPCI_SLOT_NAME=0000:03:00.0\n",
+                ),
+                file("/sys/class/drm/card1-DP-1/status", "connected\n"),
+                file("/sys/class/drm/card0/device/vendor", "0x8086\n"),
+                file("/sys/class/drm/card0/device/device", "0x4680\n"),
+                file("/sys/class/drm/renderD128/dev", "226:128\n"),
+            ]
+            .into(),
+            commands: [(
+                "lspci -mm -s 0000:03:00.0".to_string(),
+                "03:00.0 \"VGA compatible controller\" \"Advanced Micro Devices, Inc. [AMD/ATI]\" \
+                 \"Navi 21 [Radeon RX 6800/6800 XT / 6900 XT]\" -rc1 \"Sapphire\" \"Device e438\"\n"
+                    .to_string(),
+            )]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            linux_gpus(&provider),
+            vec![
+                gpu("8086", "4680", "", 0),
+                gpu(
+                    "1002",
+                    "73bf",
+                    "Navi 21 [Radeon RX 6800/6800 XT / 6900 XT]",
+                    16368
+                ),
+            ]
+        );
+
+        if cfg!(target_os = "linux") {
+            let mut builder = IdentifierBuilder::default();
+            builder.add(IdentifierType::GPU);
+
+            assert_eq!(
+                builder.build().to_string_with(&provider, false),
+                "[GPU(n=navi 21 [radeon rx 6800/6800 xt / 6900 xt], v=1002, d=73bf, m=16368)\
+                 GPU(n=, v=8086, d=4680, m=0)]"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_win32_video_controller() {
+        let output = "\r\n\r\nName        : NVIDIA GeForce RTX 3070\r\n\
+                      AdapterRAM  : 4293918720\r\n\
+                      PNPDeviceID : PCI\\VEN_10DE&DEV_2484&SUBSYS_39011462&REV_A1\\4&2B3C1D&0&0008\r\n\r\n";
+
+        assert_eq!(
+            parse_win32_video_controller(&wmi::parse_format_list(output)),
+            vec![gpu("10de", "2484", "NVIDIA GeForce RTX 3070", 4095)]
+        );
+    }
+
+    #[test]
+    fn test_parse_system_profiler_displays() {
+        let output = "Graphics/Displays:
+
+    AMD Radeon Pro 5500M:
+
+      Chipset Model: AMD Radeon Pro 5500M
+      Type: GPU
+      VRAM (Total): 4 GB
+      Vendor: AMD (0x1002)
+      Device ID: 0x7340
+
+    Apple M1:
+
+      Chipset Model: Apple M1
+      Type: GPU
+      Vendor: Apple (0x106b)
+      Displays:
+        Color LCD:
+          Resolution: 2560 x 1600 Retina
+";
+
+        assert_eq!(
+            parse_system_profiler_displays(output),
+            vec![
+                gpu("1002", "7340", "AMD Radeon Pro 5500M", 4096),
+                gpu("106b", "", "Apple M1", 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_gpu() {
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::GPU);
+
+        assert_eq!(
+            builder
+                .build()
+                .to_string_with(&FakeProvider::default(), false),
+            "[GPU()]"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use sha3::{Digest, Sha3_256, Sha3_512};
+use tiny_keccak::{Hasher, TupleHash};
+
+use crate::ascon::ascon_hash256;
+
+/// The customization string used by the TupleHash256 algorithm.
+const TUPLE_HASH_CUSTOMIZATION: &[u8] = b"uniqueid";
+
+/// The prefix of the salt of the key derivation functions, which Argon2 requires to be at least
+/// 8 bytes long.
+#[cfg(any(feature = "argon2", feature = "scrypt"))]
+const KDF_SALT_PREFIX: &[u8] = b"uniqueid";
+
+/// Enum representing the algorithms an Identifier can be hashed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HashAlgorithm {
+    /// SHA3-256, 256 bits of output.
+    SHA3_256,
+    /// SHA3-512, 512 bits of output. This is the algorithm used by `Identifier::to_string(true)`.
+    #[default]
+    SHA3_512,
+    /// TupleHash256 (NIST SP 800-185), 512 bits of output.
+    /// When hashing an Identifier, the name, every type tag, every pair count and every key and value
+    /// are absorbed as separate tuple elements instead of hashing the formatted string.
+    TupleHash256,
+}
+
+impl HashAlgorithm {
+    /// Returns the hash algorithm as a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashAlgorithm::SHA3_256 => "SHA3-256",
+            HashAlgorithm::SHA3_512 => "SHA3-512",
+            HashAlgorithm::TupleHash256 => "TupleHash256",
+        }
+    }
+
+    /// Returns the size of the algorithm's output in bits.
+    /// # Examples
+    /// ```
+    /// use uniqueid::HashAlgorithm;
+    ///
+    /// assert_eq!(HashAlgorithm::SHA3_512.output_bits(), 512);
+    /// ```
+    pub fn output_bits(&self) -> u32 {
+        match self {
+            HashAlgorithm::SHA3_256 => 256,
+            HashAlgorithm::SHA3_512 => 512,
+            HashAlgorithm::TupleHash256 => 512,
+        }
+    }
+
+    /// Hashes the input and returns the raw digest.
+    /// TupleHash256 hashes the input as a tuple of a single element.
+    /// # Examples
+    /// ```
+    /// use uniqueid::HashAlgorithm;
+    ///
+    /// assert_eq!(HashAlgorithm::SHA3_256.digest(b"test").len(), 32);
+    /// ```
+    pub fn digest(&self, input: &[u8]) -> Vec<u8> {
+        match self {
+            HashAlgorithm::SHA3_256 => Sha3_256::digest(input).to_vec(),
+            HashAlgorithm::SHA3_512 => Sha3_512::digest(input).to_vec(),
+            HashAlgorithm::TupleHash256 => tuple_hash_256(&[input]),
+        }
+    }
+}
+
+/// Enum representing the hash algorithms considered quantum-resistant for hardware fingerprinting.
+///
+/// Grover's algorithm at best halves the preimage security of a hash function, so both algorithms
+/// keep at least 128 bits of security against a quantum adversary. Neither relies on a structure
+/// broken by Shor's algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[allow(non_camel_case_types)]
+pub enum PqAlgorithm {
+    /// Ascon-Hash256 (NIST SP 800-232), 256 bits of output, 128 bits of security.
+    /// A lightweight sponge, suited for constrained devices.
+    AsconHash256,
+    /// SHA3-512, 512 bits of output. The Keccak sponge construction keeps 256 bits of security.
+    #[default]
+    SHA3_512,
+}
+
+impl PqAlgorithm {
+    /// Returns the hash algorithm as a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PqAlgorithm::AsconHash256 => "Ascon-Hash256",
+            PqAlgorithm::SHA3_512 => "SHA3-512",
+        }
+    }
+
+    /// Returns the size of the algorithm's output in bits.
+    /// # Examples
+    /// ```
+    /// use uniqueid::PqAlgorithm;
+    ///
+    /// assert_eq!(PqAlgorithm::AsconHash256.output_bits(), 256);
+    /// ```
+    pub fn output_bits(&self) -> u32 {
+        match self {
+            PqAlgorithm::AsconHash256 => 256,
+            PqAlgorithm::SHA3_512 => 512,
+        }
+    }
+
+    /// Hashes the input and returns the raw digest.
+    /// # Examples
+    /// ```
+    /// use uniqueid::PqAlgorithm;
+    ///
+    /// assert_eq!(PqAlgorithm::AsconHash256.digest(b"test").len(), 32);
+    /// ```
+    pub fn digest(&self, input: &[u8]) -> Vec<u8> {
+        match self {
+            PqAlgorithm::AsconHash256 => ascon_hash256(input),
+            PqAlgorithm::SHA3_512 => Sha3_512::digest(input).to_vec(),
+        }
+    }
+}
+
+/// Hashes the elements as a tuple with TupleHash256 and returns the 512 bit digest.
+pub(crate) fn tuple_hash_256<T: AsRef<[u8]>>(elements: &[T]) -> Vec<u8> {
+    let mut hasher = TupleHash::v256(TUPLE_HASH_CUSTOMIZATION);
+
+    for element in elements {
+        hasher.update(element.as_ref());
+    }
+
+    let mut output = vec![0; 64];
+    hasher.finalize(&mut output);
+
+    output
+}
+
+/// Hashes a sensitive value with SHA3-256 and returns the hex digest, so that it can be included
+/// in an Identifier without exposing the raw value.
+pub(crate) fn hash_value<T: AsRef<[u8]>>(value: T) -> String {
+    to_hex(&HashAlgorithm::SHA3_256.digest(value.as_ref()))
+}
+
+/// Returns the salt of the key derivation functions: `uniqueid` followed by the name of the
+/// Identifier, if it has one.
+#[cfg(any(feature = "argon2", feature = "scrypt"))]
+pub(crate) fn kdf_salt(name: Option<&str>) -> Vec<u8> {
+    [KDF_SALT_PREFIX, name.unwrap_or_default().as_bytes()].concat()
+}
+
+/// Returns the bytes as a lowercase hex string.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tuple_hash_256_nist_sample() {
+        // NIST SP 800-185 TupleHash256 sample #4, without the crate's customization string.
+        let mut hasher = TupleHash::v256(b"");
+        hasher.update(&[0x00, 0x01, 0x02]);
+        hasher.update(&[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]);
+
+        let mut output = [0; 64];
+        hasher.finalize(&mut output);
+
+        assert_eq!(
+            to_hex(&output),
+            "cfb7058caca5e668f81a12a20a2195ce97a925f1dba3e7449a56f82201ec6073\
+             11ac2696b1ab5ea2352df1423bde7bd4bb78c9aed1a853c78672f9eb23bbe194"
+        );
+    }
+
+    #[test]
+    fn test_tuple_hash_256_is_structural() {
+        assert_ne!(tuple_hash_256(&["ab", "c"]), tuple_hash_256(&["a", "bc"]));
+        assert_ne!(tuple_hash_256(&["abc"]), tuple_hash_256(&["a", "bc"]));
+    }
+}
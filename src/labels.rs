@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use crate::Identifier;
+
+/// A struct representing an Identifier with metadata labels, such as the environment or region of
+/// the machine. The labels are not part of the hash, so they can change without changing it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LabeledIdentifier {
+    /// The labeled Identifier.
+    pub identifier: Identifier,
+    /// The labels of the Identifier. (key -> value)
+    pub labels: HashMap<String, String>,
+}
+
+impl LabeledIdentifier {
+    /// Returns the SHA3-512 hash of the Identifier, which does not depend on the labels.
+    /// (Identifier::to_string(true))
+    pub fn hash(&self) -> String {
+        self.identifier.to_string(true)
+    }
+
+    /// Returns the value of a label, if it is set.
+    pub fn label(&self, key: &str) -> Option<&str> {
+        self.labels.get(key).map(String::as_str)
+    }
+
+    /// Returns true if every label of the selector is set to the same value.
+    /// # Arguments
+    /// * `selector` - The labels to match. An empty selector matches every LabeledIdentifier.
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use uniqueid::Identifier;
+    ///
+    /// let labeled = Identifier::new("app").build_with_labels(HashMap::from([
+    ///     ("environment", "production"),
+    ///     ("region", "us-east-1"),
+    /// ]));
+    ///
+    /// assert!(labeled.matches(&HashMap::from([("environment", "production")])));
+    /// assert!(!labeled.matches(&HashMap::from([("region", "eu-west-1")])));
+    /// ```
+    pub fn matches(&self, selector: &HashMap<&str, &str>) -> bool {
+        selector
+            .iter()
+            .all(|(key, value)| self.label(key) == Some(*value))
+    }
+}
+
+impl Identifier {
+    /// Attaches metadata labels to the Identifier, without affecting its hash.
+    /// # Arguments
+    /// * `labels` - The labels to attach. (key -> value)
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// let labeled = identifier.build_with_labels(HashMap::from([("environment", "production")]));
+    ///
+    /// assert_eq!(labeled.label("environment"), Some("production"));
+    /// assert_eq!(labeled.hash(), identifier.to_string(true));
+    /// ```
+    pub fn build_with_labels(&self, labels: HashMap<&str, &str>) -> LabeledIdentifier {
+        LabeledIdentifier {
+            identifier: self.clone(),
+            labels: labels
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IdentifierType, IdentifierTypeDataList};
+
+    #[test]
+    fn test_build_with_labels() {
+        let mut ram = IdentifierTypeDataList::new(IdentifierType::RAM);
+        ram.push("t", "1024");
+
+        let identifier = Identifier {
+            name: Some("app".to_string()),
+            data: vec![ram],
+            ..Default::default()
+        };
+
+        let production = identifier.build_with_labels(HashMap::from([
+            ("environment", "production"),
+            ("region", "us-east-1"),
+        ]));
+        let staging = identifier.build_with_labels(HashMap::from([("environment", "staging")]));
+
+        assert_eq!(production.hash(), staging.hash());
+        assert_eq!(production.hash(), identifier.to_string(true));
+        assert_eq!(production.label("region"), Some("us-east-1"));
+        assert_eq!(staging.label("region"), None);
+
+        let selector = HashMap::from([("environment", "production"), ("region", "us-east-1")]);
+
+        assert!(production.matches(&selector));
+        assert!(!staging.matches(&selector));
+        assert!(staging.matches(&HashMap::new()));
+    }
+}
@@ -1,53 +1,433 @@
 #![forbid(unsafe_code)]
 #![allow(dead_code, unused_macros)]
 
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::str::FromStr;
 
-use sha3::{Digest, Sha3_512};
-use sysinfo::{self, DiskExt, ProcessorExt, System, SystemExt};
+use parse::{escape_name, escape_value};
+
+#[cfg(feature = "android")]
+mod android;
+#[cfg(feature = "argon2")]
+mod argon2;
+mod ascon;
+mod battery;
+mod binary;
+mod boot;
+mod build;
+mod certificates;
+#[cfg(feature = "cloud")]
+mod cloud;
+mod container;
+mod cpuid;
+mod csv;
+mod disk;
+mod dmi;
+#[cfg(feature = "gpu")]
+mod driver;
+mod encoding;
+mod entropy;
+mod error;
+mod fallback;
+mod gpu;
+mod hash;
+mod labels;
+mod machine;
+mod merge;
+mod microcode;
+mod net;
+mod normalize;
+mod numa;
+mod options;
+mod packages;
+mod parse;
+mod pci;
+#[cfg(feature = "pkcs11")]
+mod pkcs11;
+#[cfg(feature = "server")]
+mod power;
+mod provider;
+mod ratelimit;
+mod redact;
+mod registry;
+mod report;
+mod route;
+#[cfg(feature = "scrypt")]
+mod scrypt;
+#[cfg(feature = "services")]
+mod services;
+#[cfg(feature = "smb")]
+mod smb;
+mod snapshot;
+mod soc;
+mod stability;
+mod topology;
+#[cfg(feature = "tpm")]
+mod tpm;
+mod virt;
+#[cfg(windows)]
+mod volume;
+#[cfg(feature = "watch")]
+mod watch;
+mod wmi;
+
+#[cfg(feature = "argon2")]
+pub use argon2::Argon2Params;
+pub use build::{
+    normalize_human_readable_hash, parse_from_jwt_claim, CompactId, VersionedIdentifier,
+};
+pub use container::ContainerPolicy;
+pub use disk::DiskPolicy;
+pub use error::UniqueIdError;
+pub use fallback::FallbackInfo;
+pub use hash::{HashAlgorithm, PqAlgorithm};
+pub use labels::LabeledIdentifier;
+pub use merge::MergePolicy;
+pub use net::InterfacePolicy;
+pub use normalize::{normalize_cpu_brand_default, normalize_value};
+pub use options::{
+    BrandNormalizer, CoreCount, DiskDetail, DiskGrouping, IdentifierOptions, MissingDisks, Profile,
+    StringNormalization, GIB,
+};
+pub use provider::{
+    CpuidLeaf, DiskInfo, DiskKind, FakeData, FakeProvider, HardwareProvider, OsInfo, ProcessorInfo,
+    SysinfoProvider,
+};
+pub use redact::REDACTED;
+pub use snapshot::Snapshot;
+pub use stability::Stability;
+pub use virt::{is_virtualized, is_virtualized_with, VirtKind};
+#[cfg(feature = "watch")]
+pub use watch::IdentifierWatcher;
 
 /// Enum representing the different types of possible identifiers
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "String", try_from = "String")
+)]
 pub enum IdentifierType {
+    /// The CPU brand, vendor, frequency and core count. With the `cpuid` feature on x86 and x86_64,
+    /// the family (`fa`), model (`mo`), stepping (`st`) and a digest of the feature flags (`fl`) are
+    /// added.
+    /// Systems with several sockets add the socket count (`s`) and the brand and vendor of mixed
+    /// packages (`b1`, `v1`, ...). On ARM, the serial number (`sn`) and model (`sm`) of the SoC
+    /// are added if the board exposes them, e.g. on a Raspberry Pi. With the `android` feature on
+    /// Android, the device model (`dm`) and manufacturer (`dv`) are added. Hybrid CPUs add their
+    /// core clusters, fastest first (`cl=8@4700+8@3600`, or `cl=8+4` on Apple silicon). With
+    /// `IdentifierOptions::include_cache_topology`, the cache sizes in KB (`l1d`, `l1i`, `l2`,
+    /// `l3`) are added, leaving out the levels the CPU does not have.
     CPU,
-    // GPU, // TODO: Add GPU support
+    /// The graphics adapters: one block per adapter with its name, PCI vendor and device ids and
+    /// video memory, sorted by vendor and device id.
+    GPU,
     RAM,
+    /// The fixed disks: one block per disk, sorted by total space and device name, or one block per
+    /// physical disk with `DiskGrouping::PhysicalDisk`. Network and pseudo file systems are skipped, see
+    /// `DiskPolicy`. With `IdentifierOptions::disk_kind`, the kind of drive
+    /// (`k`) is added. With the `android` feature on Android, the emulated shared storage is
+    /// skipped.
     DISK,
-    // NET, // TODO: Add network identifier
-    // OS, // TODO: Add OS identifier
+    /// The installed application instance: hashes of the executable path, its build-id and the
+    /// install id set in `IdentifierOptions::app_install_id`. The raw path is never included.
+    APP,
+    /// The installed memory modules: one block per slot with its locator, capacity and speed,
+    /// read from the SMBIOS table. (`dmidecode` on Linux, `Win32_PhysicalMemory` on Windows)
+    INSTALLED_RAM_MODULES,
+    /// The installed CA certificates: their number and a hash of their sorted SHA-256 fingerprints.
+    /// (`/etc/ssl/certs` on Linux, the LocalMachine root store on Windows, the system keychains on macOS)
+    ENROLLED_CERTIFICATES,
+    /// The physical network adapters: one block per MAC address, sorted.
+    NET,
+    /// The operating system: its name, version, kernel version and architecture. With the `android`
+    /// feature on Android, the build fingerprint (`f`) is added.
+    OS,
+    /// The physical and virtual address sizes of the CPU in bits. (CPUID leaf 0x80000008 with the
+    /// `cpuid` feature on x86, `/proc/cpuinfo` otherwise, so empty on Windows and macOS without it)
+    VIRTUAL_ADDRESS_SIZE,
+    /// The machine id of the operating system, as a single `MACHINE(id=...)` block.
+    /// (`/etc/machine-id` on Linux, MachineGuid on Windows, IOPlatformUUID on macOS, `kern.hostuuid`
+    /// on FreeBSD)
+    MACHINE,
+    /// The manufacturer, product name and serial number of the baseboard.
+    /// (`/sys/class/dmi/id/board_*` on Linux, WMI `Win32_BaseBoard` on Windows)
+    BOARD,
+    /// The vendor, version and release date of the firmware. (`/sys/class/dmi/id/bios_*` on Linux,
+    /// WMI `Win32_BIOS` on Windows, the boot ROM version on macOS)
+    BIOS,
+    /// The name and version of every installed graphics driver. Requires the `gpu` feature.
+    /// (`/proc/driver/nvidia/version` and Mesa on Linux, the display adapter registry class on Windows)
+    #[cfg(feature = "gpu")]
+    GRAPHICS_DRIVER_VERSION,
+    /// The host name of the system, lowercased, or `unknown`. Opt-in only, as users can change it:
+    /// no profile or default set includes it.
+    HOST,
+    /// The PCI devices: the number of distinct vendor:device pairs and a hash of their sorted list,
+    /// without hot-pluggable classes. (`/sys/bus/pci/devices` on Linux, `Win32_PnPEntity` on Windows)
+    PCI,
+    /// A hash of the public part of the TPM Endorsement Key. Requires the `tpm` feature.
+    /// (the TPM Software Stack, `libtss2-esys`, on Linux, `Get-TpmEndorsementKeyInfo` on Windows)
+    #[cfg(feature = "tpm")]
+    TPM,
+    /// The battery packs: one block per battery with its manufacturer, model and serial number,
+    /// sorted by serial number. (`/sys/class/power_supply` on Linux, WMI on Windows, IOKit on macOS)
+    BATTERY,
+    /// The serial number of the system volume as 8 hex digits, assigned when it is formatted.
+    /// Only available on Windows. (`Win32_LogicalDisk` of `%SystemRoot%`)
+    #[cfg(windows)]
+    VOLUME,
+    /// The hypervisor or container runtime the process runs in, or `none`, see `is_virtualized`.
+    /// (`/.dockerenv`, `/proc/1/cgroup`, the CPUID hypervisor leaf with the `cpuid` feature and the
+    /// SMBIOS system manufacturer)
+    VIRT,
+    /// The models of the power supplies of servers, one block per power supply sorted by model.
+    /// Requires the `server` feature. (`ipmitool fru print`, `/sys/class/power_supply` on Linux)
+    #[cfg(feature = "server")]
+    POWER_SUPPLY,
+    /// The serial number of the chassis. Sensitive, so only collected after
+    /// `IdentifierBuilder::include_chassis_serial(true)`. (SMBIOS on Linux and Windows, IOKit on macOS)
+    CHASSIS_SERIAL,
+    /// The SMBIOS system UUID, flagged with `bogus=1` if it is a value shared by many machines.
+    /// (product_uuid on Linux, Win32_ComputerSystemProduct on Windows, `smbios.system.uuid` on
+    /// FreeBSD, unavailable on macOS)
+    SYSUUID,
+    /// The serial number of the system, `unprovisioned` if it is a placeholder.
+    /// (product_serial on Linux, Win32_BIOS on Windows, IOPlatformSerialNumber on macOS)
+    SERIAL,
+    /// The microcode revision of the CPU, which changes with security updates.
+    /// (sysfs or `/proc/cpuinfo` on Linux, unavailable on other platforms)
+    CPU_MICROCODE,
+    /// The id of the current container and the UID of its Kubernetes pod, `none` outside of a
+    /// container. Added by `IdentifierBuilder::container_policy(ContainerPolicy::IncludeContainerId)`.
+    CONTAINER,
+    /// The cloud provider and the id of the instance, read from the local metadata service of AWS
+    /// (IMDSv2), Google Cloud or Azure within `IdentifierOptions::cloud_timeout`.
+    /// Requires the `cloud` feature, never added by default.
+    #[cfg(feature = "cloud")]
+    CLOUD,
+    /// The NUMA nodes: one block per node with its number (`n`), total memory in KB (`m`) and
+    /// logical processors (`c`). Only collected on Linux.
+    MEMORY_TOPOLOGY,
+    /// The default IPv4 routes: one block per route with its gateway (`g`) and output interface
+    /// (`i`), preferred route first. Only collected on Linux.
+    ROUTING_TABLE,
+    /// The available entropy of the kernel pool in bits (`e`). Only collected on Linux.
+    ///
+    /// This component is volatile: it changes with the activity of the system from one read to the
+    /// next, so it is only useful for session-scoped tokens, combined with stable components. Since
+    /// Linux 5.18 the value is always 256.
+    SYSTEM_ENTROPY,
+    /// A SHA-256 hash of the sorted list of installed OS packages, from `dpkg --list` or
+    /// `rpm -qa` on Linux and `pkgutil --pkgs` on macOS. Fingerprints the OS configuration rather
+    /// than the hardware and changes with every package installed, removed or upgraded.
+    INSTALLED_PACKAGES_HASH,
+    /// A hash of the sorted names and paths of the Samba shares configured in
+    /// `/etc/samba/smb.conf`. Linux only.
+    #[cfg(feature = "smb")]
+    SMB_SHARES,
+    /// A hash of the sorted names of the enabled system services, from
+    /// `systemctl list-unit-files --state=enabled` on Linux and the services started automatically
+    /// in the registry on Windows. Requires the `services` feature.
+    #[cfg(feature = "services")]
+    INSTALLED_SERVICES,
+    /// A user-defined component, see `IdentifierType::custom`.
+    /// Custom components have no collector, their data has to be provided.
+    Custom(String),
 }
 
 impl IdentifierType {
     /// Returns the identifier type as a string
-    pub fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &str {
         match self {
             IdentifierType::CPU => "CPU",
-            // IdentifierType::GPU => "GPU",
+            IdentifierType::GPU => "GPU",
             IdentifierType::RAM => "RAM",
             IdentifierType::DISK => "DISK",
-            // IdentifierType::NET => "NET",
-            // IdentifierType::OS => "OS",
+            IdentifierType::APP => "APP",
+            IdentifierType::INSTALLED_RAM_MODULES => "INSTALLED_RAM_MODULES",
+            IdentifierType::ENROLLED_CERTIFICATES => "ENROLLED_CERTIFICATES",
+            IdentifierType::NET => "NET",
+            IdentifierType::OS => "OS",
+            IdentifierType::VIRTUAL_ADDRESS_SIZE => "VIRTUAL_ADDRESS_SIZE",
+            IdentifierType::MACHINE => "MACHINE",
+            IdentifierType::BOARD => "BOARD",
+            IdentifierType::BIOS => "BIOS",
+            #[cfg(feature = "gpu")]
+            IdentifierType::GRAPHICS_DRIVER_VERSION => "GRAPHICS_DRIVER_VERSION",
+            IdentifierType::HOST => "HOST",
+            IdentifierType::PCI => "PCI",
+            #[cfg(feature = "tpm")]
+            IdentifierType::TPM => "TPM",
+            IdentifierType::BATTERY => "BATTERY",
+            #[cfg(windows)]
+            IdentifierType::VOLUME => "VOLUME",
+            IdentifierType::VIRT => "VIRT",
+            #[cfg(feature = "server")]
+            IdentifierType::POWER_SUPPLY => "POWER_SUPPLY",
+            IdentifierType::CHASSIS_SERIAL => "CHASSIS_SERIAL",
+            IdentifierType::SYSUUID => "SYSUUID",
+            IdentifierType::SERIAL => "SERIAL",
+            IdentifierType::CPU_MICROCODE => "CPU_MICROCODE",
+            IdentifierType::CONTAINER => "CONTAINER",
+            #[cfg(feature = "cloud")]
+            IdentifierType::CLOUD => "CLOUD",
+            IdentifierType::MEMORY_TOPOLOGY => "MEMORY_TOPOLOGY",
+            IdentifierType::ROUTING_TABLE => "ROUTING_TABLE",
+            IdentifierType::SYSTEM_ENTROPY => "SYSTEM_ENTROPY",
+            IdentifierType::INSTALLED_PACKAGES_HASH => "INSTALLED_PACKAGES_HASH",
+            #[cfg(feature = "smb")]
+            IdentifierType::SMB_SHARES => "SMB_SHARES",
+            #[cfg(feature = "services")]
+            IdentifierType::INSTALLED_SERVICES => "INSTALLED_SERVICES",
+            IdentifierType::Custom(tag) => tag,
+        }
+    }
+
+    /// Returns the built-in identifier type with the given name, if any.
+    fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "CPU" => Some(IdentifierType::CPU),
+            "GPU" => Some(IdentifierType::GPU),
+            "RAM" => Some(IdentifierType::RAM),
+            "DISK" => Some(IdentifierType::DISK),
+            "APP" => Some(IdentifierType::APP),
+            "INSTALLED_RAM_MODULES" => Some(IdentifierType::INSTALLED_RAM_MODULES),
+            "ENROLLED_CERTIFICATES" => Some(IdentifierType::ENROLLED_CERTIFICATES),
+            "NET" => Some(IdentifierType::NET),
+            "OS" => Some(IdentifierType::OS),
+            "VIRTUAL_ADDRESS_SIZE" => Some(IdentifierType::VIRTUAL_ADDRESS_SIZE),
+            "MACHINE" => Some(IdentifierType::MACHINE),
+            "BOARD" => Some(IdentifierType::BOARD),
+            "BIOS" => Some(IdentifierType::BIOS),
+            #[cfg(feature = "gpu")]
+            "GRAPHICS_DRIVER_VERSION" => Some(IdentifierType::GRAPHICS_DRIVER_VERSION),
+            "HOST" => Some(IdentifierType::HOST),
+            "PCI" => Some(IdentifierType::PCI),
+            #[cfg(feature = "tpm")]
+            "TPM" => Some(IdentifierType::TPM),
+            "BATTERY" => Some(IdentifierType::BATTERY),
+            #[cfg(windows)]
+            "VOLUME" => Some(IdentifierType::VOLUME),
+            "VIRT" => Some(IdentifierType::VIRT),
+            #[cfg(feature = "server")]
+            "POWER_SUPPLY" => Some(IdentifierType::POWER_SUPPLY),
+            "CHASSIS_SERIAL" => Some(IdentifierType::CHASSIS_SERIAL),
+            "SYSUUID" => Some(IdentifierType::SYSUUID),
+            "SERIAL" => Some(IdentifierType::SERIAL),
+            "CPU_MICROCODE" => Some(IdentifierType::CPU_MICROCODE),
+            "CONTAINER" => Some(IdentifierType::CONTAINER),
+            #[cfg(feature = "cloud")]
+            "CLOUD" => Some(IdentifierType::CLOUD),
+            "MEMORY_TOPOLOGY" => Some(IdentifierType::MEMORY_TOPOLOGY),
+            "ROUTING_TABLE" => Some(IdentifierType::ROUTING_TABLE),
+            "SYSTEM_ENTROPY" => Some(IdentifierType::SYSTEM_ENTROPY),
+            "INSTALLED_PACKAGES_HASH" => Some(IdentifierType::INSTALLED_PACKAGES_HASH),
+            #[cfg(feature = "smb")]
+            "SMB_SHARES" => Some(IdentifierType::SMB_SHARES),
+            #[cfg(feature = "services")]
+            "INSTALLED_SERVICES" => Some(IdentifierType::INSTALLED_SERVICES),
+            _ => None,
+        }
+    }
+
+    /// Creates a custom IdentifierType.
+    /// The tag must be non-empty, start with an uppercase ASCII letter, only contain uppercase
+    /// ASCII letters, digits and underscores, and not be the name of a built-in type.
+    /// # Examples
+    /// ```
+    /// use uniqueid::IdentifierType;
+    ///
+    /// assert!(IdentifierType::custom("DONGLE").is_ok());
+    /// assert!(IdentifierType::custom("dongle").is_err());
+    /// assert!(IdentifierType::custom("CPU").is_err());
+    /// ```
+    pub fn custom<T: Into<String>>(tag: T) -> Result<Self, UniqueIdError> {
+        let tag = tag.into();
+
+        let valid = tag.starts_with(|c: char| c.is_ascii_uppercase())
+            && tag
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+
+        if !valid || IdentifierType::builtin(&tag).is_some() {
+            return Err(UniqueIdError::InvalidTag(tag));
+        }
+
+        Ok(IdentifierType::Custom(tag))
+    }
+}
+
+impl FromStr for IdentifierType {
+    type Err = UniqueIdError;
+
+    /// Parses a built-in identifier type name or a valid custom tag.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match IdentifierType::builtin(name) {
+            Some(identifier) => Ok(identifier),
+            None => IdentifierType::custom(name),
         }
     }
 }
 
 impl From<&str> for IdentifierType {
     /// Converts a string to an IdentifierType
+    /// # Panics
+    /// Panics if the name is neither a built-in type nor a valid custom tag.
     fn from(name: &str) -> Self {
-        match name {
-            "CPU" => IdentifierType::CPU,
-            // "GPU" => IdentifierType::GPU,
-            "RAM" => IdentifierType::RAM,
-            "DISK" => IdentifierType::DISK,
-            // "NET" => IdentifierType::NET,
-            // "OS" => IdentifierType::OS,
-            _ => panic!("Unknown identifier type name: {}", name),
-        }
+        name.parse()
+            .unwrap_or_else(|_| panic!("Unknown identifier type name: {}", name))
+    }
+}
+
+impl TryFrom<String> for IdentifierType {
+    type Error = UniqueIdError;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        name.parse()
+    }
+}
+
+impl From<IdentifierType> for String {
+    fn from(identifier: IdentifierType) -> Self {
+        identifier.as_str().to_string()
     }
 }
 
+/// The value of a key whose source is not available on the system.
+const UNKNOWN: &str = "unknown";
+
+/// The tag of the custom component added by `IdentifierBuilder::add_cpu_architecture`.
+const ARCH_TAG: &str = "ARCH";
+
+/// The version of the canonical string hashed by `Identifier::to_string`. Hashes built without a
+/// version prefix are version 1, see `Identifier::to_string_v1`. Version 2 hashes are built with
+/// `StringNormalization::Legacy`, version 3 added the strict normalization of collected values.
+pub const FORMAT_VERSION: u32 = 3;
+
+/// The prefix of the canonical string, followed by the format version and `:`.
+const FORMAT_PREFIX: &str = "uid:v";
+
+/// The key of the metadata pair holding the label of a component, see `IdentifierBuilder::add_with_label`.
+pub const LABEL_KEY: &str = "_label";
+
+/// Returns true if the key holds metadata, which is not part of the built or hashed Identifier.
+/// Metadata keys start with an underscore.
+pub(crate) fn is_metadata(key: &str) -> bool {
+    key.starts_with('_')
+}
+
+/// The raw key-value data of a collected component.
+pub type ComponentData = HashMap<String, String>;
+
+/// A collected component as a (type, key-value data) tuple.
+pub type RawComponent = (IdentifierType, ComponentData);
+
 /// A struct representing the key-value pairs of an identifier's type data.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IdentifierTypeData {
     /// The key of the IdentifierTypeData object.
     pub key: String,
@@ -107,6 +487,8 @@ impl IdentifierTypeData {
 
 impl Display for IdentifierTypeData {
     /// Returns the key and value in normal format. (key=value)
+    /// Characters of the format (`%`, `,`, `=` and unbalanced parentheses) are
+    /// percent-encoded, so a value can not be mistaken for several pairs.
     /// # Examples
     /// ```
     /// use uniqueid::IdentifierTypeData;
@@ -114,9 +496,15 @@ impl Display for IdentifierTypeData {
     /// let data = IdentifierTypeData::new("key", "value");
     ///
     /// assert_eq!(data.to_string(), "key=value");
+    /// assert_eq!(IdentifierTypeData::new("b", "a=b, c").to_string(), "b=a%3Db%2C c");
     /// ```
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}={}", self.key, self.value)
+        write!(
+            f,
+            "{}={}",
+            escape_value(&self.key),
+            escape_value(&self.value)
+        )
     }
 }
 
@@ -167,6 +555,7 @@ impl IdentifierTypeDataBuilder {
     }
 
     /// Builds the IdentifierTypeData object into a string.
+    /// A builder without any pair renders as the bare type tag, e.g. `CPU()`.
     /// # Examples
     /// ```
     /// use uniqueid::IdentifierTypeDataBuilder;
@@ -177,27 +566,29 @@ impl IdentifierTypeDataBuilder {
     /// builder.add("key", "value");
     ///
     /// assert_eq!(builder.build(), "CPU(key=value)");
+    /// assert_eq!(IdentifierTypeDataBuilder::new(IdentifierType::CPU).build(), "CPU()");
     /// ```
     pub fn build(self) -> String {
-        let mut data = String::new();
-
-        data.push_str(self.identifier.as_str());
-        data.push('(');
-
-        for item in self.data {
-            data.push_str(&format!("{}={}, ", item.key, item.value));
-        }
-
-        data.pop();
-        data.pop();
+        self.render(true)
+    }
 
-        data.push(')');
+    /// Renders the block, with the keys and values escaped or as-is for the unversioned format.
+    fn render(self, escape: bool) -> String {
+        let pairs: Vec<String> = self
+            .data
+            .iter()
+            .map(|item| match escape {
+                true => item.to_string(),
+                false => format!("{}={}", item.key, item.value),
+            })
+            .collect();
 
-        data
+        format!("{}({})", self.identifier.as_str(), pairs.join(", "))
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IdentifierTypeDataList {
     /// The name of the IdentifierType object. (CPU, RAM, DISK, ...)
     pub identifier: IdentifierType,
@@ -224,87 +615,577 @@ impl IdentifierTypeDataList {
         }
     }
 
+    /// Builds the IdentifierTypeDataList object into a string, collecting the data from the system.
     pub fn build(&self) -> String {
-        match self.identifier {
-            IdentifierType::CPU => self.build_cpu(),
-            // IdentifierType::GPU => self.build_gpu(),
-            IdentifierType::RAM => self.build_ram(),
-            IdentifierType::DISK => self.build_disk(),
-            // IdentifierType::NET => self.build_net(),
-            // IdentifierType::OS => self.build_os(),
+        self.build_with(&SysinfoProvider::new(), &IdentifierOptions::default())
+    }
+
+    /// Builds the IdentifierTypeDataList object into a string, collecting the data from the given provider.
+    pub fn build_with(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+    ) -> String {
+        self.render_with(provider, options, true)
+    }
+
+    /// Builds the IdentifierTypeDataList object into a string, escaping the keys and values or
+    /// leaving them as-is for the unversioned format.
+    fn render_with(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+        escape: bool,
+    ) -> String {
+        let mut result = String::new();
+
+        for block in self.collect_with(provider, options) {
+            let mut identifier_type = IdentifierTypeDataBuilder::new(block.identifier);
+            for item in block
+                .data
+                .into_iter()
+                .filter(|item| !is_metadata(&item.key))
+            {
+                identifier_type.add(item.key, item.value);
+            }
+            result.push_str(&identifier_type.render(escape));
+        }
+
+        result
+    }
+
+    /// Collects the data of the IdentifierType from the system.
+    /// Returns one IdentifierTypeDataList per block, e.g. one per disk for DISK.
+    /// If the data has already been populated, it is returned as-is.
+    /// # Examples
+    /// ```
+    /// use uniqueid::IdentifierTypeDataList;
+    /// use uniqueid::IdentifierType;
+    ///
+    /// let data = IdentifierTypeDataList::new(IdentifierType::RAM);
+    /// let blocks = data.collect();
+    ///
+    /// assert_eq!(blocks.len(), 1);
+    /// assert_eq!(blocks[0].data[0].key, "t");
+    /// ```
+    pub fn collect(&self) -> Vec<IdentifierTypeDataList> {
+        self.collect_with(&SysinfoProvider::new(), &IdentifierOptions::default())
+    }
+
+    /// Collects the data of the IdentifierType from the given provider using the given options.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{FakeData, FakeProvider, IdentifierOptions, IdentifierType, IdentifierTypeDataList};
+    ///
+    /// let provider = FakeProvider::new(FakeData {
+    ///     total_memory: 1024,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// let data = IdentifierTypeDataList::new(IdentifierType::RAM);
+    ///
+    /// assert_eq!(data.build_with(&provider, &IdentifierOptions::default()), "RAM(t=1024)");
+    /// ```
+    pub fn collect_with(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+    ) -> Vec<IdentifierTypeDataList> {
+        let (metadata, data): (Vec<_>, Vec<_>) =
+            self.data.iter().partition(|item| is_metadata(&item.key));
+
+        if !data.is_empty() {
+            return vec![self.clone()];
+        }
+
+        let mut blocks = match self.identifier {
+            IdentifierType::CPU => self.collect_cpu(provider, options),
+            IdentifierType::GPU => self.collect_gpu(provider),
+            IdentifierType::RAM => self.collect_ram(provider, options),
+            IdentifierType::DISK => self.collect_disk(provider, options),
+            IdentifierType::APP => self.collect_app(provider, options),
+            IdentifierType::INSTALLED_RAM_MODULES => self.collect_installed_ram_modules(provider),
+            IdentifierType::ENROLLED_CERTIFICATES => self.collect_enrolled_certificates(provider),
+            IdentifierType::NET => self.collect_net(provider, options),
+            IdentifierType::OS => self.collect_os(provider),
+            IdentifierType::VIRTUAL_ADDRESS_SIZE => self.collect_virtual_address_size(provider),
+            IdentifierType::MACHINE => self.collect_machine(provider),
+            IdentifierType::BOARD => self.collect_board(provider),
+            IdentifierType::BIOS => self.collect_bios(provider, options),
+            #[cfg(feature = "gpu")]
+            IdentifierType::GRAPHICS_DRIVER_VERSION => {
+                self.collect_graphics_driver_version(provider)
+            }
+            IdentifierType::HOST => self.collect_host(provider),
+            IdentifierType::PCI => self.collect_pci(provider),
+            #[cfg(feature = "tpm")]
+            IdentifierType::TPM => self.collect_tpm(provider),
+            IdentifierType::BATTERY => self.collect_battery(provider, options),
+            #[cfg(windows)]
+            IdentifierType::VOLUME => self.collect_volume(provider),
+            IdentifierType::VIRT => self.collect_virt(provider),
+            #[cfg(feature = "server")]
+            IdentifierType::POWER_SUPPLY => self.collect_power_supply(provider),
+            IdentifierType::CHASSIS_SERIAL => self.collect_chassis_serial(provider, options),
+            IdentifierType::SYSUUID => self.collect_sysuuid(provider, options),
+            IdentifierType::SERIAL => self.collect_serial(provider),
+            IdentifierType::CPU_MICROCODE => self.collect_cpu_microcode(provider),
+            IdentifierType::CONTAINER => self.collect_container(provider),
+            #[cfg(feature = "cloud")]
+            IdentifierType::CLOUD => self.collect_cloud(provider, options),
+            IdentifierType::MEMORY_TOPOLOGY => self.collect_memory_topology(provider),
+            IdentifierType::ROUTING_TABLE => self.collect_routing_table(provider),
+            IdentifierType::SYSTEM_ENTROPY => self.collect_system_entropy(provider),
+            IdentifierType::INSTALLED_PACKAGES_HASH => {
+                self.collect_installed_packages_hash(provider)
+            }
+            #[cfg(feature = "smb")]
+            IdentifierType::SMB_SHARES => self.collect_smb_shares(provider),
+            #[cfg(feature = "services")]
+            IdentifierType::INSTALLED_SERVICES => self.collect_installed_services(provider),
+            IdentifierType::Custom(_) => Vec::new(),
+        };
+
+        for block in &mut blocks {
+            block.normalize_values(options);
         }
+
+        // Metadata such as the label is carried over to every collected block.
+        for block in &mut blocks {
+            block
+                .data
+                .extend(metadata.iter().map(|item| (*item).clone()));
+        }
+
+        blocks
     }
 
-    fn build_cpu(&self) -> String {
-        let mut sys = System::new_all();
+    /// Collects the data of the IdentifierType from the given provider, failing if no data is available.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{FakeProvider, IdentifierOptions, IdentifierType, IdentifierTypeDataList, UniqueIdError};
+    ///
+    /// let data = IdentifierTypeDataList::new(IdentifierType::CPU);
+    /// let result = data.try_collect_with(&FakeProvider::default(), &IdentifierOptions::default());
+    ///
+    /// assert_eq!(result, Err(UniqueIdError::SourceUnavailable(IdentifierType::CPU)));
+    /// ```
+    /// # Errors
+    /// Returns `UniqueIdError::SourceUnavailable` if the collector produced no key-value pairs,
+    /// not counting metadata.
+    pub fn try_collect_with(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+    ) -> Result<Vec<IdentifierTypeDataList>, UniqueIdError> {
+        let blocks = self.collect_with(provider, options);
 
-        sys.refresh_all();
+        if blocks
+            .iter()
+            .all(|block| block.data.iter().all(|item| is_metadata(&item.key)))
+        {
+            return Err(UniqueIdError::SourceUnavailable(self.identifier.clone()));
+        }
 
-        let cpu = sys.processors();
-        let brand = cpu[0].brand();
-        let vendor = cpu[0].vendor_id();
-        let frequency = cpu[0].frequency();
-        let cores = cpu.len();
+        Ok(blocks)
+    }
 
-        let mut result = String::new();
+    /// Returns the differences between the key-value pairs of this IdentifierTypeDataList object
+    /// and another one of the same type. Pairs only present in `self` are removed, pairs only
+    /// present in `other` are added.
+    /// # Arguments
+    /// * `other` - The newer IdentifierTypeDataList object.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierTypeDataList, IdentifierType};
+    ///
+    /// let old: IdentifierTypeDataList = "CPU(b=intel, f=3600)".parse().unwrap();
+    /// let new: IdentifierTypeDataList = "CPU(b=intel, f=4200, c=8)".parse().unwrap();
+    ///
+    /// let diff = old.diff(&new);
+    ///
+    /// assert_eq!(diff.added, vec![("c".to_string(), "8".to_string())]);
+    /// assert!(diff.removed.is_empty());
+    /// assert_eq!(diff.changed, vec![("f".to_string(), "3600".to_string(), "4200".to_string())]);
+    /// ```
+    pub fn diff(&self, other: &IdentifierTypeDataList) -> ComponentDiff {
+        let data = |list: &IdentifierTypeDataList| -> ComponentData {
+            list.data
+                .iter()
+                .map(|item| (item.key.clone(), item.value.clone()))
+                .collect()
+        };
 
-        let mut identifier_type = IdentifierTypeDataBuilder::new(IdentifierType::CPU);
-        identifier_type.add("b", brand.to_lowercase().trim());
-        identifier_type.add("v", vendor.to_lowercase().trim());
-        identifier_type.add("f", &frequency.to_string());
-        identifier_type.add("c", &cores.to_string());
-        result.push_str(&identifier_type.build());
+        ComponentDiff::new(&data(self), &data(other))
+    }
 
-        result
+    /// Adds a key-value pair to the data of the IdentifierTypeDataList object.
+    pub(crate) fn push<T: Into<String>>(&mut self, key: T, value: T) -> &mut Self {
+        self.data.push(IdentifierTypeData {
+            key: key.into(),
+            value: value.into(),
+        });
+
+        self
     }
 
-    fn build_ram(&self) -> String {
-        let mut sys = System::new_all();
+    fn collect_cpu(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+    ) -> Vec<IdentifierTypeDataList> {
+        let cpu = provider.processors();
+        if cpu.is_empty() {
+            return Vec::new();
+        }
 
-        sys.refresh_all();
+        // The brand and vendor of every package, in the order the processors are listed.
+        let mut packages: Vec<(&str, &str)> = Vec::new();
+        for processor in &cpu {
+            let package = (processor.brand.as_str(), processor.vendor.as_str());
+            if !packages.contains(&package) {
+                packages.push(package);
+            }
+        }
 
-        let ram = sys.total_memory();
+        // The first processor may be a performance or an efficiency core on hybrid CPUs, depending
+        // on the boot, so the highest frequency of all processors is used.
+        let frequency = cpu
+            .iter()
+            .map(|processor| processor.frequency)
+            .max()
+            .unwrap_or_default();
+        let logical = cpu.len();
+        let physical = provider.physical_core_count();
 
-        let mut result = String::new();
+        let mut block = IdentifierTypeDataList::new(IdentifierType::CPU);
+        for (i, (brand, vendor)) in packages.into_iter().enumerate() {
+            // The first package keeps the original keys, mixed packages are numbered from 1.
+            let suffix = match i {
+                0 => String::new(),
+                i => i.to_string(),
+            };
 
-        let mut identifier_type = IdentifierTypeDataBuilder::new(IdentifierType::RAM);
-        identifier_type.add("t", &ram.to_string());
-        result.push_str(&identifier_type.build());
+            match &options.cpu_brand_normalization {
+                Some(normalizer) => block.push(format!("b{}", suffix), normalizer.normalize(brand)),
+                None => block.push(
+                    format!("b{}", suffix),
+                    brand.to_lowercase().trim().to_string(),
+                ),
+            };
+            block.push(
+                format!("v{}", suffix),
+                vendor.to_lowercase().trim().to_string(),
+            );
+        }
+        if options.include_cpu_frequency {
+            let frequency = match options.cpu_frequency_granularity.filter(|g| *g > 0) {
+                // Round to the nearest multiple of the granularity.
+                Some(granularity) => (frequency + granularity / 2) / granularity * granularity,
+                None => frequency,
+            };
+            block.push("f", &frequency.to_string());
+        }
+        #[cfg(feature = "cpuid")]
+        if let Some(signature) = cpuid::cpu_signature(provider) {
+            block.push("fa", &signature.family.to_string());
+            block.push("mo", &signature.model.to_string());
+            block.push("st", &signature.stepping.to_string());
+            block.push("fl", &signature.features);
+        }
+        match options.cpu_core_count {
+            CoreCount::Logical => block.push("c", &logical.to_string()),
+            CoreCount::Physical => block.push("c", &physical.unwrap_or(logical).to_string()),
+            CoreCount::Both => {
+                let physical = physical.map_or_else(|| UNKNOWN.to_string(), |p| p.to_string());
+                block.push("pc", &physical);
+                block.push("lc", &logical.to_string())
+            }
+        };
+        // Only written for several sockets, so single socket identifiers are unchanged.
+        if let Some(sockets) = topology::socket_count(provider).filter(|sockets| *sockets > 1) {
+            block.push("s", &sockets.to_string());
+        }
+        // Only written for hybrid CPUs, so identifiers of uniform cores are unchanged.
+        let clusters = topology::core_clusters(provider);
+        if clusters.len() > 1 {
+            let clusters: Vec<String> = clusters.iter().map(|c| c.as_value()).collect();
+            block.push("cl", &clusters.join("+"));
+        }
+        if options.include_cache_topology {
+            for (key, size) in topology::cache_sizes(provider).pairs() {
+                block.push(key, &size.to_string());
+            }
+        }
+        // Only read on ARM, where every board of a model has the same brand and frequency.
+        if cfg!(any(target_arch = "aarch64", target_arch = "arm")) {
+            let soc = soc::soc_info(provider);
+            if let Some(serial) = soc.serial {
+                block.push("sn", &serial);
+            }
+            if let Some(model) = soc.model {
+                block.push("sm", &model.to_lowercase());
+            }
+        }
+        #[cfg(feature = "android")]
+        if cfg!(target_os = "android") {
+            if let Some(model) = android::property(provider, "ro.product.model") {
+                block.push("dm", &model.to_lowercase());
+            }
+            if let Some(manufacturer) = android::property(provider, "ro.product.manufacturer") {
+                block.push("dv", &manufacturer.to_lowercase());
+            }
+        }
 
-        result
+        vec![block]
     }
 
-    fn build_disk(&self) -> String {
-        let mut sys = System::new_all();
+    fn collect_ram(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+    ) -> Vec<IdentifierTypeDataList> {
+        let mut ram = provider.total_memory();
 
-        sys.refresh_all();
+        if let Some(granularity) = options.ram_granularity.filter(|g| *g > 0) {
+            // Round to the nearest multiple of the granularity.
+            ram = (ram + granularity / 2) / granularity * granularity;
+        }
 
-        let disks = sys.disks();
+        let mut block = IdentifierTypeDataList::new(IdentifierType::RAM);
+        block.push("t", &ram.to_string());
 
-        let mut result = String::new();
+        #[cfg(feature = "smbios")]
+        if options.ram_dimm_details {
+            for (key, value) in dmi::dimm_details(provider) {
+                block.push(key, value);
+            }
+        }
+
+        vec![block]
+    }
+
+    fn collect_disk(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+    ) -> Vec<IdentifierTypeDataList> {
+        match select_disks(provider, options) {
+            DiskSelection::Disks(disks) => disks
+                .iter()
+                .map(|(disk, total_space)| disk::disk_block(provider, options, disk, *total_space))
+                .collect(),
+            DiskSelection::NoEligibleDisks => match options.missing_disks {
+                MissingDisks::Marker => {
+                    let mut block = IdentifierTypeDataList::new(IdentifierType::DISK);
+                    block.push("none", "1");
+                    vec![block]
+                }
+                MissingDisks::Error => Vec::new(),
+            },
+        }
+    }
+
+    fn collect_os(&self, provider: &dyn HardwareProvider) -> Vec<IdentifierTypeDataList> {
+        let os = provider.os();
+
+        // Missing values are rendered as a placeholder, so the key set is always the same.
+        let value = |value: Option<String>| {
+            value
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .unwrap_or_else(|| UNKNOWN.to_string())
+        };
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::OS);
+        block.push("n", &value(os.name));
+        block.push("v", &value(os.version));
+        block.push("k", &value(os.kernel_version));
+        block.push("a", &value(os.architecture));
+        #[cfg(feature = "android")]
+        if cfg!(target_os = "android") {
+            block.push(
+                "f",
+                &value(android::property(provider, "ro.build.fingerprint")),
+            );
+        }
+
+        vec![block]
+    }
+
+    fn collect_app(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+    ) -> Vec<IdentifierTypeDataList> {
+        let mut block = IdentifierTypeDataList::new(IdentifierType::APP);
+
+        if let Some(path) = provider.current_exe() {
+            block.push("p", &hash::hash_value(&path));
+
+            // Executables without a build identity are identified by their path alone.
+            let identity = provider
+                .read_file(&path)
+                .and_then(|bytes| binary::build_identity(&bytes));
+            if let Some(identity) = identity {
+                block.push("b", &hash::hash_value(identity));
+            }
+        }
+
+        if let Some(install_id) = &options.app_install_id {
+            block.push("i", &hash::hash_value(install_id));
+        }
+
+        if block.data.is_empty() {
+            return Vec::new();
+        }
+
+        vec![block]
+    }
+
+    fn collect_host(&self, provider: &dyn HardwareProvider) -> Vec<IdentifierTypeDataList> {
+        let host_name = provider
+            .os()
+            .host_name
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| UNKNOWN.to_string());
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::HOST);
+        block.push("n", &host_name);
+
+        vec![block]
+    }
+}
+
+/// The disks selected for the DISK component, telling a machine without eligible disks apart
+/// from a collected list.
+enum DiskSelection {
+    /// The selected disks with the total space of their block, in a stable order.
+    Disks(Vec<(DiskInfo, u64)>),
+    /// No disk is eligible, e.g. every disk is removable on a machine booted from USB.
+    NoEligibleDisks,
+}
+
+/// Selects the disks of the DISK component with their total space, in a stable order.
+fn select_disks(provider: &dyn HardwareProvider, options: &IdentifierOptions) -> DiskSelection {
+    let mut disks = provider.disks();
+
+    // The platforms list the disks in enumeration order, which can change between boots, such
+    // as sda and sdb swapping on Linux or on every boot on FreeBSD.
+    disks.sort_by(|a, b| (a.total_space, &a.device).cmp(&(b.total_space, &b.device)));
+
+    if let Some(path) = &options.disk_device_path {
+        let mut devices: Vec<DiskInfo> = Vec::new();
 
         for disk in disks {
-            if disk.is_removable() {
-                continue;
+            if is_partition_of(&disk.device, path)
+                && !devices.iter().any(|device| device.device == disk.device)
+            {
+                devices.push(disk);
             }
+        }
+
+        if devices.is_empty() {
+            return DiskSelection::NoEligibleDisks;
+        }
+
+        let total_space: u64 = devices.iter().map(|disk| disk.total_space).sum();
+
+        return DiskSelection::Disks(vec![(devices.swap_remove(0), total_space)]);
+    }
 
-            let total_space = disk.total_space();
+    let mut selected = Vec::new();
 
-            let mut identifier_type = IdentifierTypeDataBuilder::new(IdentifierType::DISK);
-            identifier_type.add("t", &total_space.to_string());
-            result.push_str(&identifier_type.build());
+    for disk in disks {
+        if disk.removable && !options.include_removable_disks {
+            continue;
         }
 
-        result
+        #[cfg(feature = "android")]
+        if cfg!(target_os = "android") && android::is_emulated_storage(&disk) {
+            continue;
+        }
+
+        if !options.disk_policy.includes(&disk) {
+            continue;
+        }
+
+        if options.system_disk_only && !is_system_mount_point(&disk.mount_point) {
+            continue;
+        }
+
+        selected.push(disk);
+    }
+
+    if selected.is_empty() {
+        return DiskSelection::NoEligibleDisks;
+    }
+
+    DiskSelection::Disks(match options.disk_grouping {
+        DiskGrouping::Volume => selected
+            .into_iter()
+            .map(|disk| {
+                let total_space = disk.total_space;
+                (disk, total_space)
+            })
+            .collect(),
+        DiskGrouping::PhysicalDisk => disk::group_by_physical_disk(provider, selected),
+    })
+}
+
+/// Returns true if the device is the disk itself or one of its partitions.
+/// (/dev/sda -> /dev/sda1, /dev/nvme0n1 -> /dev/nvme0n1p2, /dev/ada0 -> /dev/ada0s1a)
+fn is_partition_of(device: &str, disk: &str) -> bool {
+    let suffix = match device.strip_prefix(disk) {
+        Some(suffix) => suffix,
+        None => return false,
+    };
+
+    if suffix.is_empty() {
+        return true;
+    }
+
+    let is_number = |number: &str| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit());
+
+    // Partitions of disks whose name ends with a digit are separated by a p, or by an s for the
+    // MBR slices of FreeBSD, which hold BSD partitions a to h.
+    if !disk.ends_with(|c: char| c.is_ascii_digit()) {
+        return is_number(suffix);
+    }
+
+    match suffix.split_at(1) {
+        ("p", number) => is_number(number),
+        ("s", slice) => is_number(
+            slice
+                .strip_suffix(|c| ('a'..='h').contains(&c))
+                .unwrap_or(slice),
+        ),
+        _ => false,
     }
 }
 
+/// Returns true if the mount point holds the operating system. (/ or the %SystemDrive% root)
+fn is_system_mount_point(mount_point: &str) -> bool {
+    let system_drive = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
+
+    mount_point == "/" || mount_point.eq_ignore_ascii_case(&format!("{}\\", system_drive))
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Identifier {
     /// The name of the Identifier.
     pub name: Option<String>,
     /// The data of the Identifier.
     pub data: Vec<IdentifierTypeDataList>,
+    /// The options used to collect the data of the Identifier.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub options: IdentifierOptions,
+    /// The human-readable description of the Identifier, shown in `to_pretty_string` and
+    /// `build_html_report` but not part of the built or hashed Identifier.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub description: Option<String>,
 }
 
 impl Identifier {
@@ -312,82 +1193,582 @@ impl Identifier {
         Identifier {
             name: Some(name.into()),
             data: Vec::new(),
+            options: IdentifierOptions::default(),
+            description: None,
         }
     }
 
+    /// Consumes the Identifier and returns its name and data. The IdentifierOptions are dropped.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.name("test");
+    /// builder.add(IdentifierType::CPU);
+    ///
+    /// let (name, data) = builder.build().into_parts();
+    ///
+    /// assert_eq!(name, Some("test".to_string()));
+    /// assert_eq!(data[0].identifier, IdentifierType::CPU);
+    /// ```
+    pub fn into_parts(self) -> (Option<String>, Vec<IdentifierTypeDataList>) {
+        (self.name, self.data)
+    }
+
     /// Builds the Identifier object and returns it as a String.
+    ///
+    /// The hash is computed over the canonical string, which prefixes the `NAME[...]` output with
+    /// the format version (`uid:v3:`, or `uid:v2:` with `StringNormalization::Legacy`). Hashes built before the prefix was introduced can be rebuilt
+    /// with `to_string_v1`.
     /// # Arguments
     /// * `hash` - If true, the Identifier will be hashed with SHA3-512.
     pub fn to_string(&self, hash: bool) -> String {
-        let mut result = String::new();
+        self.to_string_with(&SysinfoProvider::new(), hash)
+    }
 
-        if let Some(name) = &self.name {
-            result.push_str(name);
-        }
-        result.push('[');
-        for i in &self.data {
-            result.push_str(&i.build());
-            result.push_str(", ");
+    /// Builds the Identifier object from the data of the given provider and returns it as a String.
+    /// # Arguments
+    /// * `provider` - The HardwareProvider to collect the data from, shared by all components.
+    /// * `hash` - If true, the Identifier will be hashed with SHA3-512.
+    pub fn to_string_with(&self, provider: &dyn HardwareProvider, hash: bool) -> String {
+        if hash {
+            let canonical = self.to_canonical_string_with(provider);
+            return hash::to_hex(&HashAlgorithm::SHA3_512.digest(canonical.as_bytes()));
         }
-        result.pop();
-        result.pop();
-        result.push(']');
 
-        if hash {
-            let mut hasher = Sha3_512::default();
-            let mut result_bytes = result.as_bytes();
+        self.to_payload_string_with(provider, true)
+    }
+
+    /// Builds the Identifier object in the unversioned format of version 1 and returns it as a
+    /// String. Only meant to verify hashes stored before the format version was introduced.
+    /// # Arguments
+    /// * `hash` - If true, the Identifier will be hashed with SHA3-512.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// assert_eq!(identifier.to_string_v1(false), identifier.to_string(false));
+    /// assert_ne!(identifier.to_string_v1(true), identifier.to_string(true));
+    /// ```
+    pub fn to_string_v1(&self, hash: bool) -> String {
+        self.to_string_v1_with(&SysinfoProvider::new(), hash)
+    }
 
-            Digest::update(&mut hasher, &mut result_bytes);
-            let result_hash = format!("{:x}", hasher.finalize());
+    /// Builds the Identifier object from the data of the given provider in the unversioned format
+    /// of version 1 and returns it as a String.
+    /// # Arguments
+    /// * `provider` - The HardwareProvider to collect the data from, shared by all components.
+    /// * `hash` - If true, the Identifier will be hashed with SHA3-512.
+    pub fn to_string_v1_with(&self, provider: &dyn HardwareProvider, hash: bool) -> String {
+        // Version 1 predates the normalization of the collected values.
+        let mut legacy = self.clone();
+        legacy.options.string_normalization = StringNormalization::Legacy;
+        let result = legacy.to_payload_string_with(provider, false);
 
-            return result_hash;
+        if hash {
+            return hash::to_hex(&HashAlgorithm::SHA3_512.digest(result.as_bytes()));
         }
 
         result
     }
-}
 
-/// IdentifierBuilder is a helper struct for building Identifier objects.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
-pub struct IdentifierBuilder {
-    pub name: Option<String>,
-    pub data: Vec<IdentifierTypeDataList>,
-}
+    /// Returns the string hashed by the Identifier: the `NAME[...]` output prefixed with the
+    /// format version.
+    pub(crate) fn to_canonical_string_with(&self, provider: &dyn HardwareProvider) -> String {
+        format!(
+            "{}{}:{}",
+            FORMAT_PREFIX,
+            self.options.string_normalization.format_version(),
+            self.to_payload_string_with(provider, true)
+        )
+    }
 
-impl IdentifierBuilder {
-    /// Creates a new IdentifierBuilder object.
-    /// # Examples
-    /// ```
-    /// use uniqueid::IdentifierBuilder;
-    /// let builder = IdentifierBuilder::default();
-    /// ```
-    pub fn new<T: Into<String>>(name: Option<T>, data: Vec<IdentifierTypeDataList>) -> Self {
-        if let Some(name) = name {
-            IdentifierBuilder {
-                name: Some(name.into()),
-                data,
-            }
-        } else {
-            IdentifierBuilder { name: None, data }
-        }
+    /// Returns the unversioned `NAME[TYPE(key=value, ...), ...]` output, escaped or as-is for the
+    /// format of version 1.
+    fn to_payload_string_with(&self, provider: &dyn HardwareProvider, escape: bool) -> String {
+        // Components without any block are left out, so they can not leave a stray separator.
+        let components: Vec<String> = self
+            .data
+            .iter()
+            .map(|i| i.render_with(provider, &self.options, escape))
+            .filter(|component| !component.is_empty())
+            .collect();
+        let name = self.name.as_deref().unwrap_or_default();
+
+        format!(
+            "{}[{}]",
+            match escape {
+                true => escape_name(name),
+                false => name.to_string(),
+            },
+            components.join(", ")
+        )
     }
 
-    /// Sets the name of the Identifier.
+    /// Hashes the Identifier with the given algorithm and returns the raw digest.
     /// # Examples
     /// ```
-    /// use uniqueid::IdentifierBuilder;
+    /// use uniqueid::{HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
     /// let mut builder = IdentifierBuilder::default();
-    /// builder.name("test");
+    /// builder.add(IdentifierType::RAM);
     ///
-    /// assert_eq!(builder.name, Some("test".to_string()));
+    /// assert_eq!(builder.build().hash(HashAlgorithm::SHA3_512).len(), 64);
     /// ```
-    /// # Panics
-    /// Panics if the name is not valid.
+    pub fn hash(&self, algorithm: HashAlgorithm) -> Vec<u8> {
+        self.hash_with(&SysinfoProvider::new(), algorithm)
+    }
+
+    /// Hashes the Identifier built from the data of the given provider and returns the raw digest.
+    pub fn hash_with(&self, provider: &dyn HardwareProvider, algorithm: HashAlgorithm) -> Vec<u8> {
+        match algorithm {
+            HashAlgorithm::TupleHash256 => hash::tuple_hash_256(&self.tuple_elements(provider)),
+            _ => algorithm.digest(self.to_canonical_string_with(provider).as_bytes()),
+        }
+    }
+
+    /// Returns the elements absorbed by TupleHash256: the name, then for every block its
+    /// type tag, its number of pairs and every key and value.
+    fn tuple_elements(&self, provider: &dyn HardwareProvider) -> Vec<String> {
+        let mut elements = vec![self.name.clone().unwrap_or_default()];
+
+        for i in &self.data {
+            for block in i.collect_with(provider, &self.options) {
+                let data: Vec<IdentifierTypeData> = block
+                    .data
+                    .into_iter()
+                    .filter(|item| !is_metadata(&item.key))
+                    .collect();
+
+                elements.push(block.identifier.as_str().to_string());
+                elements.push(data.len().to_string());
+
+                for item in data {
+                    elements.push(item.key);
+                    elements.push(item.value);
+                }
+            }
+        }
+
+        elements
+    }
+
+    /// Returns the raw data of every component of the Identifier as (type, key-value map) tuples.
+    /// Components that produce several blocks (e.g. one per disk) yield one tuple per block.
+    /// The label of a component is included under `LABEL_KEY`.
+    /// # Examples
+    /// ```
+    /// use uniqueid::IdentifierBuilder;
+    /// use uniqueid::IdentifierType;
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    ///
+    /// let components = builder.build().get_raw_components();
+    ///
+    /// assert_eq!(components[0].0, IdentifierType::RAM);
+    /// assert!(components[0].1.contains_key("t"));
+    /// ```
+    pub fn get_raw_components(&self) -> Vec<RawComponent> {
+        self.get_raw_components_with(&SysinfoProvider::new())
+    }
+
+    /// Returns the raw data of every component of the Identifier, collected from the given provider.
+    pub fn get_raw_components_with(&self, provider: &dyn HardwareProvider) -> Vec<RawComponent> {
+        let mut result = Vec::new();
+
+        for i in &self.data {
+            for block in i.collect_with(provider, &self.options) {
+                let map = block
+                    .data
+                    .into_iter()
+                    .map(|item| (item.key, item.value))
+                    .collect();
+                result.push((block.identifier, map));
+            }
+        }
+
+        result
+    }
+
+    /// Builds the Identifier as a human-readable string, one component per line, with the label of
+    /// labelled components. Not meant to be parsed or hashed.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{FakeData, FakeProvider, IdentifierBuilder, IdentifierType};
+    ///
+    /// let provider = FakeProvider::new(FakeData {
+    ///     total_memory: 1024,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.name("test");
+    /// builder.add_with_label(IdentifierType::RAM, "main");
+    ///
+    /// assert_eq!(builder.build().to_pretty_string_with(&provider), "test\n  RAM [main]: t=1024\n");
+    /// ```
+    pub fn to_pretty_string(&self) -> String {
+        self.to_pretty_string_with(&SysinfoProvider::new())
+    }
+
+    /// Builds the Identifier as a human-readable string from the data of the given provider.
+    pub fn to_pretty_string_with(&self, provider: &dyn HardwareProvider) -> String {
+        let mut result = format!("{}\n", self.name.as_deref().unwrap_or_default());
+
+        if let Some(description) = &self.description {
+            result.push_str(&format!("  {}\n", description));
+        }
+
+        for i in &self.data {
+            for block in i.collect_with(provider, &self.options) {
+                let (metadata, data): (Vec<_>, Vec<_>) =
+                    block.data.iter().partition(|item| is_metadata(&item.key));
+
+                result.push_str("  ");
+                result.push_str(block.identifier.as_str());
+
+                if let Some(label) = metadata.iter().find(|item| item.key == LABEL_KEY) {
+                    result.push_str(&format!(" [{}]", label.value));
+                }
+
+                let pairs: Vec<String> = data.iter().map(|item| item.to_string()).collect();
+                result.push_str(&format!(": {}\n", pairs.join(", ")));
+            }
+        }
+
+        result
+    }
+
+    /// Returns the differences between the components of this Identifier and another Identifier.
+    /// # Examples
+    /// ```
+    /// use uniqueid::IdentifierBuilder;
+    /// use uniqueid::IdentifierType;
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    ///
+    /// let identifier = builder.build();
+    ///
+    /// assert!(identifier.diff(&identifier).is_empty());
+    /// ```
+    pub fn diff(&self, other: &Identifier) -> IdentifierDiff {
+        let provider = SysinfoProvider::new();
+
+        IdentifierDiff::new(
+            &self.get_raw_components_with(&provider),
+            &other.get_raw_components_with(&provider),
+        )
+    }
+}
+
+/// A struct representing the differences between two sets of raw Identifier components.
+/// Components of the same type and label are paired in the order they were collected.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdentifierDiff {
+    /// The components only present in the new set.
+    pub added: Vec<RawComponent>,
+    /// The components only present in the old set.
+    pub removed: Vec<RawComponent>,
+    /// The components present in both sets with different data. (type, old data, new data)
+    pub changed: Vec<(IdentifierType, ComponentData, ComponentData)>,
+}
+
+impl IdentifierDiff {
+    /// Creates a new IdentifierDiff object from an old and a new set of raw components.
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use uniqueid::{IdentifierDiff, IdentifierType};
+    ///
+    /// let old = vec![(IdentifierType::RAM, HashMap::from([("t".to_string(), "1".to_string())]))];
+    /// let new = vec![(IdentifierType::RAM, HashMap::from([("t".to_string(), "2".to_string())]))];
+    ///
+    /// let diff = IdentifierDiff::new(&old, &new);
+    ///
+    /// assert_eq!(diff.changed.len(), 1);
+    /// ```
+    pub fn new(old: &[RawComponent], new: &[RawComponent]) -> Self {
+        let mut diff = IdentifierDiff::default();
+        let mut matched = vec![false; old.len()];
+
+        for (identifier, data) in new {
+            let position = old
+                .iter()
+                .enumerate()
+                .position(|(i, (old_identifier, old_data))| {
+                    !matched[i]
+                        && old_identifier == identifier
+                        && old_data.get(LABEL_KEY) == data.get(LABEL_KEY)
+                });
+
+            match position {
+                Some(i) => {
+                    matched[i] = true;
+
+                    if !ComponentDiff::new(&old[i].1, data).is_empty() {
+                        diff.changed
+                            .push((identifier.clone(), old[i].1.clone(), data.clone()));
+                    }
+                }
+                None => diff.added.push((identifier.clone(), data.clone())),
+            }
+        }
+
+        for (i, component) in old.iter().enumerate() {
+            if !matched[i] {
+                diff.removed.push(component.clone());
+            }
+        }
+
+        diff
+    }
+
+    /// Returns true if there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A struct representing the differences between the key-value pairs of two components of the
+/// same type. Every list is sorted by key.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComponentDiff {
+    /// The pairs only present in the new component. (key, value)
+    pub added: Vec<(String, String)>,
+    /// The pairs only present in the old component. (key, value)
+    pub removed: Vec<(String, String)>,
+    /// The keys present in both components with different values. (key, old value, new value)
+    pub changed: Vec<(String, String, String)>,
+}
+
+impl ComponentDiff {
+    /// Creates a new ComponentDiff object from the old and new data of a component.
+    pub fn new(old: &ComponentData, new: &ComponentData) -> Self {
+        let mut diff = ComponentDiff::default();
+
+        for (key, value) in new {
+            match old.get(key) {
+                Some(old_value) if old_value != value => {
+                    diff.changed
+                        .push((key.clone(), old_value.clone(), value.clone()));
+                }
+                Some(_) => {}
+                None => diff.added.push((key.clone(), value.clone())),
+            }
+        }
+
+        for (key, value) in old {
+            if !new.contains_key(key) {
+                diff.removed.push((key.clone(), value.clone()));
+            }
+        }
+
+        diff.added.sort();
+        diff.removed.sort();
+        diff.changed.sort();
+
+        diff
+    }
+
+    /// Returns true if there are no differences.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Returns the IdentifierTypes added by `IdentifierBuilder::with_platform_defaults` on the
+/// operating system, as named by `std::env::consts::OS`.
+fn platform_default_types(os: &str) -> Vec<IdentifierType> {
+    let mut types = vec![IdentifierType::CPU, IdentifierType::DISK];
+
+    match os {
+        "linux" => types.extend([IdentifierType::MACHINE, IdentifierType::NET]),
+        "windows" => types.push(IdentifierType::SYSUUID),
+        "macos" => types.push(IdentifierType::SERIAL),
+        _ => {}
+    }
+
+    types
+}
+
+/// IdentifierBuilder is a helper struct for building Identifier objects.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IdentifierBuilder {
+    pub name: Option<String>,
+    pub data: Vec<IdentifierTypeDataList>,
+    pub options: IdentifierOptions,
+    pub description: Option<String>,
+}
+
+impl IdentifierBuilder {
+    /// Creates a new IdentifierBuilder object.
+    /// # Examples
+    /// ```
+    /// use uniqueid::IdentifierBuilder;
+    /// let builder = IdentifierBuilder::default();
+    /// ```
+    pub fn new<T: Into<String>>(name: Option<T>, data: Vec<IdentifierTypeDataList>) -> Self {
+        if let Some(name) = name {
+            IdentifierBuilder {
+                name: Some(name.into()),
+                data,
+                options: IdentifierOptions::default(),
+                description: None,
+            }
+        } else {
+            IdentifierBuilder {
+                name: None,
+                data,
+                options: IdentifierOptions::default(),
+                description: None,
+            }
+        }
+    }
+
+    /// Creates a new IdentifierBuilder with the most stable IdentifierTypes of the current platform:
+    /// CPU and DISK, plus MACHINE and NET on Linux, SYSUUID on Windows and SERIAL on macOS.
+    /// # Examples
+    /// ```
+    /// use uniqueid::IdentifierBuilder;
+    /// use uniqueid::IdentifierType;
+    ///
+    /// let builder = IdentifierBuilder::with_platform_defaults();
+    /// let types: Vec<IdentifierType> = builder.data.iter().map(|i| i.identifier.clone()).collect();
+    ///
+    /// assert_eq!(types[..2], [IdentifierType::CPU, IdentifierType::DISK]);
+    /// if cfg!(target_os = "linux") {
+    ///     assert!(types.contains(&IdentifierType::MACHINE));
+    ///     assert!(types.contains(&IdentifierType::NET));
+    /// }
+    /// ```
+    pub fn with_platform_defaults() -> IdentifierBuilder {
+        let mut builder = IdentifierBuilder::default();
+
+        for identifier in platform_default_types(std::env::consts::OS) {
+            builder.add(identifier);
+        }
+
+        builder
+    }
+
+    /// Sets the name of the Identifier.
+    /// # Examples
+    /// ```
+    /// use uniqueid::IdentifierBuilder;
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.name("test");
+    ///
+    /// assert_eq!(builder.name, Some("test".to_string()));
+    /// ```
+    /// # Panics
+    /// Panics if the name is not valid.
     pub fn name<T: Into<String>>(&mut self, name: T) -> &mut Self {
         self.name = Some(name.into());
         self
     }
 
+    /// Sets the human-readable description of the Identifier. It is shown in the human-readable
+    /// outputs, but not part of the built or hashed Identifier.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// builder.with_description("Production server fingerprint for App License v2");
+    /// let identifier = builder.build();
+    ///
+    /// assert!(identifier.to_pretty_string().contains("App License v2"));
+    /// assert!(!identifier.to_string(false).contains("App License v2"));
+    /// ```
+    pub fn with_description<T: Into<String>>(&mut self, description: T) -> &mut Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the IdentifierOptions used to collect the data of the Identifier.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierOptions};
+    ///
+    /// let mut options = IdentifierOptions::default();
+    /// options.include_cpu_frequency(false);
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.options(options);
+    ///
+    /// assert!(!builder.options.include_cpu_frequency);
+    /// ```
+    pub fn options(&mut self, options: IdentifierOptions) -> &mut Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets which core count is included in the CPU component.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{CoreCount, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::CPU);
+    /// builder.cpu_core_count(CoreCount::Physical);
+    ///
+    /// assert_eq!(builder.options.cpu_core_count, CoreCount::Physical);
+    /// ```
+    pub fn cpu_core_count(&mut self, core_count: CoreCount) -> &mut Self {
+        self.options.cpu_core_count = core_count;
+        self
+    }
+
+    /// Sets the function applied to the raw CPU brand string before it is stored.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{normalize_cpu_brand_default, IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::CPU);
+    /// builder.cpu_brand_normalization_fn(normalize_cpu_brand_default);
+    ///
+    /// assert!(builder.options.cpu_brand_normalization.is_some());
+    /// ```
+    pub fn cpu_brand_normalization_fn<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.options.cpu_brand_normalization = Some(BrandNormalizer::new(f));
+        self
+    }
+
+    /// Replaces the IdentifierTypes and IdentifierOptions of the IdentifierBuilder with a Profile.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, Profile};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.profile(Profile::Balanced);
+    ///
+    /// assert_eq!(builder.data.len(), 3);
+    /// assert_eq!(builder.options, Profile::Balanced.options());
+    /// ```
+    pub fn profile(&mut self, profile: Profile) -> &mut Self {
+        self.data = profile
+            .identifier_types()
+            .into_iter()
+            .map(IdentifierTypeDataList::new)
+            .collect();
+        self.options = profile.options();
+        self
+    }
+
     /// Adds a new IdentifierType object to the IdentifierBuilder.
     /// # Arguments
     /// * `identifier` - The IdentifierType object to add.
@@ -408,6 +1789,143 @@ impl IdentifierBuilder {
         self
     }
 
+    /// Adds a DISK component holding only the disk at the given device path.
+    ///
+    /// Unlike `IdentifierOptions::system_disk_only`, the disk is selected by its device and not by
+    /// a mount point, so a disk with several mounted partitions yields a single block holding the
+    /// combined size of its partitions. Removable disks can be selected.
+    /// # Arguments
+    /// * `path` - The device path of the disk or partition. (e.g. /dev/sda or /dev/nvme0n1p2)
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add_disk_by_device_path("/dev/sda");
+    ///
+    /// assert_eq!(builder.data[0].identifier, IdentifierType::DISK);
+    /// assert_eq!(builder.options.disk_device_path, Some("/dev/sda".to_string()));
+    /// ```
+    pub fn add_disk_by_device_path<T: Into<String>>(&mut self, path: T) -> &mut Self {
+        self.options.disk_device_path = Some(path.into());
+        self.add(IdentifierType::DISK)
+    }
+
+    /// Sets whether the serial number of the chassis is collected by the CHASSIS_SERIAL component.
+    /// The component is never collected without this explicit opt-in.
+    /// # Arguments
+    /// * `include` - Whether the chassis serial number is collected.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::CHASSIS_SERIAL);
+    /// builder.include_chassis_serial(true);
+    ///
+    /// assert!(builder.options.include_chassis_serial);
+    /// ```
+    pub fn include_chassis_serial(&mut self, include: bool) -> &mut Self {
+        self.options.include_chassis_serial = include;
+        self
+    }
+
+    /// Adds the architecture the binary is compiled for as a custom `ARCH(arch=...)` component.
+    /// (e.g. x86_64, aarch64, riscv64) It is known at compile time, so nothing is read.
+    /// # Examples
+    /// ```
+    /// use uniqueid::IdentifierBuilder;
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add_cpu_architecture();
+    ///
+    /// assert_eq!(
+    ///     builder.build().to_string(false),
+    ///     format!("[ARCH(arch={})]", std::env::consts::ARCH)
+    /// );
+    /// ```
+    pub fn add_cpu_architecture(&mut self) -> &mut Self {
+        let mut list = IdentifierTypeDataList::new(IdentifierType::Custom(ARCH_TAG.to_string()));
+        list.push("arch", std::env::consts::ARCH);
+
+        self.data.push(list);
+        self
+    }
+
+    /// Sets the granularity the total RAM of the RAM component is rounded to, see
+    /// `IdentifierOptions::ram_granularity`.
+    /// # Arguments
+    /// * `granularity` - The granularity in KB, such as `GIB`, or None for the exact value.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType, GIB};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// builder.ram_granularity(Some(GIB));
+    ///
+    /// assert_eq!(builder.options.ram_granularity, Some(GIB));
+    /// ```
+    pub fn ram_granularity(&mut self, granularity: Option<u64>) -> &mut Self {
+        self.options.ram_granularity = granularity;
+        self
+    }
+
+    /// Adds a new IdentifierType object with a label to the IdentifierBuilder.
+    ///
+    /// The label is stored as metadata under `LABEL_KEY`. It is not part of the built or hashed
+    /// Identifier, but names the component in `Identifier::to_pretty_string` and pairs components
+    /// of the same type by label in `Identifier::diff`.
+    /// # Arguments
+    /// * `identifier` - The IdentifierType object to add.
+    /// * `label` - The label of the component. (e.g. boot, data)
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType, LABEL_KEY};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add_with_label(IdentifierType::DISK, "boot");
+    ///
+    /// assert_eq!(builder.data[0].data[0].key, LABEL_KEY);
+    /// assert_eq!(builder.data[0].data[0].value, "boot");
+    /// ```
+    pub fn add_with_label(&mut self, identifier: IdentifierType, label: &str) -> &mut Self {
+        let mut list = IdentifierTypeDataList::new(identifier);
+        list.push(LABEL_KEY, label);
+
+        self.data.push(list);
+        self
+    }
+
+    /// Adds a custom component with the given data to the IdentifierBuilder.
+    /// # Arguments
+    /// * `tag` - The tag of the component, see `IdentifierType::custom`.
+    /// * `data` - The key-value pairs of the component.
+    /// # Examples
+    /// ```
+    /// use uniqueid::IdentifierBuilder;
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add_custom("DONGLE", &[("s", "1234")]).unwrap();
+    ///
+    /// assert_eq!(builder.build().to_string(false), "[DONGLE(s=1234)]");
+    /// ```
+    /// # Errors
+    /// Returns `UniqueIdError::InvalidTag` if the tag is not a valid custom tag.
+    pub fn add_custom<T: Into<String>>(
+        &mut self,
+        tag: T,
+        data: &[(&str, &str)],
+    ) -> Result<&mut Self, UniqueIdError> {
+        let mut list = IdentifierTypeDataList::new(IdentifierType::custom(tag)?);
+        for (key, value) in data {
+            list.push(*key, *value);
+        }
+
+        self.data.push(list);
+        Ok(self)
+    }
+
     /// Returns an Identifier object from the IdentifierBuilder.
     /// # Examples
     /// ```
@@ -423,6 +1941,8 @@ impl IdentifierBuilder {
         Identifier {
             name: self.name,
             data: self.data,
+            options: self.options,
+            description: self.description,
         }
     }
 }
@@ -448,4 +1968,688 @@ mod tests {
         println!("{}", identifier.to_string(false));
         println!("{}", identifier.to_string(true));
     }
+
+    #[test]
+    fn test_get_raw_components() {
+        let mut list = IdentifierTypeDataList::new(IdentifierType::CPU);
+        list.push("b", "brand");
+        list.push("c", "8");
+
+        let identifier = Identifier {
+            name: Some("test".to_string()),
+            data: vec![list],
+            ..Default::default()
+        };
+
+        let components = identifier.get_raw_components();
+
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].0, IdentifierType::CPU);
+        assert_eq!(components[0].1.get("b"), Some(&"brand".to_string()));
+        assert_eq!(components[0].1.get("c"), Some(&"8".to_string()));
+        assert_eq!(identifier.to_string(false), "test[CPU(b=brand, c=8)]");
+    }
+
+    #[test]
+    fn test_identifier_type_data_builder() {
+        assert_eq!(
+            IdentifierTypeDataBuilder::new(IdentifierType::CPU).build(),
+            "CPU()"
+        );
+
+        let mut builder = IdentifierTypeDataBuilder::new(IdentifierType::CPU);
+        builder.add("b", "intel");
+        assert_eq!(builder.build(), "CPU(b=intel)");
+
+        let mut builder = IdentifierTypeDataBuilder::new(IdentifierType::CPU);
+        builder
+            .add("b", "intel")
+            .add("v", "genuineintel")
+            .add("c", "8");
+        assert_eq!(builder.build(), "CPU(b=intel, v=genuineintel, c=8)");
+
+        let builder = IdentifierTypeDataBuilder::new(IdentifierType::Custom("SITE".to_string()));
+        assert_eq!(builder.build(), "SITE()");
+    }
+
+    #[test]
+    fn test_to_string_components() {
+        let provider = FakeProvider::new(FakeData {
+            total_memory: 1024,
+            ..Default::default()
+        });
+        let identifier = |data: Vec<IdentifierTypeDataList>| Identifier {
+            name: Some("test".to_string()),
+            data,
+            ..Default::default()
+        };
+        // A custom component without data has no collector, so it builds to an empty string.
+        let empty = IdentifierTypeDataList::new(IdentifierType::Custom("SITE".to_string()));
+        let ram = IdentifierTypeDataList::new(IdentifierType::RAM);
+
+        assert_eq!(
+            empty.build_with(&provider, &IdentifierOptions::default()),
+            ""
+        );
+
+        let none = identifier(Vec::new());
+        assert_eq!(none.to_string_with(&provider, false), "test[]");
+        assert_eq!(
+            none.to_string_with(&provider, true),
+            hash::to_hex(&HashAlgorithm::SHA3_512.digest(b"uid:v3:test[]"))
+        );
+
+        assert_eq!(
+            identifier(vec![ram.clone()]).to_string_with(&provider, false),
+            "test[RAM(t=1024)]"
+        );
+        assert_eq!(
+            identifier(vec![empty.clone()]).to_string_with(&provider, false),
+            "test[]"
+        );
+        assert_eq!(
+            identifier(vec![ram.clone(), empty.clone(), ram.clone()])
+                .to_string_with(&provider, false),
+            "test[RAM(t=1024), RAM(t=1024)]"
+        );
+        assert_eq!(
+            identifier(vec![empty, ram]).to_string_with(&provider, false),
+            "test[RAM(t=1024)]"
+        );
+    }
+
+    #[test]
+    fn test_format_version() {
+        let mut cpu = IdentifierTypeDataList::new(IdentifierType::CPU);
+        cpu.push("b", "intel(r) core(tm) i7");
+        cpu.push("c", "8");
+
+        let mut ram = IdentifierTypeDataList::new(IdentifierType::RAM);
+        ram.push("t", "1024");
+
+        let identifier = Identifier {
+            name: Some("test".to_string()),
+            data: vec![cpu, ram],
+            ..Default::default()
+        };
+        let provider = FakeProvider::default();
+
+        // Pinned: changing these breaks the verification of stored hashes.
+        let v1 = "test[CPU(b=intel(r) core(tm) i7, c=8), RAM(t=1024)]";
+        assert_eq!(identifier.to_string_v1_with(&provider, false), v1);
+        assert_eq!(
+            identifier.to_string_v1_with(&provider, true),
+            "3d50c960701df6b8446d3b8fa821ebb7e532bca3fb190fd3180678ba248a6fc7\
+             c796075b05f6ce09705af62283298c75eeb63c27d925e95e57bc30fc5952e55b"
+        );
+
+        assert_eq!(FORMAT_VERSION, 3);
+        assert_eq!(identifier.to_string_with(&provider, false), v1);
+        assert_eq!(
+            identifier.to_canonical_string_with(&provider),
+            format!("uid:v3:{}", v1)
+        );
+        assert_eq!(
+            identifier.to_string_with(&provider, true),
+            "5aea4b7db1c66ba8cba9dc8b054a364333d635dbacbd9c20a92d2059587b1ca0\
+             6b407b21847a56adc688e1501eec665746b2453e3636b8548f4cf529b8cc0433"
+        );
+
+        let mut legacy = identifier.clone();
+        legacy.options.string_normalization = StringNormalization::Legacy;
+        assert_eq!(
+            legacy.to_canonical_string_with(&provider),
+            format!("uid:v2:{}", v1)
+        );
+        assert_eq!(
+            legacy.to_string_with(&provider, true),
+            "1468bed048a2668512441bf012a922a8b587a7f3068e8673d8edf00bd582b346\
+             43741f44c69527c62641a5a8c08fb65e69ef3f6eb981d6142f9a3ec7d4cc2c2d"
+        );
+        assert_eq!(
+            hash::to_hex(&legacy.hash_with(&provider, HashAlgorithm::SHA3_512)),
+            legacy.to_string_with(&provider, true)
+        );
+        assert_eq!(
+            hash::to_hex(&identifier.hash_with(&provider, HashAlgorithm::SHA3_512)),
+            identifier.to_string_with(&provider, true)
+        );
+    }
+
+    #[test]
+    fn test_format_version_v1_collected() {
+        let provider = FakeProvider::new(FakeData {
+            processors: vec![ProcessorInfo {
+                brand: "Intel(R) Core(TM) i7-8750H CPU @ 2.20GHz".to_string(),
+                vendor: "GenuineIntel".to_string(),
+                frequency: 2208,
+            }],
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::CPU);
+        let identifier = builder.build();
+
+        // Version 1 keeps the values as collected, whatever the normalization of the options.
+        let v1 = "[CPU(b=intel(r) core(tm) i7-8750h cpu @ 2.20ghz, v=genuineintel, f=2208, c=1)]";
+        assert_eq!(identifier.to_string_v1_with(&provider, false), v1);
+        assert_eq!(
+            identifier.to_string_v1_with(&provider, true),
+            hash::to_hex(&HashAlgorithm::SHA3_512.digest(v1.as_bytes()))
+        );
+        assert_eq!(
+            identifier.to_string_with(&provider, false),
+            "[CPU(b=intel core i7-8750h cpu @ 2.20ghz, v=genuineintel, f=2208, c=1)]"
+        );
+    }
+
+    #[test]
+    fn test_tuple_hash_256_known_answer() {
+        let mut cpu = IdentifierTypeDataList::new(IdentifierType::CPU);
+        cpu.push("b", "intel(r) core(tm) i7");
+        cpu.push("c", "8");
+
+        let mut ram = IdentifierTypeDataList::new(IdentifierType::RAM);
+        ram.push("t", "1024");
+
+        let identifier = Identifier {
+            name: Some("test".to_string()),
+            data: vec![cpu, ram],
+            ..Default::default()
+        };
+
+        // Computed with an independent SP 800-185 implementation over the tuple
+        // (test, CPU, 2, b, intel(r) core(tm) i7, c, 8, RAM, 1, t, 1024) with S = "uniqueid".
+        assert_eq!(
+            hash::to_hex(&identifier.hash(HashAlgorithm::TupleHash256)),
+            "ebb2d4a74d06d3c3ee382528de205c044787cdd30b57977d7a6d1a77fca4a13e\
+             576917f65aa26b45039e5e5b9978b2076919cd0fb9d6a4932a3723cda088b5ec"
+        );
+    }
+
+    #[test]
+    fn test_cpu_brand_normalization_fn() {
+        let provider = FakeProvider::new(FakeData {
+            processors: vec![ProcessorInfo {
+                brand: "Intel(R) Core(TM) i7-8750H CPU @ 2.20GHz".to_string(),
+                vendor: "GenuineIntel".to_string(),
+                frequency: 2208,
+            }],
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::CPU);
+        builder.cpu_brand_normalization_fn(normalize_cpu_brand_default);
+
+        assert_eq!(
+            builder.build().to_string_with(&provider, false),
+            "[CPU(b=intel core i7-8750h cpu @ 2.20ghz, v=genuineintel, f=2208, c=1)]"
+        );
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::CPU);
+        builder.cpu_brand_normalization_fn(|brand| brand.replace(" CPU @ 2.20GHz", ""));
+
+        assert_eq!(
+            builder.build().to_string_with(&provider, false),
+            "[CPU(b=Intel(R) Core(TM) i7-8750H, v=genuineintel, f=2208, c=1)]"
+        );
+    }
+
+    #[test]
+    fn test_custom_identifier_type() {
+        assert_eq!(
+            IdentifierType::custom("CPU"),
+            Err(UniqueIdError::InvalidTag("CPU".to_string()))
+        );
+        assert!(IdentifierType::custom("").is_err());
+        assert!(IdentifierType::custom("1DONGLE").is_err());
+        assert!(IdentifierType::custom("DONGLE(").is_err());
+        assert_eq!(
+            "SITE_2".parse::<IdentifierType>(),
+            Ok(IdentifierType::Custom("SITE_2".to_string()))
+        );
+        assert_eq!("RAM".parse::<IdentifierType>(), Ok(IdentifierType::RAM));
+
+        let mut ram = IdentifierTypeDataList::new(IdentifierType::RAM);
+        ram.push("t", "1024");
+
+        let mut builder = IdentifierBuilder::default();
+        builder.name("test");
+        builder.data.push(ram);
+        builder.add_custom("SITE", &[("n", "berlin")]).unwrap();
+        assert!(builder.add_custom("DISK", &[("t", "1")]).is_err());
+
+        let identifier = builder.build();
+        let string = identifier.to_string(false);
+
+        assert_eq!(string, "test[RAM(t=1024), SITE(n=berlin)]");
+        assert_eq!(
+            identifier.to_string(true),
+            hash::to_hex(&HashAlgorithm::SHA3_512.digest(format!("uid:v3:{}", string).as_bytes()))
+        );
+        assert_eq!(string.parse::<Identifier>().unwrap(), identifier);
+    }
+
+    #[test]
+    fn test_app_component() {
+        let path = "/opt/app/bin/app";
+        let elf = include_bytes!("../tests/fixtures/binaries/build_id_x86_64.elf");
+
+        let provider = FakeProvider::new(FakeData {
+            current_exe: Some(path.to_string()),
+            files: [(path.to_string(), elf.to_vec())].into(),
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::APP);
+        builder.options.app_install_id = Some("4f2a0e1c".to_string());
+        let identifier = builder.build();
+
+        let components = identifier.get_raw_components_with(&provider);
+        let build_id: Vec<u8> = (0x10..0x24).collect();
+
+        assert_eq!(components[0].1["p"], hash::hash_value(path));
+        assert_eq!(components[0].1["b"], hash::hash_value(build_id));
+        assert_eq!(components[0].1["i"], hash::hash_value("4f2a0e1c"));
+        assert!(!identifier
+            .to_string_with(&provider, false)
+            .contains("/opt/app"));
+
+        // Without a readable build-id, only the path hash remains.
+        provider.update(|data| data.files.clear());
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::APP);
+
+        assert_eq!(
+            builder.build().to_string_with(&provider, false),
+            format!("[APP(p={})]", hash::hash_value(path))
+        );
+    }
+
+    #[test]
+    fn test_os_component() {
+        let provider = FakeProvider::new(FakeData {
+            os: OsInfo {
+                name: Some("Ubuntu".to_string()),
+                version: Some("22.04".to_string()),
+                kernel_version: None,
+                architecture: Some("x86_64".to_string()),
+                host_name: None,
+            },
+            ..Default::default()
+        });
+
+        let block = IdentifierTypeDataList::new(IdentifierType::OS)
+            .build_with(&provider, &IdentifierOptions::default());
+
+        assert_eq!(block, "OS(n=Ubuntu, v=22.04, k=unknown, a=x86_64)");
+
+        let keys = |block: &str| {
+            let list: IdentifierTypeDataList = block.parse().unwrap();
+            list.data
+                .into_iter()
+                .map(|item| item.key)
+                .collect::<Vec<_>>()
+        };
+
+        let system = IdentifierTypeDataList::new(IdentifierType::OS)
+            .build_with(&SysinfoProvider::new(), &IdentifierOptions::default());
+
+        assert_eq!(keys(&block), vec!["n", "v", "k", "a"]);
+        assert_eq!(keys(&system), keys(&block));
+    }
+
+    #[test]
+    fn test_disk_by_device_path() {
+        let disk = |device: &str, mount_point: &str, total_space: u64| DiskInfo {
+            device: device.to_string(),
+            mount_point: mount_point.to_string(),
+            total_space,
+            removable: false,
+            ..Default::default()
+        };
+
+        let provider = FakeProvider::new(FakeData {
+            disks: vec![
+                disk("/dev/sda1", "/", 100),
+                disk("/dev/sda2", "/home", 200),
+                disk("/dev/sda2", "/var/lib/docker", 200),
+                disk("/dev/sdb1", "/mnt/backup", 400),
+                disk("/dev/sda10", "/srv", 800),
+                disk("/dev/nvme0n1p1", "/boot", 1600),
+            ],
+            ..Default::default()
+        });
+
+        let build = |path: &str| {
+            let mut builder = IdentifierBuilder::default();
+            builder.add_disk_by_device_path(path);
+            builder.build().to_string_with(&provider, false)
+        };
+
+        assert_eq!(build("/dev/sda"), "[DISK(t=1100)]");
+        assert_eq!(build("/dev/sda2"), "[DISK(t=200)]");
+        assert_eq!(build("/dev/nvme0n1"), "[DISK(t=1600)]");
+        assert_eq!(build("/dev/sd"), "[DISK(none=1)]");
+
+        assert!(is_partition_of("/dev/mmcblk0p1", "/dev/mmcblk0"));
+        assert!(!is_partition_of("/dev/sdap1", "/dev/sda"));
+        assert!(!is_partition_of("/dev/nvme0n10", "/dev/nvme0n1"));
+        assert!(is_partition_of("/dev/ada0p2", "/dev/ada0"));
+        assert!(is_partition_of("/dev/ada0s1a", "/dev/ada0"));
+        assert!(is_partition_of("/dev/da0s2", "/dev/da0"));
+        assert!(!is_partition_of("/dev/ada0s1z", "/dev/ada0"));
+        assert!(!is_partition_of("/dev/ada01", "/dev/ada0"));
+    }
+
+    #[test]
+    fn test_cpu_core_counts() {
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::CPU);
+        builder.cpu_core_count(CoreCount::Both);
+
+        let identifier = builder.build();
+        let blocks = identifier.data[0].collect_with(&SysinfoProvider::new(), &identifier.options);
+        let value = |key: &str| {
+            blocks[0]
+                .data
+                .iter()
+                .find(|item| item.key == key)
+                .map(|item| item.value.clone())
+                .unwrap()
+        };
+
+        let logical: usize = value("lc").parse().unwrap();
+        // The physical core count is not known on every platform.
+        if let Ok(physical) = value("pc").parse::<usize>() {
+            assert!(physical <= logical);
+        }
+
+        let provider = FakeProvider::new(FakeData {
+            processors: vec![ProcessorInfo::default(); 4],
+            ..Default::default()
+        });
+
+        assert!(identifier
+            .to_string_with(&provider, false)
+            .ends_with("pc=unknown, lc=4)]"));
+    }
+
+    #[test]
+    fn test_cpu_hybrid() {
+        let processor = |frequency: u64| ProcessorInfo {
+            brand: "12th Gen Intel(R) Core(TM) i5-12600K".to_string(),
+            vendor: "GenuineIntel".to_string(),
+            frequency,
+        };
+        let performance = std::iter::repeat_n(processor(4900), 12);
+        let efficiency = std::iter::repeat_n(processor(3600), 4);
+
+        let data = IdentifierTypeDataList::new(IdentifierType::CPU);
+        let options = IdentifierOptions::default();
+
+        // The scheduler enumerates either kind of core first, depending on the boot.
+        let outputs: Vec<String> = [
+            performance.clone().chain(efficiency.clone()).collect(),
+            efficiency.clone().chain(performance.clone()).collect(),
+        ]
+        .into_iter()
+        .map(|processors| {
+            data.build_with(
+                &FakeProvider::new(FakeData {
+                    processors,
+                    ..Default::default()
+                }),
+                &options,
+            )
+        })
+        .collect();
+
+        assert_eq!(
+            outputs[0],
+            "CPU(b=12th gen intel core i5-12600k, v=genuineintel, f=4900, c=16)"
+        );
+        assert_eq!(outputs[0], outputs[1]);
+
+        if cfg!(target_os = "linux") {
+            let hybrid = FakeProvider::new(FakeData {
+                processors: efficiency.chain(performance).collect(),
+                files: [
+                    ("/sys/devices/cpu_core/cpus".to_string(), b"0-11".to_vec()),
+                    ("/sys/devices/cpu_atom/cpus".to_string(), b"12-15".to_vec()),
+                ]
+                .into(),
+                ..Default::default()
+            });
+
+            assert_eq!(
+                data.build_with(&hybrid, &options),
+                "CPU(b=12th gen intel core i5-12600k, v=genuineintel, f=4900, c=16, cl=12+4)"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cpu_sockets() {
+        let processor = |brand: &str| ProcessorInfo {
+            brand: brand.to_string(),
+            vendor: "GenuineIntel".to_string(),
+            frequency: 2100,
+        };
+        let package = |cpu: usize, package: usize| {
+            (
+                format!(
+                    "/sys/devices/system/cpu/cpu{}/topology/physical_package_id",
+                    cpu
+                ),
+                package.to_string().into_bytes(),
+            )
+        };
+
+        let data = IdentifierTypeDataList::new(IdentifierType::CPU);
+        let options = IdentifierOptions::default();
+
+        assert_eq!(
+            data.try_collect_with(&FakeProvider::default(), &options),
+            Err(UniqueIdError::SourceUnavailable(IdentifierType::CPU))
+        );
+
+        let single = FakeProvider::new(FakeData {
+            processors: vec![processor("Intel(R) Xeon(R) Gold 6230")],
+            files: [package(0, 0)].into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            data.build_with(&single, &options),
+            "CPU(b=intel xeon gold 6230, v=genuineintel, f=2100, c=1)"
+        );
+
+        if cfg!(target_os = "linux") {
+            let dual = FakeProvider::new(FakeData {
+                processors: vec![
+                    processor("Intel(R) Xeon(R) Gold 6230"),
+                    processor("Intel(R) Xeon(R) Gold 6230R"),
+                ],
+                files: [package(0, 0), package(1, 1)].into(),
+                ..Default::default()
+            });
+
+            assert_eq!(
+                data.build_with(&dual, &options),
+                "CPU(b=intel xeon gold 6230, v=genuineintel, \
+                 b1=intel xeon gold 6230r, v1=genuineintel, f=2100, c=2, s=2)"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cpu_soc() {
+        let provider = FakeProvider::new(FakeData {
+            processors: vec![ProcessorInfo {
+                brand: "Cortex-A72".to_string(),
+                vendor: "ARM".to_string(),
+                frequency: 0,
+            }],
+            files: [(
+                "/proc/cpuinfo".to_string(),
+                include_bytes!("../tests/fixtures/proc/cpuinfo_raspberry_pi4.txt").to_vec(),
+            )]
+            .into(),
+            ..Default::default()
+        });
+
+        let expected = match cfg!(any(target_arch = "aarch64", target_arch = "arm")) {
+            true => {
+                "CPU(b=cortex-a72, v=arm, f=0, c=1, sn=10000000a3b4c5d6, \
+                     sm=raspberry pi 4 model b rev 1.4)"
+            }
+            false => "CPU(b=cortex-a72, v=arm, f=0, c=1)",
+        };
+
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::CPU)
+                .build_with(&provider, &IdentifierOptions::default()),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_add_with_label() {
+        let provider = FakeProvider::new(FakeData {
+            total_memory: 1024,
+            ..Default::default()
+        });
+
+        let mut labelled = IdentifierBuilder::default();
+        labelled.add_with_label(IdentifierType::RAM, "main");
+        let labelled = labelled.build();
+
+        let mut plain = IdentifierBuilder::default();
+        plain.add(IdentifierType::RAM);
+        let plain = plain.build();
+
+        assert_eq!(
+            labelled.to_string_with(&provider, false),
+            plain.to_string_with(&provider, false)
+        );
+        assert_eq!(
+            labelled.hash_with(&provider, HashAlgorithm::TupleHash256),
+            plain.hash_with(&provider, HashAlgorithm::TupleHash256)
+        );
+        assert_eq!(
+            labelled.to_pretty_string_with(&provider),
+            "\n  RAM [main]: t=1024\n"
+        );
+
+        let components = labelled.get_raw_components_with(&provider);
+        assert_eq!(components[0].1[LABEL_KEY], "main");
+
+        let component = |label: &str, t: &str| {
+            (
+                IdentifierType::DISK,
+                HashMap::from([
+                    (LABEL_KEY.to_string(), label.to_string()),
+                    ("t".to_string(), t.to_string()),
+                ]),
+            )
+        };
+
+        let diff = IdentifierDiff::new(
+            &[component("boot", "1"), component("data", "2")],
+            &[component("data", "3"), component("boot", "1")],
+        );
+
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(
+            diff.changed,
+            vec![(
+                IdentifierType::DISK,
+                component("data", "2").1,
+                component("data", "3").1
+            )]
+        );
+    }
+
+    #[test]
+    fn test_component_diff() {
+        let mut old = IdentifierTypeDataList::new(IdentifierType::DISK);
+        old.push("t", "512").push("s", "ssd").push("fs", "ext4");
+
+        let mut new = IdentifierTypeDataList::new(IdentifierType::DISK);
+        new.push("t", "1024").push("fs", "ext4").push("mp", "/");
+
+        let pair = |key: &str, value: &str| (key.to_string(), value.to_string());
+
+        assert_eq!(
+            old.diff(&new),
+            ComponentDiff {
+                added: vec![pair("mp", "/")],
+                removed: vec![pair("s", "ssd")],
+                changed: vec![("t".to_string(), "512".to_string(), "1024".to_string())],
+            }
+        );
+        assert_eq!(new.diff(&old).added, vec![pair("s", "ssd")]);
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn test_host_component() {
+        let provider = FakeProvider::new(FakeData {
+            os: OsInfo {
+                host_name: Some(" Build-Server-01\n".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::HOST);
+        let identifier = builder.build();
+
+        let built = identifier.to_string_with(&provider, false);
+        assert_eq!(built, "[HOST(n=build-server-01)]");
+        assert_eq!(built.parse::<Identifier>().unwrap().data, {
+            let mut list = IdentifierTypeDataList::new(IdentifierType::HOST);
+            list.push("n", "build-server-01");
+            vec![list]
+        });
+        assert_eq!(
+            identifier.to_string_with(&FakeProvider::default(), false),
+            "[HOST(n=unknown)]"
+        );
+        assert_eq!("HOST".parse::<IdentifierType>(), Ok(IdentifierType::HOST));
+
+        for profile in [Profile::Strict, Profile::Balanced, Profile::Relaxed] {
+            assert!(!profile.identifier_types().contains(&IdentifierType::HOST));
+        }
+        assert!(IdentifierBuilder::with_platform_defaults()
+            .data
+            .iter()
+            .all(|list| list.identifier != IdentifierType::HOST));
+    }
+
+    #[test]
+    fn test_platform_defaults() {
+        use IdentifierType::*;
+
+        assert_eq!(
+            platform_default_types("linux"),
+            vec![CPU, DISK, MACHINE, NET]
+        );
+        assert_eq!(platform_default_types("windows"), vec![CPU, DISK, SYSUUID]);
+        assert_eq!(platform_default_types("macos"), vec![CPU, DISK, SERIAL]);
+        assert_eq!(platform_default_types("freebsd"), vec![CPU, DISK]);
+
+        let types: Vec<IdentifierType> = IdentifierBuilder::with_platform_defaults()
+            .data
+            .into_iter()
+            .map(|list| list.identifier)
+            .collect();
+        assert_eq!(types, platform_default_types(std::env::consts::OS));
+    }
 }
@@ -0,0 +1,176 @@
+use crate::dmi::is_bogus_system_uuid;
+use crate::registry;
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList, UniqueIdError};
+
+/// The files holding the machine id on Linux, in order of preference.
+const LINUX_MACHINE_ID_FILES: [&str; 2] = ["/etc/machine-id", "/var/lib/dbus/machine-id"];
+
+/// Normalizes a machine id to lowercase, returning None unless it is made of 32 hex digits,
+/// optionally hyphenated like a UUID.
+pub(crate) fn normalize_machine_id(id: &str) -> Option<String> {
+    let id = id.trim().to_lowercase();
+
+    let digits = id.chars().filter(|c| *c != '-').count();
+    let valid = digits == 32 && id.chars().all(|c| c.is_ascii_hexdigit() || c == '-');
+
+    valid.then_some(id)
+}
+
+/// Parses a string property of `ioreg -rd1 -c IOPlatformExpertDevice`. ("key" = "value")
+pub(crate) fn parse_ioreg_property(output: &str, name: &str) -> Option<String> {
+    let key = format!("\"{}\"", name);
+
+    output.lines().find_map(|line| {
+        let (_, value) = line.trim().strip_prefix(&key)?.split_once('=')?;
+
+        Some(value.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Reads the host UUID of FreeBSD, which is set per jail, falling back to the SMBIOS system UUID.
+/// The all zeros UUID of unconfigured hosts is skipped.
+fn freebsd_host_uuid(provider: &dyn HardwareProvider) -> Option<String> {
+    [
+        provider.run_command("sysctl", &["-n", "kern.hostuuid"]),
+        provider.run_command("kenv", &["-q", "smbios.system.uuid"]),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|id| normalize_machine_id(id).is_some_and(|id| !is_bogus_system_uuid(&id)))
+}
+
+/// Reads the machine id of the operating system.
+/// # Errors
+/// Returns `UniqueIdError::SourceUnavailable` if no source holds a valid machine id,
+/// e.g. in minimal containers without `/etc/machine-id`.
+pub(crate) fn machine_id(provider: &dyn HardwareProvider) -> Result<String, UniqueIdError> {
+    let id = if cfg!(target_os = "windows") {
+        registry::query(
+            provider,
+            "HKLM\\SOFTWARE\\Microsoft\\Cryptography",
+            "MachineGuid",
+            false,
+        )
+        .into_values()
+        .find_map(|mut key| key.remove("MachineGuid"))
+    } else if cfg!(target_os = "macos") {
+        provider
+            .run_command("ioreg", &["-rd1", "-c", "IOPlatformExpertDevice"])
+            .and_then(|output| parse_ioreg_property(&output, "IOPlatformUUID"))
+    } else if cfg!(target_os = "freebsd") {
+        freebsd_host_uuid(provider)
+    } else {
+        LINUX_MACHINE_ID_FILES
+            .iter()
+            .filter_map(|file| provider.read_to_string(file))
+            .find(|id| normalize_machine_id(id).is_some())
+    };
+
+    id.as_deref()
+        .and_then(normalize_machine_id)
+        .ok_or(UniqueIdError::SourceUnavailable(IdentifierType::MACHINE))
+}
+
+impl IdentifierTypeDataList {
+    /// Collects the machine id of the operating system, or nothing if it is unavailable.
+    pub(crate) fn collect_machine(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        match machine_id(provider) {
+            Ok(id) => {
+                let mut block = IdentifierTypeDataList::new(IdentifierType::MACHINE);
+                block.push("id", &id);
+                vec![block]
+            }
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierOptions, SysinfoProvider};
+
+    #[test]
+    fn test_parse_machine_id() {
+        assert_eq!(
+            normalize_machine_id("3D1219C7C4C5404AAA1F6D2A48ADFDA4\n"),
+            Some("3d1219c7c4c5404aaa1f6d2a48adfda4".to_string())
+        );
+        assert_eq!(normalize_machine_id("uninitialized\n"), None);
+        assert_eq!(normalize_machine_id(""), None);
+
+        let ioreg = "+-o MacBookPro18,3  <class IOPlatformExpertDevice>\n    {\n      \
+                     \"IOPlatformSerialNumber\" = \"C02XXXXXXXXX\"\n      \
+                     \"IOPlatformUUID\" = \"A1B2C3D4-E5F6-4718-9A0B-1C2D3E4F5A6B\"\n    }\n";
+        assert_eq!(
+            parse_ioreg_property(ioreg, "IOPlatformUUID"),
+            Some("A1B2C3D4-E5F6-4718-9A0B-1C2D3E4F5A6B".to_string())
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_machine_id_fallback() {
+        let provider = FakeProvider::new(FakeData {
+            files: [(
+                "/var/lib/dbus/machine-id".to_string(),
+                b"3d1219c7c4c5404aaa1f6d2a48adfda4\n".to_vec(),
+            )]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::MACHINE)
+                .build_with(&provider, &IdentifierOptions::default()),
+            "MACHINE(id=3d1219c7c4c5404aaa1f6d2a48adfda4)"
+        );
+
+        provider.update(|data| data.files.clear());
+
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::MACHINE)
+                .try_collect_with(&provider, &IdentifierOptions::default()),
+            Err(UniqueIdError::SourceUnavailable(IdentifierType::MACHINE))
+        );
+    }
+
+    #[test]
+    fn test_machine_id_on_system() {
+        // Minimal containers have no machine id, the error is the expected outcome there.
+        if let Ok(id) = machine_id(&SysinfoProvider::new()) {
+            assert_eq!(id.chars().filter(char::is_ascii_hexdigit).count(), 32);
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "freebsd")]
+    fn test_freebsd_host_uuid() {
+        let provider = FakeProvider::new(FakeData {
+            commands: [
+                (
+                    "sysctl -n kern.hostuuid".to_string(),
+                    "00000000-0000-0000-0000-000000000000\n".to_string(),
+                ),
+                (
+                    "kenv -q smbios.system.uuid".to_string(),
+                    "4C4C4544-0042-3510-8052-B4C04F564E32\n".to_string(),
+                ),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            machine_id(&provider),
+            Ok("4c4c4544-0042-3510-8052-b4c04f564e32".to_string())
+        );
+        assert_eq!(
+            machine_id(&FakeProvider::default()),
+            Err(UniqueIdError::SourceUnavailable(IdentifierType::MACHINE))
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+use std::process::ExitCode;
+
+use uniqueid::{
+    ComponentData, FakeData, FakeProvider, HardwareProvider, Identifier, IdentifierBuilder,
+    IdentifierDiff, Snapshot, StringNormalization, SysinfoProvider,
+};
+
+/// Environment variable pointing to a JSON FakeData file used instead of the real hardware.
+const FAKE_HARDWARE_ENV: &str = "UNIQUEID_FAKE_HARDWARE";
+
+const USAGE: &str = "Usage:
+    uniqueid [--name NAME] [--json]            Print the identifier hash of this machine
+    uniqueid snapshot [--name NAME]            Print a JSON snapshot of this machine
+    uniqueid verify <HASH|SNAPSHOT> [--json]   Verify this machine against a hash or snapshot file
+    uniqueid diff <SNAPSHOT> [--json]          Compare this machine against a snapshot file
+
+Exit codes: 0 = match, 1 = mismatch, 2 = error";
+
+/// Exit code returned when the machine matches.
+const EXIT_MATCH: u8 = 0;
+/// Exit code returned when the machine does not match.
+const EXIT_MISMATCH: u8 = 1;
+/// Exit code returned when an error occurred.
+const EXIT_ERROR: u8 = 2;
+
+struct Args {
+    command: Option<String>,
+    target: Option<String>,
+    name: Option<String>,
+    json: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        command: None,
+        target: None,
+        name: None,
+        json: false,
+    };
+
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--json" => args.json = true,
+            "--name" => args.name = Some(iter.next().ok_or("--name requires a value")?),
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if arg.starts_with('-') => return Err(format!("Unknown option: {}", arg)),
+            _ if args.command.is_none() => args.command = Some(arg),
+            _ if args.target.is_none() => args.target = Some(arg),
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    Ok(args)
+}
+
+/// Returns the provider to collect from, honoring the fake hardware override.
+fn provider() -> Result<Box<dyn HardwareProvider>, String> {
+    match std::env::var(FAKE_HARDWARE_ENV) {
+        Ok(path) => {
+            let data: FakeData = serde_json::from_str(&read(&path)?)
+                .map_err(|e| format!("Invalid fake hardware file {}: {}", path, e))?;
+            Ok(Box::new(FakeProvider::new(data)))
+        }
+        Err(_) => Ok(Box::new(SysinfoProvider::new())),
+    }
+}
+
+fn read(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path, e))
+}
+
+fn read_snapshot(path: &str) -> Result<Snapshot, String> {
+    serde_json::from_str(&read(path)?).map_err(|e| format!("Invalid snapshot {}: {}", path, e))
+}
+
+fn identifier(name: Option<String>) -> Identifier {
+    let mut builder = IdentifierBuilder::with_platform_defaults();
+
+    if let Some(name) = name {
+        builder.name(name);
+    }
+
+    builder.build()
+}
+
+fn render(data: &ComponentData) -> String {
+    let sorted: BTreeMap<_, _> = data.iter().collect();
+
+    sorted
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn print_diff(diff: &IdentifierDiff) {
+    for (identifier, data) in &diff.removed {
+        println!("- {}({})", identifier.as_str(), render(data));
+    }
+    for (identifier, data) in &diff.added {
+        println!("+ {}({})", identifier.as_str(), render(data));
+    }
+    for (identifier, old, new) in &diff.changed {
+        println!(
+            "~ {}({}) -> ({})",
+            identifier.as_str(),
+            render(old),
+            render(new)
+        );
+    }
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Compares the current machine against a stored snapshot.
+fn compare(stored: &Snapshot, provider: &dyn HardwareProvider, json: bool) -> Result<u8, String> {
+    let current = Snapshot::new_with(&stored.identifier, provider);
+    let diff = stored.diff(&current);
+    let matches = stored.matches(&current);
+
+    if json {
+        print_json(&serde_json::json!({
+            "match": matches,
+            "hash": current.hash,
+            "diff": diff,
+        }))?;
+    } else {
+        println!("{}", if matches { "match" } else { "mismatch" });
+        print_diff(&diff);
+    }
+
+    Ok(if matches { EXIT_MATCH } else { EXIT_MISMATCH })
+}
+
+fn run(args: Args) -> Result<u8, String> {
+    let provider = provider()?;
+
+    match args.command.as_deref() {
+        None => {
+            let hash = identifier(args.name).to_string_with(provider.as_ref(), true);
+
+            if args.json {
+                print_json(&serde_json::json!({ "hash": hash }))?;
+            } else {
+                println!("{}", hash);
+            }
+
+            Ok(EXIT_MATCH)
+        }
+        Some("snapshot") => {
+            print_json(&Snapshot::new_with(
+                &identifier(args.name),
+                provider.as_ref(),
+            ))?;
+
+            Ok(EXIT_MATCH)
+        }
+        Some("verify") => {
+            let target = args
+                .target
+                .ok_or("verify requires a hash or snapshot file")?;
+
+            if std::path::Path::new(&target).is_file() {
+                return compare(&read_snapshot(&target)?, provider.as_ref(), args.json);
+            }
+
+            let identifier = identifier(args.name);
+            let hash = identifier.to_string_with(provider.as_ref(), true);
+            // Hashes printed before the format version was introduced, or before the strict
+            // normalization of the collected values, are still accepted.
+            let legacy = identifier.to_string_v1_with(provider.as_ref(), true);
+            let mut unnormalized = identifier.clone();
+            unnormalized.options.string_normalization = StringNormalization::Legacy;
+            let unnormalized = unnormalized.to_string_with(provider.as_ref(), true);
+            let matches = [&hash, &legacy, &unnormalized]
+                .iter()
+                .any(|hash| hash.eq_ignore_ascii_case(target.trim()));
+
+            if args.json {
+                print_json(&serde_json::json!({ "match": matches, "hash": hash }))?;
+            } else {
+                println!("{}", if matches { "match" } else { "mismatch" });
+            }
+
+            Ok(if matches { EXIT_MATCH } else { EXIT_MISMATCH })
+        }
+        Some("diff") => {
+            let target = args.target.ok_or("diff requires a snapshot file")?;
+
+            compare(&read_snapshot(&target)?, provider.as_ref(), args.json)
+        }
+        Some(command) => Err(format!("Unknown command: {}\n\n{}", command, USAGE)),
+    }
+}
+
+fn main() -> ExitCode {
+    let result = parse_args().and_then(run);
+
+    match result {
+        Ok(code) => ExitCode::from(code),
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
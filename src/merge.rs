@@ -0,0 +1,282 @@
+use crate::{Identifier, IdentifierType, IdentifierTypeDataList, UniqueIdError};
+
+/// Enum representing how conflicts are resolved when merging two Identifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MergePolicy {
+    /// Any conflict is an error. Symmetric.
+    #[default]
+    Error,
+    /// The left Identifier (self) wins conflicts.
+    PreferLeft,
+    /// The right Identifier (other) wins conflicts.
+    PreferRight,
+    /// Both sides are kept: conflicting components are both included and names are joined with `+`
+    /// in sorted order. Conflicting options are an error. Symmetric.
+    Concatenate,
+}
+
+impl Identifier {
+    /// Merges this Identifier with another Identifier.
+    ///
+    /// Components with equal data are deduplicated, and the components of the merged Identifier
+    /// are sorted by type and data, so under a symmetric policy (Error, Concatenate) the result
+    /// does not depend on the argument order.
+    /// # Arguments
+    /// * `other` - The Identifier to merge into this one.
+    /// * `policy` - The MergePolicy deciding conflicts on the name, the options and components of the same type.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{Identifier, IdentifierType, IdentifierTypeData, IdentifierTypeDataList, MergePolicy};
+    ///
+    /// let mut client = Identifier::new("app");
+    /// client.data.push(IdentifierTypeDataList::new(IdentifierType::CPU));
+    ///
+    /// let mut server = Identifier::default();
+    /// let mut ram = IdentifierTypeDataList::new(IdentifierType::RAM);
+    /// ram.data.push(IdentifierTypeData::new("t", "1024"));
+    /// server.data.push(ram);
+    ///
+    /// let merged = client.merge(&server, MergePolicy::Error).unwrap();
+    ///
+    /// assert_eq!(merged.name, Some("app".to_string()));
+    /// assert_eq!(merged.data.len(), 2);
+    /// ```
+    pub fn merge(
+        &self,
+        other: &Identifier,
+        policy: MergePolicy,
+    ) -> Result<Identifier, UniqueIdError> {
+        if policy == MergePolicy::PreferRight {
+            return other.merge(self, MergePolicy::PreferLeft);
+        }
+
+        let name = match (&self.name, &other.name) {
+            (Some(left), Some(right)) if left != right => match policy {
+                MergePolicy::Error => {
+                    return Err(UniqueIdError::NameConflict(left.clone(), right.clone()))
+                }
+                MergePolicy::Concatenate => {
+                    let mut names = [left.as_str(), right.as_str()];
+                    names.sort_unstable();
+                    Some(names.join("+"))
+                }
+                MergePolicy::PreferLeft | MergePolicy::PreferRight => Some(left.clone()),
+            },
+            (left, right) => left.clone().or_else(|| right.clone()),
+        };
+
+        if self.options != other.options
+            && matches!(policy, MergePolicy::Error | MergePolicy::Concatenate)
+        {
+            return Err(UniqueIdError::OptionsConflict);
+        }
+
+        let mut data: Vec<IdentifierTypeDataList> = self.data.clone();
+
+        for list in &other.data {
+            if data.contains(list) {
+                continue;
+            }
+
+            let conflict = self.data.iter().any(|i| i.identifier == list.identifier);
+
+            match policy {
+                _ if !conflict => data.push(list.clone()),
+                MergePolicy::Concatenate => data.push(list.clone()),
+                MergePolicy::Error => {
+                    return Err(UniqueIdError::ComponentConflict(list.identifier.clone()))
+                }
+                MergePolicy::PreferLeft | MergePolicy::PreferRight => {}
+            }
+        }
+
+        data.sort();
+
+        Ok(Identifier {
+            name,
+            data,
+            options: self.options.clone(),
+            description: self
+                .description
+                .clone()
+                .or_else(|| other.description.clone()),
+        })
+    }
+
+    /// Splits the Identifier into a primary Identifier with the components of the given types and
+    /// a backup Identifier with the other components, for a two-level match: if the primary hash
+    /// does not match on verification, the backup hash is compared instead.
+    ///
+    /// Both Identifiers keep the name, the description and the options, and the components keep
+    /// their order.
+    /// # Arguments
+    /// * `primary_types` - The IdentifierTypes of the components of the primary Identifier.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.name("app");
+    /// builder.add(IdentifierType::CPU);
+    /// builder.add(IdentifierType::RAM);
+    /// builder.add(IdentifierType::DISK);
+    /// builder.add(IdentifierType::OS);
+    ///
+    /// let (primary, backup) = builder
+    ///     .build()
+    ///     .split_into_primary_backup(&[IdentifierType::CPU, IdentifierType::DISK]);
+    ///
+    /// // Stored at activation.
+    /// let (stored_primary, stored_backup) = (primary.to_string(true), backup.to_string(true));
+    ///
+    /// // Verification falls back to the backup if the primary components changed.
+    /// let matches = primary.to_string(true) == stored_primary
+    ///     || backup.to_string(true) == stored_backup;
+    ///
+    /// assert!(matches);
+    /// assert_eq!(primary.data.len(), 2);
+    /// assert_eq!(backup.data[0].identifier, IdentifierType::RAM);
+    /// ```
+    pub fn split_into_primary_backup(
+        &self,
+        primary_types: &[IdentifierType],
+    ) -> (Identifier, Identifier) {
+        let (primary, backup): (Vec<_>, Vec<_>) = self
+            .data
+            .iter()
+            .cloned()
+            .partition(|list| primary_types.contains(&list.identifier));
+
+        let split = |data| Identifier {
+            name: self.name.clone(),
+            data,
+            options: self.options.clone(),
+            description: self.description.clone(),
+        };
+
+        (split(primary), split(backup))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HashAlgorithm, IdentifierTypeData};
+
+    fn list(identifier: IdentifierType, key: &str, value: &str) -> IdentifierTypeDataList {
+        let mut list = IdentifierTypeDataList::new(identifier);
+        list.data.push(IdentifierTypeData::new(key, value));
+        list
+    }
+
+    fn identifier(name: Option<&str>, data: Vec<IdentifierTypeDataList>) -> Identifier {
+        Identifier {
+            name: name.map(|name| name.to_string()),
+            data,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_error() {
+        let left = identifier(Some("a"), vec![list(IdentifierType::CPU, "b", "x")]);
+        let right = identifier(None, vec![list(IdentifierType::RAM, "t", "1")]);
+
+        let merged = left.merge(&right, MergePolicy::Error).unwrap();
+        assert_eq!(merged, right.merge(&left, MergePolicy::Error).unwrap());
+        assert_eq!(merged.to_string(false), "a[CPU(b=x), RAM(t=1)]");
+
+        let conflict = identifier(None, vec![list(IdentifierType::CPU, "b", "y")]);
+        assert_eq!(
+            left.merge(&conflict, MergePolicy::Error),
+            Err(UniqueIdError::ComponentConflict(IdentifierType::CPU))
+        );
+
+        let renamed = identifier(Some("b"), vec![]);
+        assert_eq!(
+            left.merge(&renamed, MergePolicy::Error),
+            Err(UniqueIdError::NameConflict(
+                "a".to_string(),
+                "b".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_merge_prefer() {
+        let left = identifier(Some("a"), vec![list(IdentifierType::CPU, "b", "x")]);
+        let right = identifier(Some("b"), vec![list(IdentifierType::CPU, "b", "y")]);
+
+        let merged = left.merge(&right, MergePolicy::PreferLeft).unwrap();
+        assert_eq!(merged.to_string(false), "a[CPU(b=x)]");
+
+        let merged = left.merge(&right, MergePolicy::PreferRight).unwrap();
+        assert_eq!(merged.to_string(false), "b[CPU(b=y)]");
+    }
+
+    #[test]
+    fn test_merge_concatenate() {
+        let left = identifier(
+            Some("client"),
+            vec![
+                list(IdentifierType::DISK, "t", "1"),
+                list(IdentifierType::RAM, "t", "1"),
+            ],
+        );
+        let right = identifier(
+            Some("server"),
+            vec![
+                list(IdentifierType::DISK, "t", "2"),
+                list(IdentifierType::RAM, "t", "1"),
+            ],
+        );
+
+        let merged = left.merge(&right, MergePolicy::Concatenate).unwrap();
+        let reversed = right.merge(&left, MergePolicy::Concatenate).unwrap();
+
+        assert_eq!(
+            merged.to_string(false),
+            "client+server[RAM(t=1), DISK(t=1), DISK(t=2)]"
+        );
+        assert_eq!(
+            merged.hash(HashAlgorithm::SHA3_512),
+            reversed.hash(HashAlgorithm::SHA3_512)
+        );
+        assert_eq!(merged.to_string(true), reversed.to_string(true));
+    }
+
+    #[test]
+    fn test_split_into_primary_backup() {
+        let mut original = identifier(
+            Some("app"),
+            vec![
+                list(IdentifierType::CPU, "b", "x"),
+                list(IdentifierType::RAM, "t", "1"),
+                list(IdentifierType::DISK, "t", "2"),
+                list(IdentifierType::DISK, "t", "3"),
+            ],
+        );
+        original.description = Some("license server".to_string());
+
+        let (primary, backup) =
+            original.split_into_primary_backup(&[IdentifierType::CPU, IdentifierType::DISK]);
+
+        assert_eq!(
+            primary.to_string(false),
+            "app[CPU(b=x), DISK(t=2), DISK(t=3)]"
+        );
+        assert_eq!(backup.to_string(false), "app[RAM(t=1)]");
+        assert_eq!(primary.description, original.description);
+        assert_eq!(backup.options, original.options);
+
+        // Merging the halves back gives the same components.
+        let merged = primary.merge(&backup, MergePolicy::Error).unwrap();
+        let mut expected = original.data.clone();
+        expected.sort();
+        assert_eq!(merged.data, expected);
+
+        let (primary, backup) = original.split_into_primary_backup(&[]);
+        assert!(primary.data.is_empty());
+        assert_eq!(backup.data, original.data);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// The file holding the microcode revision of the first logical processor on Linux.
+const LINUX_MICROCODE_FILE: &str = "/sys/devices/system/cpu/cpu0/microcode/version";
+
+/// Normalizes a hex microcode revision to lowercase without leading zeros. (e.g. 0x000000F0 -> 0xf0)
+pub(crate) fn normalize_revision(revision: &str) -> Option<String> {
+    let revision = revision.trim();
+    let digits = revision
+        .strip_prefix("0x")
+        .or_else(|| revision.strip_prefix("0X"))
+        .unwrap_or(revision);
+
+    u64::from_str_radix(digits, 16)
+        .ok()
+        .map(|revision| format!("{:#x}", revision))
+}
+
+/// Reads the microcode revision of the CPU, from sysfs or the `microcode` line of `/proc/cpuinfo`.
+/// Only Linux exposes the revision without elevated privileges.
+fn microcode_revision(provider: &dyn HardwareProvider) -> Option<String> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    let cpuinfo = || {
+        provider
+            .read_to_string("/proc/cpuinfo")
+            .and_then(|cpuinfo| {
+                cpuinfo.lines().find_map(|line| {
+                    let (key, value) = line.split_once(':')?;
+                    (key.trim() == "microcode").then(|| value.to_string())
+                })
+            })
+    };
+
+    provider
+        .read_to_string(LINUX_MICROCODE_FILE)
+        .or_else(cpuinfo)
+        .and_then(|revision| normalize_revision(&revision))
+}
+
+impl IdentifierTypeDataList {
+    /// Collects the microcode revision of the CPU, or nothing if it is not readable.
+    pub(crate) fn collect_cpu_microcode(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        match microcode_revision(provider) {
+            Some(revision) => {
+                let mut block = IdentifierTypeDataList::new(IdentifierType::CPU_MICROCODE);
+                block.push("v", &revision);
+                vec![block]
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierOptions, UniqueIdError};
+
+    #[test]
+    fn test_normalize_revision() {
+        assert_eq!(normalize_revision("0xf0\n"), Some("0xf0".to_string()));
+        assert_eq!(normalize_revision(" 0x000000F0"), Some("0xf0".to_string()));
+        assert_eq!(normalize_revision("a201016"), Some("0xa201016".to_string()));
+        assert_eq!(normalize_revision(""), None);
+        assert_eq!(normalize_revision("unknown"), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_cpu_microcode() {
+        let data = IdentifierTypeDataList::new(IdentifierType::CPU_MICROCODE);
+        let options = IdentifierOptions::default();
+
+        let sysfs = FakeProvider::new(FakeData {
+            files: [
+                (LINUX_MICROCODE_FILE.to_string(), b"0xf0\n".to_vec()),
+                (
+                    "/proc/cpuinfo".to_string(),
+                    b"processor\t: 0\nmicrocode\t: 0xea\n".to_vec(),
+                ),
+            ]
+            .into(),
+            ..Default::default()
+        });
+        let cpuinfo = FakeProvider::new(FakeData {
+            files: [(
+                "/proc/cpuinfo".to_string(),
+                b"processor\t: 0\nmicrocode\t: 0xa201016\n".to_vec(),
+            )]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(data.build_with(&sysfs, &options), "CPU_MICROCODE(v=0xf0)");
+        assert_eq!(
+            data.build_with(&cpuinfo, &options),
+            "CPU_MICROCODE(v=0xa201016)"
+        );
+        assert_eq!(
+            data.try_collect_with(&FakeProvider::default(), &options),
+            Err(UniqueIdError::SourceUnavailable(
+                IdentifierType::CPU_MICROCODE
+            ))
+        );
+    }
+}
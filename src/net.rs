@@ -0,0 +1,424 @@
+use crate::wmi::{self, WmiRecord};
+use crate::{HardwareProvider, IdentifierOptions, IdentifierType, IdentifierTypeDataList};
+
+/// The directory holding the network interfaces on Linux.
+const LINUX_NET_DIR: &str = "/sys/class/net";
+
+/// A single network interface.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct NetworkInterface {
+    /// The name of the interface. (e.g. eth0, en0, Ethernet)
+    pub name: String,
+    /// The MAC address as lowercase, colon separated hex. (e.g. 02:fc:00:00:00:01)
+    pub mac: String,
+    /// Whether the interface is backed by a hardware adapter.
+    pub physical: bool,
+}
+
+/// Name prefixes of virtual and transient interfaces, compared case-insensitively: container and
+/// VM bridges, veth pairs, VPN tunnels, hypervisor host-only adapters and Bluetooth PAN.
+const VIRTUAL_PREFIXES: [&str; 22] = [
+    "veth",
+    "docker",
+    "br-",
+    "virbr",
+    "cni",
+    "flannel",
+    "tun",
+    "tap",
+    "wg",
+    "tailscale",
+    "zt",
+    "ppp",
+    "ipsec",
+    "utun",
+    "awdl",
+    "llw",
+    "bnep",
+    "vmnet",
+    "vboxnet",
+    "vethernet",
+    "vmware network adapter",
+    "virtualbox host-only",
+];
+
+/// Name fragments of virtual adapters on Windows, where adapters are named after their driver.
+const VIRTUAL_FRAGMENTS: [&str; 4] = ["bluetooth", "hyper-v", "tap-windows", "wireguard"];
+
+/// A policy selecting the network interfaces included in the NET component.
+///
+/// The default policy selects physical adapters with a globally unique MAC address only: virtual
+/// interfaces (see `InterfacePolicy::is_virtual`) and locally administered MAC addresses, such as
+/// randomized Wi-Fi addresses, are left out, so starting a container or connecting a VPN does not
+/// change the Identifier.
+///
+/// Patterns match an interface name case-insensitively, exactly or as a prefix with a trailing `*`.
+/// (e.g. `eth0`, `enp*`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct InterfacePolicy {
+    /// The patterns of interfaces always included, even if classified as virtual.
+    pub allow: Vec<String>,
+    /// The patterns of interfaces never included. Takes precedence over `allow`.
+    pub deny: Vec<String>,
+    /// Whether virtual interfaces are included as well. (default: false)
+    pub include_virtual: bool,
+}
+
+/// Returns true if the interface name matches the pattern, see `InterfacePolicy`.
+fn matches_pattern(name: &str, pattern: &str) -> bool {
+    let name = name.to_lowercase();
+    let pattern = pattern.to_lowercase();
+
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == pattern,
+    }
+}
+
+/// Returns true if the locally administered bit of a normalized MAC address is set.
+fn is_locally_administered(mac: &str) -> bool {
+    u8::from_str_radix(&mac[..2], 16).is_ok_and(|octet| octet & 0x02 != 0)
+}
+
+impl InterfacePolicy {
+    /// Adds a pattern of interfaces always included.
+    /// # Examples
+    /// ```
+    /// use uniqueid::InterfacePolicy;
+    ///
+    /// let mut policy = InterfacePolicy::default();
+    /// policy.allow("wg0").deny("enp*");
+    ///
+    /// assert_eq!(policy.allow, vec!["wg0"]);
+    /// ```
+    pub fn allow<T: Into<String>>(&mut self, pattern: T) -> &mut Self {
+        self.allow.push(pattern.into());
+        self
+    }
+
+    /// Adds a pattern of interfaces never included.
+    pub fn deny<T: Into<String>>(&mut self, pattern: T) -> &mut Self {
+        self.deny.push(pattern.into());
+        self
+    }
+
+    /// Returns true if an interface is virtual or transient, judging by its name and whether it is
+    /// backed by a hardware adapter.
+    /// # Examples
+    /// ```
+    /// use uniqueid::InterfacePolicy;
+    ///
+    /// assert!(InterfacePolicy::is_virtual("docker0", true));
+    /// assert!(InterfacePolicy::is_virtual("vEthernet (Default Switch)", true));
+    /// assert!(!InterfacePolicy::is_virtual("enp3s0", true));
+    /// ```
+    pub fn is_virtual(name: &str, physical: bool) -> bool {
+        let lowercase = name.to_lowercase();
+
+        !physical
+            || VIRTUAL_PREFIXES
+                .iter()
+                .any(|prefix| lowercase.starts_with(prefix))
+            || VIRTUAL_FRAGMENTS
+                .iter()
+                .any(|fragment| lowercase.contains(fragment))
+    }
+
+    /// Returns true if the policy includes the interface. The MAC address must be normalized.
+    pub(crate) fn selects(&self, interface: &NetworkInterface, mac: &str) -> bool {
+        let matches = |patterns: &[String]| {
+            patterns
+                .iter()
+                .any(|pattern| matches_pattern(&interface.name, pattern))
+        };
+
+        if matches(&self.deny) {
+            return false;
+        }
+
+        if matches(&self.allow) || self.include_virtual {
+            return true;
+        }
+
+        !InterfacePolicy::is_virtual(&interface.name, interface.physical)
+            && !is_locally_administered(mac)
+    }
+}
+
+/// Normalizes a MAC address to lowercase, colon separated hex. (e.g. 02-FC-00-00-00-01)
+/// Returns None if the address is not a 48 bit MAC address or is all zeros.
+pub(crate) fn normalize_mac(mac: &str) -> Option<String> {
+    let octets: Vec<&str> = mac.trim().split([':', '-']).collect();
+
+    let valid = octets.len() == 6
+        && octets
+            .iter()
+            .all(|octet| octet.len() == 2 && octet.chars().all(|c| c.is_ascii_hexdigit()));
+
+    if !valid || octets.iter().all(|octet| *octet == "00") {
+        return None;
+    }
+
+    Some(octets.join(":").to_lowercase())
+}
+
+/// Reads the network interfaces from sysfs. An interface is physical if it has a backing device.
+pub(crate) fn linux_interfaces(provider: &dyn HardwareProvider) -> Vec<NetworkInterface> {
+    provider
+        .read_dir(LINUX_NET_DIR)
+        .into_iter()
+        .filter_map(|name| {
+            let read = |file: &str| {
+                provider.read_to_string(&format!("{}/{}/{}", LINUX_NET_DIR, name, file))
+            };
+
+            Some(NetworkInterface {
+                mac: read("address")?,
+                physical: read("device/uevent").is_some(),
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Parses the Win32_NetworkAdapter WMI instances.
+pub(crate) fn parse_win32_network_adapter(records: &[WmiRecord]) -> Vec<NetworkInterface> {
+    records
+        .iter()
+        .map(|record| {
+            let field = |name: &str| record.get(name).cloned().unwrap_or_default();
+
+            NetworkInterface {
+                name: field("NetConnectionID"),
+                mac: field("MACAddress"),
+                physical: field("PhysicalAdapter") == "True",
+            }
+        })
+        .collect()
+}
+
+/// Parses the output of `networksetup -listallhardwareports`, which only lists hardware ports.
+pub(crate) fn parse_networksetup_hardware_ports(output: &str) -> Vec<NetworkInterface> {
+    let mut result = Vec::new();
+    let mut name = None;
+
+    for line in output.lines() {
+        if let Some(device) = line.strip_prefix("Device: ") {
+            name = Some(device.trim().to_string());
+        }
+
+        if let Some(mac) = line.strip_prefix("Ethernet Address: ") {
+            result.push(NetworkInterface {
+                name: name.take().unwrap_or_default(),
+                mac: mac.trim().to_string(),
+                physical: true,
+            });
+        }
+    }
+
+    result
+}
+
+/// Reads the network interfaces of the system.
+fn interfaces(provider: &dyn HardwareProvider) -> Vec<NetworkInterface> {
+    if cfg!(target_os = "windows") {
+        parse_win32_network_adapter(&wmi::query(
+            provider,
+            "Win32_NetworkAdapter",
+            &["NetConnectionID", "MACAddress", "PhysicalAdapter"],
+        ))
+    } else if cfg!(target_os = "macos") {
+        provider
+            .run_command("networksetup", &["-listallhardwareports"])
+            .map(|output| parse_networksetup_hardware_ports(&output))
+            .unwrap_or_default()
+    } else {
+        linux_interfaces(provider)
+    }
+}
+
+impl IdentifierTypeDataList {
+    /// Collects one block per network adapter selected by `IdentifierOptions::net_policy` with its
+    /// MAC address, sorted by MAC. Loopback and interfaces without a hardware address are skipped.
+    /// The state of the interface is not read, so adapters being brought up or down does not
+    /// change the output.
+    pub(crate) fn collect_net(
+        &self,
+        provider: &dyn HardwareProvider,
+        options: &IdentifierOptions,
+    ) -> Vec<IdentifierTypeDataList> {
+        let mut macs: Vec<String> = interfaces(provider)
+            .into_iter()
+            .filter_map(|interface| {
+                let mac = normalize_mac(&interface.mac)?;
+                options.net_policy.selects(&interface, &mac).then_some(mac)
+            })
+            .collect();
+
+        macs.sort();
+        macs.dedup();
+
+        macs.into_iter()
+            .map(|mac| {
+                let mut block = IdentifierTypeDataList::new(IdentifierType::NET);
+                block.push("m", &mac);
+                block
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierBuilder, SysinfoProvider};
+
+    fn interface(name: &str, mac: &str) -> NetworkInterface {
+        NetworkInterface {
+            name: name.to_string(),
+            mac: mac.to_string(),
+            physical: true,
+        }
+    }
+
+    #[test]
+    fn test_normalize_mac() {
+        assert_eq!(
+            normalize_mac("02-FC-00-00-00-01\n"),
+            Some("02:fc:00:00:00:01".to_string())
+        );
+        assert_eq!(normalize_mac("00:00:00:00:00:00"), None);
+        assert_eq!(normalize_mac(""), None);
+        assert_eq!(
+            normalize_mac("00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00:00"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_linux_interfaces() {
+        let file = |path: &str, contents: &str| {
+            (
+                format!("{}/{}", LINUX_NET_DIR, path),
+                contents.as_bytes().to_vec(),
+            )
+        };
+
+        let provider = FakeProvider::new(FakeData {
+            files: [
+                file("lo/address", "00:00:00:00:00:00\n"),
+                file("eth1/address", "3c:ec:ef:00:00:02\n"),
+                file("eth1/device/uevent", "DRIVER=igb\n"),
+                file("eth1/operstate", "down\n"),
+                file("eth0/address", "3c:ec:ef:00:00:01\n"),
+                file("eth0/device/uevent", "DRIVER=igb\n"),
+                file("docker0/address", "02:42:ac:11:00:01\n"),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(linux_interfaces(&provider).len(), 4);
+
+        if cfg!(target_os = "linux") {
+            let mut builder = IdentifierBuilder::default();
+            builder.add(IdentifierType::NET);
+
+            assert_eq!(
+                builder.build().to_string_with(&provider, false),
+                "[NET(m=3c:ec:ef:00:00:01)NET(m=3c:ec:ef:00:00:02)]"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_interfaces() {
+        let records = wmi::parse_format_list(
+            "NetConnectionID : Ethernet\nMACAddress      : 3C:EC:EF:00:00:01\nPhysicalAdapter : True\n\n\
+             NetConnectionID : \nMACAddress      : \nPhysicalAdapter : False\n",
+        );
+
+        assert_eq!(
+            parse_win32_network_adapter(&records),
+            vec![
+                interface("Ethernet", "3C:EC:EF:00:00:01"),
+                NetworkInterface {
+                    physical: false,
+                    ..interface("", "")
+                },
+            ]
+        );
+
+        let output =
+            "\nHardware Port: Ethernet\nDevice: en0\nEthernet Address: 3c:ec:ef:00:00:01\n\n\
+                      Hardware Port: Thunderbolt Bridge\nDevice: bridge0\nEthernet Address: N/A\n\n\
+                      VLAN Configurations\n===================\n";
+
+        assert_eq!(
+            parse_networksetup_hardware_ports(output),
+            vec![
+                interface("en0", "3c:ec:ef:00:00:01"),
+                interface("bridge0", "N/A"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interface_policy() {
+        let interfaces = vec![
+            interface("enp3s0", "3c:ec:ef:00:00:01"),
+            interface("wlp2s0", "da:a1:19:00:00:02"),
+            interface("docker0", "02:42:ac:11:00:01"),
+            interface("veth1a2b3c", "3c:ec:ef:00:00:03"),
+            interface("tun0", "3c:ec:ef:00:00:04"),
+            interface("vEthernet (Default Switch)", "00:15:5d:00:00:05"),
+            interface("VirtualBox Host-Only Network", "0a:00:27:00:00:06"),
+            interface("Bluetooth Network Connection", "3c:ec:ef:00:00:07"),
+            interface("bnep0", "3c:ec:ef:00:00:08"),
+            NetworkInterface {
+                physical: false,
+                ..interface("bond0", "3c:ec:ef:00:00:09")
+            },
+        ];
+
+        let select = |policy: &InterfacePolicy| -> Vec<String> {
+            interfaces
+                .iter()
+                .filter(|interface| {
+                    policy.selects(interface, &normalize_mac(&interface.mac).unwrap())
+                })
+                .map(|interface| interface.name.clone())
+                .collect()
+        };
+
+        // The randomized Wi-Fi address is locally administered.
+        assert_eq!(select(&InterfacePolicy::default()), vec!["enp3s0"]);
+
+        let mut policy = InterfacePolicy::default();
+        policy.allow("TUN*").allow("wlp2s0").deny("enp*");
+
+        assert_eq!(select(&policy), vec!["wlp2s0", "tun0"]);
+
+        let everything = InterfacePolicy {
+            include_virtual: true,
+            deny: vec!["docker0".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(select(&everything).len(), interfaces.len() - 1);
+    }
+
+    #[test]
+    fn test_net_on_system() {
+        let blocks = IdentifierTypeDataList::new(IdentifierType::NET)
+            .collect_with(&SysinfoProvider::new(), &Default::default());
+
+        // Machines without a physical adapter, such as containers, have no NET data.
+        for block in blocks {
+            assert_eq!(block.data.len(), 1);
+            assert!(normalize_mac(&block.data[0].value).is_some());
+        }
+    }
+}
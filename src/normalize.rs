@@ -0,0 +1,196 @@
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{IdentifierOptions, IdentifierType, IdentifierTypeDataList, StringNormalization};
+
+/// The trademark symbols removed by `normalize_cpu_brand_default` and `normalize_value`, lowercase.
+/// `normalize_value` matches the ASCII spellings ignoring case.
+const TRADEMARKS: [&str; 8] = ["(r)", "(tm)", "(sm)", "(c)", "®", "™", "℠", "©"];
+
+/// The default CPU brand normalization.
+/// Strips trademark symbols, lowercases, trims and collapses the whitespace left behind.
+/// # Examples
+/// ```
+/// use uniqueid::normalize_cpu_brand_default;
+///
+/// assert_eq!(
+///     normalize_cpu_brand_default(" Intel(R) Core(TM) i7-8750H CPU @ 2.20GHz "),
+///     "intel core i7-8750h cpu @ 2.20ghz"
+/// );
+/// assert_eq!(normalize_cpu_brand_default("Intel® Core™ i7-8750H"), "intel core i7-8750h");
+/// ```
+pub fn normalize_cpu_brand_default(brand: &str) -> String {
+    let mut result = brand.to_lowercase();
+
+    for trademark in TRADEMARKS {
+        result = result.replace(trademark, " ");
+    }
+
+    result.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Replaces every trademark symbol and spelling of `TRADEMARKS` with a space, ignoring the case.
+fn strip_trademarks(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(c) = rest.chars().next() {
+        let trademark = TRADEMARKS.iter().find(|trademark| {
+            rest.get(..trademark.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(trademark))
+        });
+
+        match trademark {
+            Some(trademark) => {
+                result.push(' ');
+                rest = &rest[trademark.len()..];
+            }
+            None => {
+                result.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    result
+}
+
+/// Normalizes a collected string value, as applied by `StringNormalization::Strict`.
+/// Strips trademark symbols, applies the Unicode NFKC normalization, trims and collapses runs of
+/// whitespace into a single space. The case is kept.
+/// # Examples
+/// ```
+/// use uniqueid::normalize_value;
+///
+/// assert_eq!(
+///     normalize_value("intel(r)  core(tm) i7-8700k cpu @ 3.70ghz   "),
+///     "intel core i7-8700k cpu @ 3.70ghz"
+/// );
+/// assert_eq!(normalize_value("Ｘｅｏｎ®\u{a0}Gold"), "Xeon Gold");
+/// ```
+pub fn normalize_value(value: &str) -> String {
+    // The symbols are stripped before NFKC turns `™` into `TM`, the spellings after it turned
+    // fullwidth spellings into ASCII.
+    let normalized: String = strip_trademarks(value).nfkc().collect();
+
+    strip_trademarks(&normalized)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns true if the key holds a CPU brand, `b` or `b1`, `b2`, ... on mixed systems.
+fn is_cpu_brand(identifier: &IdentifierType, key: &str) -> bool {
+    *identifier == IdentifierType::CPU
+        && key
+            .strip_prefix('b')
+            .is_some_and(|index| index.chars().all(|c| c.is_ascii_digit()))
+}
+
+impl IdentifierTypeDataList {
+    /// Normalizes the values of the block with `normalize_value` under
+    /// `StringNormalization::Strict`. The CPU brand is left to `cpu_brand_normalization` if set.
+    pub(crate) fn normalize_values(&mut self, options: &IdentifierOptions) {
+        if options.string_normalization != StringNormalization::Strict {
+            return;
+        }
+
+        let custom_brand = options.cpu_brand_normalization.is_some();
+        for item in &mut self.data {
+            if !(custom_brand && is_cpu_brand(&self.identifier, &item.key)) {
+                item.value = normalize_value(&item.value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        FakeData, FakeProvider, IdentifierOptions, IdentifierType, IdentifierTypeDataList,
+        ProcessorInfo, StringNormalization,
+    };
+
+    #[test]
+    fn test_normalize_value() {
+        // Pinned: changing these breaks the verification of stored hashes.
+        let golden = [
+            (
+                "Intel(R) Core(TM) i7-8700K CPU @ 3.70GHz",
+                "Intel Core i7-8700K CPU @ 3.70GHz",
+            ),
+            (
+                "       Intel(R) Xeon(R) CPU E5-2680 v4 @ 2.40GHz",
+                "Intel Xeon CPU E5-2680 v4 @ 2.40GHz",
+            ),
+            (
+                "AMD Ryzen 7 5800X 8-Core Processor             ",
+                "AMD Ryzen 7 5800X 8-Core Processor",
+            ),
+            (
+                "Intel(R) Core(TM)2 Duo CPU     E8400  @ 3.00GHz",
+                "Intel Core 2 Duo CPU E8400 @ 3.00GHz",
+            ),
+            ("Intel® Core™ i5-1135G7", "Intel Core i5-1135G7"),
+            ("Intel®Core™i5-1135G7", "Intel Core i5-1135G7"),
+            ("ＡＭＤ　Ｒｙｚｅｎ　５", "AMD Ryzen 5"),
+            ("Apple\u{a0}M2\tPro\n", "Apple M2 Pro"),
+            ("Pentium(tm) Pro", "Pentium Pro"),
+            (
+                "Qualcomm® Snapdragon™ 8cx Gen 3",
+                "Qualcomm Snapdragon 8cx Gen 3",
+            ),
+            ("Ａｒｍ℠ Cortex-A78", "Arm Cortex-A78"),
+            ("ｌｅｎｏｖｏ (c) 2023", "lenovo 2023"),
+            ("（Ｒ）Ｐｅｎｔｉｕｍ", "Pentium"),
+            ("", ""),
+        ];
+
+        for (raw, normalized) in golden {
+            assert_eq!(normalize_value(raw), normalized, "{:?}", raw);
+            assert_eq!(normalize_value(normalized), normalized);
+        }
+    }
+
+    #[test]
+    fn test_string_normalization() {
+        let provider = |brand: &str| {
+            FakeProvider::new(FakeData {
+                processors: vec![ProcessorInfo {
+                    brand: brand.to_string(),
+                    vendor: "GenuineIntel".to_string(),
+                    frequency: 3700,
+                }],
+                ..Default::default()
+            })
+        };
+        let padded = provider("Intel(R) Core(TM) i7-8700K  CPU @ 3.70GHz ");
+        let localized = provider("Intel® Core™ i7-8700K CPU @ 3.70GHz");
+
+        let data = IdentifierTypeDataList::new(IdentifierType::CPU);
+        let strict = IdentifierOptions::default();
+        assert_eq!(strict.string_normalization, StringNormalization::Strict);
+
+        assert_eq!(
+            data.build_with(&padded, &strict),
+            "CPU(b=intel core i7-8700k cpu @ 3.70ghz, v=genuineintel, f=3700, c=1)"
+        );
+        assert_eq!(
+            data.build_with(&padded, &strict),
+            data.build_with(&localized, &strict)
+        );
+
+        let legacy = IdentifierOptions {
+            string_normalization: StringNormalization::Legacy,
+            ..Default::default()
+        };
+        assert_eq!(
+            data.build_with(&padded, &legacy),
+            "CPU(b=intel(r) core(tm) i7-8700k  cpu @ 3.70ghz, v=genuineintel, f=3700, c=1)"
+        );
+        assert_ne!(
+            data.build_with(&padded, &legacy),
+            data.build_with(&localized, &legacy)
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use crate::topology::parse_cpu_list;
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList, UNKNOWN};
+
+/// The directory holding the NUMA nodes on Linux.
+const LINUX_NODE_DIR: &str = "/sys/devices/system/node";
+
+/// A struct representing a NUMA node, its memory and the logical processors assigned to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NumaNode {
+    /// The number of the node. (node0 -> 0)
+    pub id: usize,
+    /// The total memory of the node in KB, if known.
+    pub memory: Option<u64>,
+    /// The logical processors of the node as ranges. (e.g. 0-7+16-23)
+    pub cpus: String,
+}
+
+/// Parses the `MemTotal` line of a node meminfo file in KB. (Node 0 MemTotal:  32768000 kB)
+pub(crate) fn parse_node_meminfo(meminfo: &str) -> Option<u64> {
+    meminfo.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        if !key.trim_end().ends_with("MemTotal") {
+            return None;
+        }
+        value.split_whitespace().next()?.parse().ok()
+    })
+}
+
+/// Reads the NUMA nodes from sysfs, sorted by number. Systems without NUMA support have none.
+pub(crate) fn linux_numa_nodes(provider: &dyn HardwareProvider) -> Vec<NumaNode> {
+    let mut nodes: Vec<NumaNode> = provider
+        .read_dir(LINUX_NODE_DIR)
+        .iter()
+        .filter_map(|entry| {
+            let id = entry.strip_prefix("node")?.parse().ok()?;
+            let read = |file: &str| {
+                provider.read_to_string(&format!("{}/{}/{}", LINUX_NODE_DIR, entry, file))
+            };
+
+            Some(NumaNode {
+                id,
+                memory: read("meminfo").and_then(|meminfo| parse_node_meminfo(&meminfo)),
+                // Normalized, so an equivalent list written differently gives the same value.
+                cpus: format_cpu_list(&parse_cpu_list(&read("cpulist").unwrap_or_default())),
+            })
+        })
+        .collect();
+
+    nodes.sort_by_key(|node| node.id);
+    nodes
+}
+
+/// Formats CPU numbers as ranges joined by `+`, as commas are reserved in component values.
+/// (0, 1, 2, 3, 8 -> 0-3+8)
+pub(crate) fn format_cpu_list(cpus: &[usize]) -> String {
+    let mut cpus = cpus.to_vec();
+    cpus.sort_unstable();
+    cpus.dedup();
+
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for cpu in cpus {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == cpu => *end = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+
+    ranges
+        .iter()
+        .map(|(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{}-{}", start, end),
+        })
+        .collect::<Vec<String>>()
+        .join("+")
+}
+
+impl IdentifierTypeDataList {
+    /// Collects the NUMA nodes: one block per node with its number, total memory in KB and logical
+    /// processors. Only Linux exposes the NUMA topology, nothing is collected elsewhere.
+    pub(crate) fn collect_memory_topology(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        if !cfg!(target_os = "linux") {
+            return Vec::new();
+        }
+
+        linux_numa_nodes(provider)
+            .into_iter()
+            .map(|node| {
+                let mut block = IdentifierTypeDataList::new(IdentifierType::MEMORY_TOPOLOGY);
+                block.push("n", &node.id.to_string());
+                block.push(
+                    "m",
+                    &node
+                        .memory
+                        .map_or_else(|| UNKNOWN.to_string(), |m| m.to_string()),
+                );
+                block.push("c", &node.cpus);
+                block
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierOptions, UniqueIdError};
+
+    fn file(path: &str, contents: &str) -> (String, Vec<u8>) {
+        (
+            format!("{}/{}", LINUX_NODE_DIR, path),
+            contents.as_bytes().to_vec(),
+        )
+    }
+
+    #[test]
+    fn test_parse_node_meminfo() {
+        let meminfo = "Node 1 MemTotal:       65842344 kB\nNode 1 MemFree:        61234512 kB\n";
+
+        assert_eq!(parse_node_meminfo(meminfo), Some(65842344));
+        assert_eq!(parse_node_meminfo("Node 0 MemFree: 1 kB\n"), None);
+        assert_eq!(format_cpu_list(&[8, 0, 1, 2, 3, 10, 11]), "0-3+8+10-11");
+        assert_eq!(format_cpu_list(&[]), "");
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_memory_topology() {
+        let data = IdentifierTypeDataList::new(IdentifierType::MEMORY_TOPOLOGY);
+        let options = IdentifierOptions::default();
+
+        // A dual socket server with hyper-threading, listed out of order.
+        let provider = FakeProvider::new(FakeData {
+            files: [
+                file("node1/meminfo", "Node 1 MemTotal:       65842344 kB\n"),
+                file("node1/cpulist", "8-15,24-31\n"),
+                file("node0/meminfo", "Node 0 MemTotal:       65970368 kB\n"),
+                file("node0/cpulist", "0-7,16-23\n"),
+                file("possible", "0-1\n"),
+                file("has_cpu", "0-1\n"),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            data.build_with(&provider, &options),
+            "MEMORY_TOPOLOGY(n=0, m=65970368, c=0-7+16-23)MEMORY_TOPOLOGY(n=1, m=65842344, c=8-15+24-31)"
+        );
+        assert_eq!(
+            data.try_collect_with(&FakeProvider::default(), &options),
+            Err(UniqueIdError::SourceUnavailable(
+                IdentifierType::MEMORY_TOPOLOGY
+            ))
+        );
+    }
+}
@@ -0,0 +1,801 @@
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::{ContainerPolicy, DiskPolicy, IdentifierType, InterfacePolicy};
+
+/// The number of KB in a GiB, for use with `IdentifierOptions::ram_granularity`.
+pub const GIB: u64 = 1024 * 1024;
+
+/// Enum representing which core count is written into the CPU component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CoreCount {
+    /// The number of logical processors (threads).
+    #[default]
+    Logical,
+    /// The number of physical cores, falling back to the logical count if unknown.
+    Physical,
+    /// Both counts, as `pc` (physical, `unknown` if unknown) and `lc` (logical) instead of `c`.
+    Both,
+}
+
+/// Enum representing which data of each disk is written into the DISK component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiskDetail {
+    /// The total space only, reproducing the original DISK component.
+    #[default]
+    SizeOnly,
+    /// The model and serial number of the drive in addition to the total space.
+    Full,
+}
+
+/// Enum representing how the disks reported by the platform are written into the DISK component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiskGrouping {
+    /// One block per volume, reproducing the original DISK component.
+    #[default]
+    Volume,
+    /// One block per physical disk with the summed space of its volumes, so resizing partitions
+    /// does not change the Identifier. Volumes whose physical disk is unknown keep their own block.
+    PhysicalDisk,
+}
+
+/// Enum representing what the DISK component holds when no disk is eligible, e.g. on a machine
+/// booted from USB where every disk is removable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MissingDisks {
+    /// An explicit `DISK(none=1)` block, so the missing disks still change the Identifier.
+    #[default]
+    Marker,
+    /// No block, so `IdentifierTypeDataList::try_collect_with` and
+    /// `Identifier::build_with_optional_fallback` report the DISK component as unavailable.
+    Error,
+}
+
+/// Enum representing how the collected string values are normalized before they enter the
+/// Identifier. Each mode hashes under its own format version, see `FORMAT_VERSION`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StringNormalization {
+    /// The values as returned by each collector, reproducing the hashes of format version 2.
+    Legacy,
+    /// Trademark symbols stripped, NFKC applied and whitespace collapsed with `normalize_value`,
+    /// so padded or localized strings of the same hardware hash the same. Format version 3.
+    #[default]
+    Strict,
+}
+
+impl StringNormalization {
+    /// Returns the format version the canonical string is prefixed with in this mode.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{StringNormalization, FORMAT_VERSION};
+    ///
+    /// assert_eq!(StringNormalization::Legacy.format_version(), 2);
+    /// assert_eq!(StringNormalization::Strict.format_version(), FORMAT_VERSION);
+    /// ```
+    pub fn format_version(&self) -> u32 {
+        match self {
+            StringNormalization::Legacy => 2,
+            StringNormalization::Strict => 3,
+        }
+    }
+}
+
+/// The string normalization of options stored before it was recorded.
+#[cfg(feature = "serde")]
+fn legacy_string_normalization() -> StringNormalization {
+    StringNormalization::Legacy
+}
+
+/// A user-supplied function normalizing the raw CPU brand string.
+/// Two BrandNormalizers are equal if they share the same function.
+#[derive(Clone)]
+pub struct BrandNormalizer(Arc<dyn Fn(&str) -> String + Send + Sync>);
+
+impl BrandNormalizer {
+    /// Creates a new BrandNormalizer object.
+    pub fn new<F: Fn(&str) -> String + Send + Sync + 'static>(f: F) -> Self {
+        BrandNormalizer(Arc::new(f))
+    }
+
+    /// Applies the normalization function to a raw brand string.
+    pub fn normalize(&self, brand: &str) -> String {
+        (self.0)(brand)
+    }
+}
+
+impl Debug for BrandNormalizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "BrandNormalizer({:p})", Arc::as_ptr(&self.0))
+    }
+}
+
+impl PartialEq for BrandNormalizer {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for BrandNormalizer {}
+
+impl Hash for BrandNormalizer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (Arc::as_ptr(&self.0) as *const () as usize).hash(state);
+    }
+}
+
+/// A struct controlling which data the IdentifierType collectors emit.
+/// Apart from the normalization of the collected strings, see `StringNormalization`, the default
+/// options reproduce the original output of every collector.
+///
+/// New options are added in minor versions, so the struct is built from `Default::default()`,
+/// with the setters or by assigning fields.
+/// # Examples
+/// ```
+/// use uniqueid::{DiskDetail, IdentifierOptions, GIB};
+///
+/// let mut options = IdentifierOptions::default();
+/// options
+///     .include_cpu_frequency(false)
+///     .ram_granularity(Some(GIB))
+///     .disk_detail(DiskDetail::Full);
+/// options.disk_mount_info = true;
+///
+/// assert_eq!(options.ram_granularity, Some(GIB));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IdentifierOptions {
+    /// Whether the CPU frequency is included in the CPU component. (default: true)
+    pub include_cpu_frequency: bool,
+    /// The granularity in MHz the CPU frequency is rounded to, or None for the exact value. The
+    /// frequency follows the load on systems reporting the current frequency. (default: None)
+    pub cpu_frequency_granularity: Option<u64>,
+    /// Which core count is included in the CPU component. (default: Logical)
+    pub cpu_core_count: CoreCount,
+    /// Whether the L1 data, L1 instruction, L2 and L3 cache sizes are included in the CPU
+    /// component. (default: false)
+    pub include_cache_topology: bool,
+    /// The granularity in KB the total RAM is rounded to, or None for the exact value. The total is
+    /// rounded to the nearest multiple, halfway values up, so memory reserved by an integrated GPU
+    /// or the kernel does not change it. (default: None)
+    pub ram_granularity: Option<u64>,
+    /// Whether the manufacturer, part number, speed and serial number of every installed DIMM are
+    /// added to the RAM component. Requires the `smbios` feature. (default: false)
+    #[cfg(feature = "smbios")]
+    pub ram_dimm_details: bool,
+    /// Whether removable disks, such as USB drives, are included in the DISK component.
+    /// (default: false)
+    pub include_removable_disks: bool,
+    /// Whether only the disk holding the operating system is included in the DISK component. (default: false)
+    pub system_disk_only: bool,
+    /// The device path of the only disk included in the DISK component, or None. (default: None)
+    /// Partitions of the device are included, a partition mounted more than once is counted once.
+    pub disk_device_path: Option<String>,
+    /// Which data of each disk is included in the DISK component. (default: SizeOnly)
+    pub disk_detail: DiskDetail,
+    /// Whether the DISK component has one block per volume or per physical disk. (default: Volume)
+    pub disk_grouping: DiskGrouping,
+    /// What the DISK component holds when no disk is eligible. (default: Marker)
+    pub missing_disks: MissingDisks,
+    /// Whether the file system and the mount point of each disk are included in the DISK component,
+    /// to tell the root volume from data volumes. (default: false)
+    pub disk_mount_info: bool,
+    /// Whether the kind of drive (`hdd`, `ssd`, `nvme` or `unknown`) of each disk is included in
+    /// the DISK component. (default: false)
+    pub disk_kind: bool,
+    /// The policy selecting the disks included in the DISK component by their file system.
+    /// (default: local file systems only)
+    pub disk_policy: DiskPolicy,
+    /// The policy selecting the network interfaces included in the NET component. (default:
+    /// physical adapters with a globally unique MAC address)
+    pub net_policy: InterfacePolicy,
+    /// The function applied to the raw CPU brand, whose result is used as-is without the
+    /// `string_normalization`, or None to lowercase and trim it. (default: None)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub cpu_brand_normalization: Option<BrandNormalizer>,
+    /// How every collected string value is normalized. (default: Strict, Legacy when deserializing
+    /// options stored without it, which were collected before the normalization was added)
+    #[cfg_attr(feature = "serde", serde(default = "legacy_string_normalization"))]
+    pub string_normalization: StringNormalization,
+    /// The install GUID of the application included (hashed) in the APP component, or None. (default: None)
+    pub app_install_id: Option<String>,
+    /// Whether an empty `BIOS()` block is collected instead of nothing when the firmware is not
+    /// readable, such as inside containers. (default: false)
+    pub empty_bios_when_unavailable: bool,
+    /// Whether a `BATTERY(none=1)` block is collected instead of nothing on systems without a
+    /// battery. (default: false)
+    pub battery_placeholder: bool,
+    /// Whether the CHASSIS_SERIAL component is collected. The serial number is sensitive, so the
+    /// component is empty unless opted in. (default: false)
+    pub include_chassis_serial: bool,
+    /// Whether a SYSUUID shared by many machines, like all zeros, is left out instead of being
+    /// flagged with `bogus=1`. (default: false)
+    pub exclude_bogus_sysuuid: bool,
+    /// How running inside a container is handled. (default: ContainerPolicy::Ignore)
+    pub container_policy: ContainerPolicy,
+    /// The time the CLOUD component may spend querying the metadata services. (default: 1 second)
+    #[cfg(feature = "cloud")]
+    pub cloud_timeout: std::time::Duration,
+}
+
+impl Default for IdentifierOptions {
+    fn default() -> Self {
+        IdentifierOptions {
+            include_cpu_frequency: true,
+            cpu_frequency_granularity: None,
+            cpu_core_count: CoreCount::Logical,
+            include_cache_topology: false,
+            ram_granularity: None,
+            #[cfg(feature = "smbios")]
+            ram_dimm_details: false,
+            include_removable_disks: false,
+            system_disk_only: false,
+            disk_device_path: None,
+            disk_detail: DiskDetail::SizeOnly,
+            disk_grouping: DiskGrouping::Volume,
+            missing_disks: MissingDisks::Marker,
+            disk_mount_info: false,
+            disk_kind: false,
+            disk_policy: DiskPolicy::default(),
+            net_policy: InterfacePolicy::default(),
+            cpu_brand_normalization: None,
+            string_normalization: StringNormalization::Strict,
+            app_install_id: None,
+            empty_bios_when_unavailable: false,
+            battery_placeholder: false,
+            include_chassis_serial: false,
+            exclude_bogus_sysuuid: false,
+            container_policy: ContainerPolicy::Ignore,
+            #[cfg(feature = "cloud")]
+            cloud_timeout: std::time::Duration::from_secs(1),
+        }
+    }
+}
+
+impl IdentifierOptions {
+    /// Sets whether the CPU frequency is included in the CPU component.
+    pub fn include_cpu_frequency(&mut self, include: bool) -> &mut Self {
+        self.include_cpu_frequency = include;
+        self
+    }
+
+    /// Sets the granularity in MHz the CPU frequency is rounded to, or None for the exact value.
+    pub fn cpu_frequency_granularity(&mut self, granularity: Option<u64>) -> &mut Self {
+        self.cpu_frequency_granularity = granularity;
+        self
+    }
+
+    /// Sets which core count is included in the CPU component.
+    pub fn cpu_core_count(&mut self, count: CoreCount) -> &mut Self {
+        self.cpu_core_count = count;
+        self
+    }
+
+    /// Sets whether the cache sizes are included in the CPU component.
+    pub fn include_cache_topology(&mut self, include: bool) -> &mut Self {
+        self.include_cache_topology = include;
+        self
+    }
+
+    /// Sets the granularity in KB the total RAM is rounded to, or None for the exact value.
+    pub fn ram_granularity(&mut self, granularity: Option<u64>) -> &mut Self {
+        self.ram_granularity = granularity;
+        self
+    }
+
+    /// Sets whether the details of every installed DIMM are added to the RAM component.
+    #[cfg(feature = "smbios")]
+    pub fn ram_dimm_details(&mut self, include: bool) -> &mut Self {
+        self.ram_dimm_details = include;
+        self
+    }
+
+    /// Sets whether removable disks are included in the DISK component.
+    pub fn include_removable_disks(&mut self, include: bool) -> &mut Self {
+        self.include_removable_disks = include;
+        self
+    }
+
+    /// Sets whether only the disk holding the operating system is included in the DISK component.
+    pub fn system_disk_only(&mut self, only: bool) -> &mut Self {
+        self.system_disk_only = only;
+        self
+    }
+
+    /// Sets the device path of the only disk included in the DISK component, or None.
+    pub fn disk_device_path(&mut self, path: Option<String>) -> &mut Self {
+        self.disk_device_path = path;
+        self
+    }
+
+    /// Sets which data of each disk is included in the DISK component.
+    pub fn disk_detail(&mut self, detail: DiskDetail) -> &mut Self {
+        self.disk_detail = detail;
+        self
+    }
+
+    /// Sets whether the DISK component has one block per volume or per physical disk.
+    pub fn disk_grouping(&mut self, grouping: DiskGrouping) -> &mut Self {
+        self.disk_grouping = grouping;
+        self
+    }
+
+    /// Sets what the DISK component holds when no disk is eligible.
+    pub fn missing_disks(&mut self, missing: MissingDisks) -> &mut Self {
+        self.missing_disks = missing;
+        self
+    }
+
+    /// Sets whether the file system and the mount point of each disk are included.
+    pub fn disk_mount_info(&mut self, include: bool) -> &mut Self {
+        self.disk_mount_info = include;
+        self
+    }
+
+    /// Sets whether the kind of drive of each disk is included in the DISK component.
+    pub fn disk_kind(&mut self, include: bool) -> &mut Self {
+        self.disk_kind = include;
+        self
+    }
+
+    /// Sets the policy selecting the disks included in the DISK component.
+    pub fn disk_policy(&mut self, policy: DiskPolicy) -> &mut Self {
+        self.disk_policy = policy;
+        self
+    }
+
+    /// Sets the policy selecting the network interfaces included in the NET component.
+    pub fn net_policy(&mut self, policy: InterfacePolicy) -> &mut Self {
+        self.net_policy = policy;
+        self
+    }
+
+    /// Sets the function applied to the raw CPU brand, or None to lowercase and trim it.
+    pub fn cpu_brand_normalization(&mut self, normalizer: Option<BrandNormalizer>) -> &mut Self {
+        self.cpu_brand_normalization = normalizer;
+        self
+    }
+
+    /// Sets how every collected string value is normalized.
+    pub fn string_normalization(&mut self, normalization: StringNormalization) -> &mut Self {
+        self.string_normalization = normalization;
+        self
+    }
+
+    /// Sets the install GUID of the application included in the APP component, or None.
+    pub fn app_install_id(&mut self, id: Option<String>) -> &mut Self {
+        self.app_install_id = id;
+        self
+    }
+
+    /// Sets whether an empty `BIOS()` block is collected when the firmware is not readable.
+    pub fn empty_bios_when_unavailable(&mut self, empty: bool) -> &mut Self {
+        self.empty_bios_when_unavailable = empty;
+        self
+    }
+
+    /// Sets whether a `BATTERY(none=1)` block is collected on systems without a battery.
+    pub fn battery_placeholder(&mut self, placeholder: bool) -> &mut Self {
+        self.battery_placeholder = placeholder;
+        self
+    }
+
+    /// Sets whether the CHASSIS_SERIAL component is collected.
+    pub fn include_chassis_serial(&mut self, include: bool) -> &mut Self {
+        self.include_chassis_serial = include;
+        self
+    }
+
+    /// Sets whether a SYSUUID shared by many machines is left out instead of being flagged.
+    pub fn exclude_bogus_sysuuid(&mut self, exclude: bool) -> &mut Self {
+        self.exclude_bogus_sysuuid = exclude;
+        self
+    }
+
+    /// Sets how running inside a container is handled.
+    pub fn container_policy(&mut self, policy: ContainerPolicy) -> &mut Self {
+        self.container_policy = policy;
+        self
+    }
+
+    /// Sets the time the CLOUD component may spend querying the metadata services.
+    #[cfg(feature = "cloud")]
+    pub fn cloud_timeout(&mut self, timeout: std::time::Duration) -> &mut Self {
+        self.cloud_timeout = timeout;
+        self
+    }
+}
+
+/// Enum representing the built-in collection presets.
+///
+/// The contents of each profile are frozen, upgrading the crate does not change a profile's hash.
+/// Every profile keeps `StringNormalization::Legacy` for that reason.
+///
+/// | Profile  | Components            | CPU frequency | Cores    | RAM          | Disks            |
+/// |----------|-----------------------|---------------|----------|--------------|------------------|
+/// | Strict   | CPU, RAM, DISK        | exact         | logical  | exact        | all non-removable|
+/// | Balanced | CPU, RAM, DISK        | excluded      | logical  | rounded (GiB)| system disk only |
+/// | Relaxed  | CPU, MACHINE, SYSUUID | excluded      | physical | -            | -                |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// CPU, RAM and every non-removable disk, with exact values.
+    Strict,
+    /// No CPU frequency, RAM rounded to GiB, system disk only.
+    Balanced,
+    /// CPU brand, vendor and physical cores, and the machine ID and system UUID.
+    Relaxed,
+}
+
+impl Profile {
+    /// Returns the IdentifierTypes included by the profile.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierType, Profile};
+    ///
+    /// assert_eq!(
+    ///     Profile::Relaxed.identifier_types(),
+    ///     vec![IdentifierType::CPU, IdentifierType::MACHINE, IdentifierType::SYSUUID]
+    /// );
+    /// ```
+    pub fn identifier_types(&self) -> Vec<IdentifierType> {
+        match self {
+            Profile::Strict | Profile::Balanced => vec![
+                IdentifierType::CPU,
+                IdentifierType::RAM,
+                IdentifierType::DISK,
+            ],
+            Profile::Relaxed => vec![
+                IdentifierType::CPU,
+                IdentifierType::MACHINE,
+                IdentifierType::SYSUUID,
+            ],
+        }
+    }
+
+    /// Returns the IdentifierOptions used by the profile.
+    /// # Examples
+    /// ```
+    /// use uniqueid::Profile;
+    ///
+    /// assert!(!Profile::Balanced.options().include_cpu_frequency);
+    /// ```
+    pub fn options(&self) -> IdentifierOptions {
+        let frozen = Profile::frozen_options();
+
+        match self {
+            Profile::Strict => frozen,
+            Profile::Balanced => IdentifierOptions {
+                include_cpu_frequency: false,
+                ram_granularity: Some(GIB),
+                system_disk_only: true,
+                ..frozen
+            },
+            Profile::Relaxed => IdentifierOptions {
+                include_cpu_frequency: false,
+                cpu_core_count: CoreCount::Physical,
+                ..frozen
+            },
+        }
+    }
+
+    /// Returns the options shared by every profile. Every field is listed, so an option added to
+    /// IdentifierOptions must be given a frozen value here instead of following its default.
+    fn frozen_options() -> IdentifierOptions {
+        IdentifierOptions {
+            include_cpu_frequency: true,
+            cpu_frequency_granularity: None,
+            cpu_core_count: CoreCount::Logical,
+            include_cache_topology: false,
+            ram_granularity: None,
+            #[cfg(feature = "smbios")]
+            ram_dimm_details: false,
+            include_removable_disks: false,
+            system_disk_only: false,
+            disk_device_path: None,
+            disk_detail: DiskDetail::SizeOnly,
+            disk_grouping: DiskGrouping::Volume,
+            missing_disks: MissingDisks::Marker,
+            disk_mount_info: false,
+            disk_kind: false,
+            disk_policy: DiskPolicy::default(),
+            net_policy: InterfacePolicy::default(),
+            cpu_brand_normalization: None,
+            string_normalization: StringNormalization::Legacy,
+            app_install_id: None,
+            empty_bios_when_unavailable: false,
+            battery_placeholder: false,
+            include_chassis_serial: false,
+            exclude_bogus_sysuuid: false,
+            container_policy: ContainerPolicy::Ignore,
+            #[cfg(feature = "cloud")]
+            cloud_timeout: std::time::Duration::from_secs(1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DiskInfo, FakeData, FakeProvider, IdentifierBuilder, IdentifierTypeDataList, ProcessorInfo,
+    };
+
+    fn platform(mounts: &[&str]) -> FakeProvider {
+        let processor = ProcessorInfo {
+            brand: "Intel(R) Core(TM) i7-8750H CPU @ 2.20GHz".to_string(),
+            vendor: "GenuineIntel".to_string(),
+            frequency: 2208,
+        };
+
+        FakeProvider::new(FakeData {
+            processors: vec![processor; 12],
+            physical_core_count: Some(6),
+            total_memory: 16_306_520,
+            disks: mounts
+                .iter()
+                .enumerate()
+                .map(|(i, mount)| DiskInfo {
+                    mount_point: mount.to_string(),
+                    total_space: 500_000_000_000 * (i as u64 + 1),
+                    removable: false,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    fn build(profile: Profile, provider: &FakeProvider) -> String {
+        let mut builder = IdentifierBuilder::default();
+        builder.name("golden");
+        builder.profile(profile);
+
+        builder.build().to_string_with(provider, false)
+    }
+
+    #[test]
+    fn test_identifier_options() {
+        let provider = platform(&["/", "/home"]);
+        provider.update(|data| {
+            data.disks.push(DiskInfo {
+                device: "/dev/sdc1".to_string(),
+                mount_point: "/media/usb".to_string(),
+                total_space: 64_000_000_000,
+                removable: true,
+                ..Default::default()
+            })
+        });
+
+        let build = |options: &IdentifierOptions| {
+            let mut builder = IdentifierBuilder::default();
+            builder.add(IdentifierType::CPU);
+            builder.add(IdentifierType::RAM);
+            builder.add(IdentifierType::DISK);
+            builder.options(options.clone());
+            builder.build().to_string_with(&provider, false)
+        };
+
+        let mut stable = IdentifierOptions::default();
+        stable
+            .include_cpu_frequency(false)
+            .ram_granularity(Some(GIB));
+        let mut removable = IdentifierOptions::default();
+        removable.include_removable_disks(true);
+
+        let outputs = [
+            build(&IdentifierOptions::default()),
+            build(&stable),
+            build(&removable),
+        ];
+
+        assert_eq!(
+            outputs,
+            [
+                "[CPU(b=intel core i7-8750h cpu @ 2.20ghz, v=genuineintel, f=2208, c=12), \
+                 RAM(t=16306520), DISK(t=500000000000)DISK(t=1000000000000)]",
+                "[CPU(b=intel core i7-8750h cpu @ 2.20ghz, v=genuineintel, c=12), \
+                 RAM(t=16777216), DISK(t=500000000000)DISK(t=1000000000000)]",
+                "[CPU(b=intel core i7-8750h cpu @ 2.20ghz, v=genuineintel, f=2208, c=12), \
+                 RAM(t=16306520), DISK(t=64000000000)DISK(t=500000000000)DISK(t=1000000000000)]",
+            ]
+        );
+        assert_eq!(build(&stable), outputs[1]);
+    }
+
+    #[test]
+    fn test_setters() {
+        let mut options = IdentifierOptions::default();
+        options
+            .cpu_core_count(CoreCount::Physical)
+            .disk_kind(true)
+            .disk_mount_info(true)
+            .disk_grouping(DiskGrouping::PhysicalDisk)
+            .app_install_id(Some("3f2a".to_string()))
+            .string_normalization(StringNormalization::Legacy);
+
+        assert_eq!(
+            options,
+            IdentifierOptions {
+                cpu_core_count: CoreCount::Physical,
+                disk_kind: true,
+                disk_mount_info: true,
+                disk_grouping: DiskGrouping::PhysicalDisk,
+                app_install_id: Some("3f2a".to_string()),
+                string_normalization: StringNormalization::Legacy,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_ram_granularity() {
+        let provider = |total_memory: u64| {
+            FakeProvider::new(FakeData {
+                total_memory,
+                ..Default::default()
+            })
+        };
+        let build = |granularity: Option<u64>, total_memory: u64| {
+            let mut builder = IdentifierBuilder::default();
+            builder.add(IdentifierType::RAM);
+            builder.ram_granularity(granularity);
+            builder
+                .build()
+                .to_string_with(&provider(total_memory), true)
+        };
+
+        // 16 GiB, with 128 MB more reserved by the integrated GPU after a firmware update.
+        let before = 16 * GIB - 262_144;
+        let after = 16 * GIB - 393_216;
+
+        assert_ne!(build(None, before), build(None, after));
+        assert_eq!(build(Some(GIB), before), build(Some(GIB), after));
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::RAM).build_with(
+                &provider(after),
+                &IdentifierOptions {
+                    ram_granularity: Some(GIB),
+                    ..Default::default()
+                }
+            ),
+            "RAM(t=16777216)"
+        );
+
+        // Halfway values are rounded up, a granularity of 0 keeps the exact value.
+        assert_eq!(
+            build(Some(GIB), 15 * GIB + GIB / 2),
+            build(Some(GIB), 16 * GIB)
+        );
+        assert_eq!(build(Some(0), before), build(None, before));
+    }
+
+    #[test]
+    fn test_cpu_frequency_granularity() {
+        let provider = |frequency: u64| {
+            FakeProvider::new(FakeData {
+                processors: vec![ProcessorInfo {
+                    brand: "AMD Ryzen 7 5800X".to_string(),
+                    vendor: "AuthenticAMD".to_string(),
+                    frequency,
+                }],
+                ..Default::default()
+            })
+        };
+        let build = |options: &IdentifierOptions, frequency: u64| {
+            IdentifierTypeDataList::new(IdentifierType::CPU)
+                .build_with(&provider(frequency), options)
+        };
+
+        let rounded = IdentifierOptions {
+            cpu_frequency_granularity: Some(100),
+            ..Default::default()
+        };
+        let excluded = IdentifierOptions {
+            include_cpu_frequency: false,
+            ..Default::default()
+        };
+
+        // The frequency scaled by the governor under different loads.
+        assert_ne!(
+            build(&IdentifierOptions::default(), 3793),
+            build(&IdentifierOptions::default(), 3812)
+        );
+        assert_eq!(build(&rounded, 3793), build(&rounded, 3812));
+        assert!(build(&rounded, 3793).contains("f=3800"));
+        assert_eq!(build(&excluded, 2200), build(&excluded, 4700));
+        assert!(!build(&excluded, 2200).contains("f="));
+    }
+
+    #[test]
+    fn test_profiles_linux() {
+        let provider = platform(&["/home", "/"]);
+
+        assert_eq!(
+            build(Profile::Strict, &provider),
+            "golden[CPU(b=intel(r) core(tm) i7-8750h cpu @ 2.20ghz, v=genuineintel, f=2208, c=12), \
+             RAM(t=16306520), DISK(t=500000000000)DISK(t=1000000000000)]"
+        );
+        assert_eq!(
+            build(Profile::Balanced, &provider),
+            "golden[CPU(b=intel(r) core(tm) i7-8750h cpu @ 2.20ghz, v=genuineintel, c=12), \
+             RAM(t=16777216), DISK(t=1000000000000)]"
+        );
+        assert_eq!(
+            build(Profile::Relaxed, &provider),
+            "golden[CPU(b=intel(r) core(tm) i7-8750h cpu @ 2.20ghz, v=genuineintel, c=6)]"
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_relaxed_profile_machine_ids() {
+        let provider = platform(&["/"]);
+        provider.update(|data| {
+            data.files.insert(
+                "/etc/machine-id".to_string(),
+                b"3d1219c7c4c5404aaa1f6d2a48adfda4\n".to_vec(),
+            );
+            data.files.insert(
+                "/sys/class/dmi/id/product_uuid".to_string(),
+                b"4C4C4544-0042-3510-8052-B4C04F564E32\n".to_vec(),
+            );
+        });
+
+        assert_eq!(
+            build(Profile::Relaxed, &provider),
+            "golden[CPU(b=intel(r) core(tm) i7-8750h cpu @ 2.20ghz, v=genuineintel, c=6), \
+             MACHINE(id=3d1219c7c4c5404aaa1f6d2a48adfda4), \
+             SYSUUID(id=4c4c4544-0042-3510-8052-b4c04f564e32)]"
+        );
+    }
+
+    #[test]
+    fn test_profiles_windows() {
+        let provider = platform(&["C:\\", "D:\\"]);
+
+        assert_eq!(
+            build(Profile::Strict, &provider),
+            "golden[CPU(b=intel(r) core(tm) i7-8750h cpu @ 2.20ghz, v=genuineintel, f=2208, c=12), \
+             RAM(t=16306520), DISK(t=500000000000)DISK(t=1000000000000)]"
+        );
+        assert_eq!(
+            build(Profile::Balanced, &provider),
+            "golden[CPU(b=intel(r) core(tm) i7-8750h cpu @ 2.20ghz, v=genuineintel, c=12), \
+             RAM(t=16777216), DISK(t=500000000000)]"
+        );
+        assert_eq!(
+            build(Profile::Relaxed, &provider),
+            "golden[CPU(b=intel(r) core(tm) i7-8750h cpu @ 2.20ghz, v=genuineintel, c=6)]"
+        );
+    }
+
+    #[test]
+    fn test_profile_hash_is_frozen() {
+        let provider = platform(&["/"]);
+
+        let mut builder = IdentifierBuilder::default();
+        builder.name("golden");
+        builder.profile(Profile::Relaxed);
+
+        let identifier = builder.build();
+
+        assert_eq!(
+            identifier.to_string_with(&provider, true),
+            "41a2185ffc0f67224ffc187b764927417e92111d83e27333356349013614bbbf\
+             e6b19776c9e5c591d37881b7cc7d523425c8884dca5c5d0be622e6b2b4400110"
+        );
+        assert_eq!(
+            identifier.to_string_v1_with(&provider, true),
+            "f4dd85c17e1afc13c75bdf72f86f023f3de2b23c9db7df82f6393691cac4ac12\
+             7c836cb048cd911ac8d07dd6ad2d567510d601976e48ea76ef5e7132e8aa7f28"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use sha2::{Digest, Sha256};
+
+use crate::hash::to_hex;
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// Parses the installed packages listed by `dpkg --list`, as `name version architecture`.
+/// Only packages in the installed state (`ii`) are listed, the header and removed packages whose
+/// configuration is left (`rc`) are skipped.
+fn parse_dpkg_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            if fields.next()? != "ii" {
+                return None;
+            }
+
+            Some(fields.take(3).collect::<Vec<_>>().join(" "))
+        })
+        .collect()
+}
+
+/// Parses a list of packages printed one per line, as by `rpm -qa` and `pkgutil --pkgs`.
+fn parse_package_lines(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Lists the installed OS packages with the package manager of the platform, or None if none of
+/// them is available.
+fn installed_packages(provider: &dyn HardwareProvider) -> Option<Vec<String>> {
+    let packages = if cfg!(target_os = "linux") {
+        provider
+            .run_command("dpkg", &["--list"])
+            .map(|output| parse_dpkg_list(&output))
+            .or_else(|| {
+                provider
+                    .run_command("rpm", &["-qa"])
+                    .map(|output| parse_package_lines(&output))
+            })
+    } else if cfg!(target_os = "macos") {
+        provider
+            .run_command("pkgutil", &["--pkgs"])
+            .map(|output| parse_package_lines(&output))
+    } else {
+        None
+    };
+
+    packages.filter(|packages| !packages.is_empty())
+}
+
+/// Hashes the package list with SHA-256, sorted so the order of the package manager does not
+/// matter.
+fn hash_packages(mut packages: Vec<String>) -> String {
+    packages.sort();
+    packages.dedup();
+
+    to_hex(&Sha256::digest(packages.join("\n").as_bytes()))
+}
+
+impl IdentifierTypeDataList {
+    /// Collects a hash of the installed OS packages, or nothing without a known package manager.
+    pub(crate) fn collect_installed_packages_hash(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        match installed_packages(provider) {
+            Some(packages) => {
+                let mut block =
+                    IdentifierTypeDataList::new(IdentifierType::INSTALLED_PACKAGES_HASH);
+                block.push("h", &hash_packages(packages));
+                vec![block]
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierOptions};
+
+    const DPKG_LIST: &str = "\
+Desired=Unknown/Install/Remove/Purge/Hold
+| Status=Not/Inst/Conf-files/Unpacked/halF-conf/Half-inst/trig-aWait/Trig-pend
+|/ Err?=(none)/Reinst-required (Status,Err: uppercase=bad)
+||/ Name           Version        Architecture Description
++++-==============-==============-============-=================================
+ii  adduser        3.134          all          add and remove users and groups
+rc  apache2        2.4.57-2       amd64        Apache HTTP Server
+ii  bash           5.2.15-2+b7    amd64        GNU Bourne Again SHell
+";
+
+    #[test]
+    fn test_parse_packages() {
+        assert_eq!(
+            parse_dpkg_list(DPKG_LIST),
+            vec!["adduser 3.134 all", "bash 5.2.15-2+b7 amd64"]
+        );
+        assert_eq!(
+            parse_package_lines("bash-5.2.26-3.fc40.x86_64\n\nzlib-1.3.1-1.fc40.x86_64\n"),
+            vec!["bash-5.2.26-3.fc40.x86_64", "zlib-1.3.1-1.fc40.x86_64"]
+        );
+        assert_eq!(
+            hash_packages(vec!["b".to_string(), "a".to_string()]),
+            hash_packages(vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(
+            hash_packages(vec!["a".to_string(), "b".to_string()]),
+            to_hex(&Sha256::digest(b"a\nb"))
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_installed_packages_hash() {
+        let data = IdentifierTypeDataList::new(IdentifierType::INSTALLED_PACKAGES_HASH);
+        let options = IdentifierOptions::default();
+        let expected = to_hex(&Sha256::digest(
+            b"adduser 3.134 all\nbash 5.2.15-2+b7 amd64",
+        ));
+
+        let provider = FakeProvider::new(FakeData {
+            commands: [("dpkg --list".to_string(), DPKG_LIST.to_string())].into(),
+            ..Default::default()
+        });
+        assert_eq!(
+            data.build_with(&provider, &options),
+            format!("INSTALLED_PACKAGES_HASH(h={})", expected)
+        );
+
+        // RPM based distributions.
+        let provider = FakeProvider::new(FakeData {
+            commands: [(
+                "rpm -qa".to_string(),
+                "zlib-1.3.1-1.fc40.x86_64\nbash-5.2.26-3.fc40.x86_64\n".to_string(),
+            )]
+            .into(),
+            ..Default::default()
+        });
+        assert_eq!(
+            data.build_with(&provider, &options),
+            format!(
+                "INSTALLED_PACKAGES_HASH(h={})",
+                to_hex(&Sha256::digest(
+                    b"bash-5.2.26-3.fc40.x86_64\nzlib-1.3.1-1.fc40.x86_64"
+                ))
+            )
+        );
+
+        assert!(data
+            .collect_with(&FakeProvider::default(), &options)
+            .is_empty());
+    }
+}
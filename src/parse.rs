@@ -0,0 +1,385 @@
+use std::str::FromStr;
+
+use crate::{
+    Identifier, IdentifierType, IdentifierTypeData, IdentifierTypeDataList, UniqueIdError,
+};
+
+/// The characters of the `TYPE(key=value, ...)` format escaped in keys and values.
+const RESERVED: [char; 5] = ['%', ',', '(', ')', '='];
+
+/// The characters escaped in the name of an Identifier, which also delimits the components.
+const NAME_RESERVED: [char; 7] = ['%', ',', '(', ')', '=', '[', ']'];
+
+/// Returns true if every parenthesis of the value is closed, in order.
+fn is_balanced(value: &str) -> bool {
+    let mut depth = 0usize;
+
+    for c in value.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => match depth.checked_sub(1) {
+                Some(next) => depth = next,
+                None => return false,
+            },
+            _ => {}
+        }
+    }
+
+    depth == 0
+}
+
+/// Percent-encodes the reserved characters of the value, keeping balanced parentheses.
+fn escape(value: &str, reserved: &[char]) -> String {
+    let balanced = is_balanced(value);
+    let mut result = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        let kept = balanced && (c == '(' || c == ')');
+
+        if reserved.contains(&c) && !kept {
+            result.push_str(&format!("%{:02X}", c as u32));
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Percent-encodes the characters of a key or value that would corrupt the `key=value, ...`
+/// format. (e.g. /mnt/a=b, c -> /mnt/a%3Db%2C c)
+///
+/// Balanced parentheses are kept, as in CPU brands (intel(r) core(tm)), since the parser tracks
+/// their depth. Every `%` is encoded, so the encoding is reversible and two different values can
+/// never render to the same string.
+pub(crate) fn escape_value(value: &str) -> String {
+    escape(value, &RESERVED)
+}
+
+/// Percent-encodes the characters of the name of an Identifier that would corrupt the format,
+/// including the brackets around the components.
+pub(crate) fn escape_name(name: &str) -> String {
+    escape(name, &NAME_RESERVED)
+}
+
+/// Decodes the percent-encoded characters of a value escaped by `escape_value`. Sequences that are
+/// not a valid encoding are kept as-is.
+pub(crate) fn unescape_value(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(index) = rest.find('%') {
+        result.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        let decoded = rest
+            .get(1..3)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .map(char::from)
+            .filter(|c| NAME_RESERVED.contains(c));
+
+        match decoded {
+            Some(c) => {
+                result.push(c);
+                rest = &rest[3..];
+            }
+            None => {
+                result.push('%');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Splits the string on `, ` separators that are not inside parentheses.
+fn split_top_level(input: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let bytes = input.as_bytes();
+
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 && bytes.get(i + 1) == Some(&b' ') => {
+                result.push(&input[start..i]);
+                start = i + 2;
+            }
+            _ => {}
+        }
+    }
+
+    result.push(&input[start..]);
+    result
+}
+
+/// Splits the body of an Identifier into its blocks. Blocks may be separated by `, ` or written
+/// back to back, as done for components with several blocks (e.g. one per disk).
+fn split_blocks(body: &str) -> Result<Vec<&str>, UniqueIdError> {
+    let mut result = Vec::new();
+    let mut rest = body;
+
+    while !rest.is_empty() {
+        let open = rest
+            .find('(')
+            .ok_or_else(|| UniqueIdError::Parse(format!("missing '(' in {}", rest)))?;
+
+        let mut depth = 0usize;
+        let mut end = None;
+        for (i, c) in rest[open..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth = depth.checked_sub(1).ok_or_else(|| {
+                        UniqueIdError::Parse(format!("unbalanced ')' in {}", rest))
+                    })?;
+                    if depth == 0 {
+                        let candidate = open + i + 1;
+                        let next = &rest[candidate..];
+                        // A block ends at a closing parenthesis followed by a new block or the end.
+                        if next.is_empty() || next.starts_with(", ") || starts_with_tag(next) {
+                            end = Some(candidate);
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let end = end.ok_or_else(|| UniqueIdError::Parse(format!("unclosed block {}", rest)))?;
+        result.push(&rest[..end]);
+        rest = rest[end..].strip_prefix(", ").unwrap_or(&rest[end..]);
+    }
+
+    Ok(result)
+}
+
+/// Returns true if the string starts with an IdentifierType tag followed by `(`.
+fn starts_with_tag(input: &str) -> bool {
+    match input.find('(') {
+        Some(open) => input[..open].parse::<IdentifierType>().is_ok(),
+        None => false,
+    }
+}
+
+impl FromStr for IdentifierTypeDataList {
+    type Err = UniqueIdError;
+
+    /// Parses a single block in the `TYPE(key=value, ...)` format.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierType, IdentifierTypeDataList};
+    ///
+    /// let list: IdentifierTypeDataList = "RAM(t=1024)".parse().unwrap();
+    ///
+    /// assert_eq!(list.identifier, IdentifierType::RAM);
+    /// assert_eq!(list.data[0].value, "1024");
+    /// ```
+    fn from_str(block: &str) -> Result<Self, Self::Err> {
+        let open = block
+            .find('(')
+            .ok_or_else(|| UniqueIdError::Parse(format!("missing '(' in {}", block)))?;
+        let inner = block[open + 1..]
+            .strip_suffix(')')
+            .ok_or_else(|| UniqueIdError::Parse(format!("missing ')' in {}", block)))?;
+
+        let mut list = IdentifierTypeDataList::new(block[..open].parse()?);
+
+        if inner.is_empty() {
+            return Ok(list);
+        }
+
+        for pair in split_top_level(inner) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| UniqueIdError::Parse(format!("missing '=' in {}", pair)))?;
+            list.data.push(IdentifierTypeData::new(
+                &unescape_value(key),
+                &unescape_value(value),
+            ));
+        }
+
+        Ok(list)
+    }
+}
+
+impl FromStr for Identifier {
+    type Err = UniqueIdError;
+
+    /// Parses an unhashed Identifier in the `NAME[TYPE(key=value, ...), ...]` format.
+    /// Every block is parsed into its own IdentifierTypeDataList with populated data.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{Identifier, IdentifierType};
+    ///
+    /// let identifier: Identifier = "test[CPU(b=intel(r) core(tm), c=8), RAM(t=1024)]".parse().unwrap();
+    ///
+    /// assert_eq!(identifier.name, Some("test".to_string()));
+    /// assert_eq!(identifier.data[0].data[0].value, "intel(r) core(tm)");
+    /// assert_eq!(identifier.data[1].identifier, IdentifierType::RAM);
+    /// ```
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let open = input
+            .find('[')
+            .ok_or_else(|| UniqueIdError::Parse("missing '['".to_string()))?;
+        let body = input[open + 1..]
+            .strip_suffix(']')
+            .ok_or_else(|| UniqueIdError::Parse("missing ']'".to_string()))?;
+
+        let name = &input[..open];
+
+        Ok(Identifier {
+            name: (!name.is_empty()).then(|| unescape_value(name)),
+            data: split_blocks(body)?
+                .into_iter()
+                .map(str::parse)
+                .collect::<Result<_, _>>()?,
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FakeProvider;
+
+    fn list(identifier: IdentifierType, pairs: &[(&str, &str)]) -> IdentifierTypeDataList {
+        let mut list = IdentifierTypeDataList::new(identifier);
+        for (key, value) in pairs {
+            list.push(*key, *value);
+        }
+        list
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let input = "test[CPU(b=intel(r) core(tm) i7, v=genuineintel, f=2208, c=12), RAM(t=1024), \
+                     DONGLE(s=abc, v=2)]";
+        let identifier: Identifier = input.parse().unwrap();
+
+        assert_eq!(identifier.data.len(), 3);
+        assert_eq!(
+            identifier.data[2].identifier,
+            IdentifierType::Custom("DONGLE".to_string())
+        );
+        assert_eq!(identifier.to_string(false), input);
+    }
+
+    #[test]
+    fn test_parse_back_to_back_blocks() {
+        let identifier: Identifier = "[DISK(t=1)DISK(t=2), RAM(t=3)]".parse().unwrap();
+
+        assert_eq!(identifier.name, None);
+        assert_eq!(identifier.data.len(), 3);
+        assert_eq!(identifier.data[1].data[0].value, "2");
+    }
+
+    #[test]
+    fn test_escape_value() {
+        assert_eq!(escape_value("/mnt/a=b (old)"), "/mnt/a%3Db (old)");
+        assert_eq!(escape_value("C:\\100%, final"), "C:\\100%25%2C final");
+        assert_eq!(escape_value("intel(r) core(tm)"), "intel(r) core(tm)");
+        assert_eq!(escape_value("a) (b"), "a%29 %28b");
+        assert_eq!(escape_value("[x]"), "[x]");
+        assert_eq!(escape_name("a[1]"), "a%5B1%5D");
+
+        for value in [
+            "/mnt/a=b (old)",
+            "C:\\100%, final",
+            "a) (b",
+            "%zz%2",
+            "[x]",
+            "",
+        ] {
+            assert_eq!(unescape_value(&escape_value(value)), value);
+            assert_eq!(unescape_value(&escape_name(value)), value);
+        }
+    }
+
+    #[test]
+    fn test_escaped_structure() {
+        let one = Identifier {
+            name: Some("test".to_string()),
+            data: vec![list(IdentifierType::CPU, &[("b", "a=b, c=d")])],
+            ..Default::default()
+        };
+        let two = Identifier {
+            name: Some("test".to_string()),
+            data: vec![list(IdentifierType::CPU, &[("b", "a"), ("c", "d")])],
+            ..Default::default()
+        };
+        // A value closing its block and opening a new one.
+        let injected = Identifier {
+            name: Some("test".to_string()),
+            data: vec![list(IdentifierType::CPU, &[("b", "x), RAM(t=1")])],
+            ..Default::default()
+        };
+        let split = Identifier {
+            name: Some("test".to_string()),
+            data: vec![
+                list(IdentifierType::CPU, &[("b", "x")]),
+                list(IdentifierType::RAM, &[("t", "1")]),
+            ],
+            ..Default::default()
+        };
+        let provider = FakeProvider::default();
+
+        for (left, right) in [(&one, &two), (&injected, &split)] {
+            assert_ne!(
+                left.to_string_with(&provider, false),
+                right.to_string_with(&provider, false)
+            );
+            assert_ne!(
+                left.to_string_with(&provider, true),
+                right.to_string_with(&provider, true)
+            );
+        }
+
+        assert_eq!(
+            one.to_string_with(&provider, false),
+            "test[CPU(b=a%3Db%2C c%3Dd)]"
+        );
+        // The unversioned format of version 1 is rendered as-is, to verify old hashes.
+        assert_eq!(
+            one.to_string_v1_with(&provider, false),
+            "test[CPU(b=a=b, c=d)]"
+        );
+        assert_eq!(
+            injected.to_string_with(&provider, false),
+            "test[CPU(b=x%29%2C RAM%28t%3D1)]"
+        );
+
+        for identifier in [one, two, injected, split] {
+            let parsed: Identifier = identifier.to_string_with(&provider, false).parse().unwrap();
+            assert_eq!(parsed, identifier);
+        }
+
+        let named: Identifier = "a%5B1%5D[RAM(t=1)]".parse().unwrap();
+        assert_eq!(named.name, Some("a[1]".to_string()));
+        assert_eq!(named.to_string_with(&provider, false), "a%5B1%5D[RAM(t=1)]");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("test".parse::<Identifier>().is_err());
+        assert!("[cpu(b=x)]".parse::<Identifier>().is_err());
+        assert!("[CPU(b)]".parse::<Identifier>().is_err());
+        assert!("[CPU(b=x]".parse::<Identifier>().is_err());
+    }
+
+    #[test]
+    fn test_parse_unbalanced_parenthesis() {
+        for input in ["[CPU(b=x))]", "[CPU(b=x)y)]"] {
+            assert!(matches!(
+                input.parse::<Identifier>(),
+                Err(UniqueIdError::Parse(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::BTreeSet;
+
+use crate::hash::hash_value;
+use crate::wmi::{self, WmiRecord};
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// The directory holding the PCI devices on Linux.
+const LINUX_PCI_DIR: &str = "/sys/bus/pci/devices";
+
+/// The PCI base classes of devices commonly added and removed at runtime, excluded from the
+/// component: multimedia (0x04, such as capture and audio devices behind Thunderbolt docks) and
+/// wireless controllers (0x0d, such as swappable M.2 cards).
+const HOT_PLUGGABLE_CLASSES: [u8; 2] = [0x04, 0x0d];
+
+/// A single PCI function.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct PciDevice {
+    /// The vendor id as 4 lowercase hex digits. (e.g. 8086)
+    pub vendor: String,
+    /// The device id as 4 lowercase hex digits. (e.g. a0e0)
+    pub device: String,
+    /// The base class code, or None if unknown. (e.g. 0x0c for serial bus controllers)
+    pub class: Option<u8>,
+}
+
+/// Normalizes a hex id to 4 lowercase digits without prefix. (e.g. 0x8086 -> 8086)
+fn normalize_id(id: &str) -> String {
+    format!("{:0>4}", id.trim().trim_start_matches("0x").to_lowercase())
+}
+
+/// Returns the base class of a class code of any length. (e.g. 0x0c0330 -> 0x0c)
+fn base_class(class: &str) -> Option<u8> {
+    let class = class.trim().trim_start_matches("0x");
+
+    u8::from_str_radix(class.get(..2)?, 16).ok()
+}
+
+/// Reads the PCI devices from sysfs. SR-IOV virtual functions, which come and go with the
+/// configuration of their physical function, are skipped.
+pub(crate) fn linux_pci_devices(provider: &dyn HardwareProvider) -> Vec<PciDevice> {
+    provider
+        .read_dir(LINUX_PCI_DIR)
+        .into_iter()
+        .filter_map(|slot| {
+            let read = |file: &str| {
+                provider.read_to_string(&format!("{}/{}/{}", LINUX_PCI_DIR, slot, file))
+            };
+
+            if read("physfn/vendor").is_some() {
+                return None;
+            }
+
+            Some(PciDevice {
+                vendor: normalize_id(&read("vendor")?),
+                device: normalize_id(&read("device")?),
+                class: read("class").as_deref().and_then(base_class),
+            })
+        })
+        .collect()
+}
+
+/// Parses the Win32_PnPEntity WMI instances of PCI devices, reading the ids and the class code
+/// from the hardware ids. (e.g. PCI\VEN_8086&DEV_A0E0&CC_0C0330)
+pub(crate) fn parse_win32_pnp_entity(records: &[WmiRecord]) -> Vec<PciDevice> {
+    records
+        .iter()
+        .filter_map(|record| {
+            let hardware_ids = record.get("HardwareID")?;
+            let field = |prefix: &str| {
+                hardware_ids
+                    .split(['{', '}', ',', '\\', '&'])
+                    .find_map(|part| part.trim().strip_prefix(prefix))
+            };
+
+            if !record.get("PNPDeviceID")?.starts_with("PCI\\") {
+                return None;
+            }
+
+            Some(PciDevice {
+                vendor: normalize_id(field("VEN_")?),
+                device: normalize_id(field("DEV_")?),
+                class: field("CC_").and_then(base_class),
+            })
+        })
+        .collect()
+}
+
+/// Reads the PCI devices of the system. Platforms without a PCI source yield no devices.
+fn pci_devices(provider: &dyn HardwareProvider) -> Vec<PciDevice> {
+    if cfg!(target_os = "windows") {
+        parse_win32_pnp_entity(&wmi::query(
+            provider,
+            "Win32_PnPEntity",
+            &["PNPDeviceID", "HardwareID"],
+        ))
+    } else if cfg!(target_os = "linux") {
+        linux_pci_devices(provider)
+    } else {
+        Vec::new()
+    }
+}
+
+impl IdentifierTypeDataList {
+    /// Collects the number of distinct PCI vendor:device pairs and a hash of their sorted list.
+    /// Devices of hot-pluggable classes are excluded, so docking a laptop does not change the
+    /// component. Nothing is collected if no PCI device is readable.
+    pub(crate) fn collect_pci(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        let ids: BTreeSet<String> = pci_devices(provider)
+            .into_iter()
+            .filter(|device| {
+                !device
+                    .class
+                    .is_some_and(|class| HOT_PLUGGABLE_CLASSES.contains(&class))
+            })
+            .map(|device| format!("{}:{}", device.vendor, device.device))
+            .collect();
+
+        if ids.is_empty() {
+            return Vec::new();
+        }
+
+        let combined = ids.iter().cloned().collect::<Vec<_>>().join("\n");
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::PCI);
+        block.push("h", &hash_value(combined));
+        block.push("n", &ids.len().to_string());
+
+        vec![block]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierOptions};
+
+    fn file(path: &str, contents: &str) -> (String, Vec<u8>) {
+        (
+            format!("{}/{}", LINUX_PCI_DIR, path),
+            contents.as_bytes().to_vec(),
+        )
+    }
+
+    fn device(vendor: &str, device: &str, class: Option<u8>) -> PciDevice {
+        PciDevice {
+            vendor: vendor.to_string(),
+            device: device.to_string(),
+            class,
+        }
+    }
+
+    fn provider() -> FakeProvider {
+        FakeProvider::new(FakeData {
+            files: [
+                file("0000:00:00.0/vendor", "0x8086\n"),
+                file("0000:00:00.0/device", "0x9a14\n"),
+                file("0000:00:00.0/class", "0x060000\n"),
+                file("0000:00:14.0/vendor", "0x8086\n"),
+                file("0000:00:14.0/device", "0xa0ed\n"),
+                file("0000:00:14.0/class", "0x0c0330\n"),
+                file("0000:00:1f.3/vendor", "0x8086\n"),
+                file("0000:00:1f.3/device", "0xa0c8\n"),
+                file("0000:00:1f.3/class", "0x040380\n"),
+                file("0000:01:00.0/vendor", "0x15b3\n"),
+                file("0000:01:00.0/device", "0x1017\n"),
+                file("0000:01:00.0/class", "0x020000\n"),
+                file("0000:01:00.1/vendor", "0x15b3\n"),
+                file("0000:01:00.1/device", "0x1017\n"),
+                file("0000:01:00.1/class", "0x020000\n"),
+                file("0000:01:00.2/vendor", "0x15b3\n"),
+                file("0000:01:00.2/device", "0x1018\n"),
+                file("0000:01:00.2/class", "0x020000\n"),
+                file("0000:01:00.2/physfn/vendor", "0x15b3\n"),
+            ]
+            .into(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_linux_pci_devices() {
+        assert_eq!(
+            linux_pci_devices(&provider()),
+            vec![
+                device("8086", "9a14", Some(0x06)),
+                device("8086", "a0ed", Some(0x0c)),
+                device("8086", "a0c8", Some(0x04)),
+                device("15b3", "1017", Some(0x02)),
+                device("15b3", "1017", Some(0x02)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_win32_pnp_entity() {
+        let output = "\r\n\r\nPNPDeviceID : PCI\\VEN_8086&DEV_A0ED&SUBSYS_0A1F1028&REV_20\\3&11583659&0&A0\r\n\
+                      HardwareID  : {PCI\\VEN_8086&DEV_A0ED&SUBSYS_0A1F1028&REV_20, \
+                      PCI\\VEN_8086&DEV_A0ED&SUBSYS_0A1F1028, PCI\\VEN_8086&DEV_A0ED&CC_0C0330, \
+                      PCI\\VEN_8086&DEV_A0ED&CC_0C03...}\r\n\r\n\
+                      PNPDeviceID : USB\\VID_046D&PID_C077\\5&2A1B3C4D&0&2\r\n\
+                      HardwareID  : {USB\\VID_046D&PID_C077&REV_7200, USB\\VID_046D&PID_C077}\r\n\r\n";
+
+        assert_eq!(
+            parse_win32_pnp_entity(&wmi::parse_format_list(output)),
+            vec![device("8086", "a0ed", Some(0x0c))]
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_pci() {
+        let block = IdentifierTypeDataList::new(IdentifierType::PCI)
+            .build_with(&provider(), &IdentifierOptions::default());
+
+        let expected = hash_value("15b3:1017\n8086:9a14\n8086:a0ed");
+        assert_eq!(block, format!("PCI(h={}, n=3)", expected));
+
+        let parsed: IdentifierTypeDataList = block.parse().unwrap();
+        assert_eq!(parsed.identifier, IdentifierType::PCI);
+        assert_eq!(parsed.data[0].value, expected);
+        assert_eq!(parsed.data[1].value, "3");
+
+        assert!(IdentifierTypeDataList::new(IdentifierType::PCI)
+            .collect_with(&FakeProvider::default(), &IdentifierOptions::default())
+            .is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+use std::ptr;
+
+use ::pkcs11::types::{
+    CKA_CLASS, CKA_KEY_TYPE, CKA_SIGN, CKF_SERIAL_SESSION, CKK_RSA, CKM_SHA256_RSA_PKCS,
+    CKO_PRIVATE_KEY, CK_ATTRIBUTE, CK_MECHANISM, CK_SLOT_ID, CK_TRUE,
+};
+use ::pkcs11::Ctx;
+
+use crate::hash::to_hex;
+use crate::{HardwareProvider, HashAlgorithm, Identifier, SysinfoProvider, UniqueIdError};
+
+/// The environment variable overriding the PKCS#11 module used by `build_with_pkcs11_token`.
+const PKCS11_MODULE_VARIABLE: &str = "UNIQUEID_PKCS11_MODULE";
+
+/// The PKCS#11 module of OpenSC, used unless `UNIQUEID_PKCS11_MODULE` is set.
+const DEFAULT_MODULE: &str = if cfg!(windows) {
+    "opensc-pkcs11.dll"
+} else if cfg!(target_os = "macos") {
+    "/Library/OpenSC/lib/opensc-pkcs11.so"
+} else {
+    "opensc-pkcs11.so"
+};
+
+/// Returns the path of the PKCS#11 module the token is accessed through.
+fn module() -> String {
+    std::env::var(PKCS11_MODULE_VARIABLE).unwrap_or_else(|_| DEFAULT_MODULE.to_string())
+}
+
+/// Signs the message in memory with the first RSA private key of the token in the slot, with
+/// `CKM_SHA256_RSA_PKCS`. PKCS#1 v1.5 signatures are deterministic, so the same token always
+/// produces the same fingerprint. Returns None without a token or without a key that can sign
+/// without a login.
+pub(crate) fn sign_with_token(module: &str, slot: u64, message: &[u8]) -> Option<Vec<u8>> {
+    let slot = CK_SLOT_ID::try_from(slot).ok()?;
+    let context = Ctx::new_and_initialize(module).ok()?;
+    let session = context
+        .open_session(slot, CKF_SERIAL_SESSION, None, None)
+        .ok()?;
+
+    let (class, key_type, sign) = (CKO_PRIVATE_KEY, CKK_RSA, CK_TRUE);
+    let template = [
+        CK_ATTRIBUTE::new(CKA_CLASS).with_ck_ulong(&class),
+        CK_ATTRIBUTE::new(CKA_KEY_TYPE).with_ck_ulong(&key_type),
+        CK_ATTRIBUTE::new(CKA_SIGN).with_bool(&sign),
+    ];
+    let mechanism = CK_MECHANISM {
+        mechanism: CKM_SHA256_RSA_PKCS,
+        pParameter: ptr::null_mut(),
+        ulParameterLen: 0,
+    };
+
+    let signature = context
+        .find_objects_init(session, &template)
+        .and_then(|_| {
+            let keys = context.find_objects(session, 1);
+            context.find_objects_final(session)?;
+            keys
+        })
+        .ok()
+        .and_then(|keys| keys.first().copied())
+        .and_then(|key| context.sign_init(session, &mechanism, key).ok())
+        .and_then(|_| context.sign(session, message).ok());
+
+    let _ = context.close_session(session);
+
+    signature.filter(|signature| !signature.is_empty())
+}
+
+impl Identifier {
+    /// Signs the canonical string of the Identifier with a key stored in a PKCS#11 token (smart
+    /// card, YubiKey, HSM) and returns the hex encoded SHA3-512 hash of the signature and the
+    /// canonical string, binding the fingerprint to the presence of the token.
+    ///
+    /// The signature is made in memory with `C_Sign` and the first RSA key of the token, which has
+    /// to be usable without a login. The token is accessed through the PKCS#11 module of OpenSC,
+    /// or the module at the path in the `UNIQUEID_PKCS11_MODULE` environment variable.
+    /// # Arguments
+    /// * `slot` - The id of the slot holding the token.
+    /// # Errors
+    /// Returns `UniqueIdError::TokenUnavailable` if no signature could be made with the token.
+    /// # Examples
+    /// ```no_run
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::CPU);
+    ///
+    /// let hash = builder.build().build_with_pkcs11_token(0).unwrap();
+    ///
+    /// assert_eq!(hash.len(), 128);
+    /// ```
+    pub fn build_with_pkcs11_token(&self, slot: u64) -> Result<String, UniqueIdError> {
+        self.build_with_pkcs11_token_with(&SysinfoProvider::new(), slot)
+    }
+
+    /// Signs the canonical string of the Identifier built from the data of the given provider with
+    /// a key stored in a PKCS#11 token and returns the hex encoded hash.
+    /// # Arguments
+    /// * `provider` - The HardwareProvider the data is collected from and the token is accessed by.
+    /// * `slot` - The id of the slot holding the token.
+    /// # Errors
+    /// Returns `UniqueIdError::TokenUnavailable` if no signature could be made with the token.
+    pub fn build_with_pkcs11_token_with(
+        &self,
+        provider: &dyn HardwareProvider,
+        slot: u64,
+    ) -> Result<String, UniqueIdError> {
+        let canonical = self.to_canonical_string_with(provider);
+        let signature = provider
+            .sign_with_pkcs11_token(&module(), slot, canonical.as_bytes())
+            .ok_or(UniqueIdError::TokenUnavailable(slot))?;
+
+        let input = format!("{}:{}", to_hex(&signature), canonical);
+
+        Ok(to_hex(&HashAlgorithm::SHA3_512.digest(input.as_bytes())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierBuilder, IdentifierType};
+
+    #[test]
+    fn test_build_with_pkcs11_token() {
+        let provider = FakeProvider::new(FakeData {
+            total_memory: 1024,
+            pkcs11_signatures: [(1, vec![0xAB; 256])].into(),
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.name("test");
+        builder.add(IdentifierType::RAM);
+        let identifier = builder.build();
+
+        let hash = identifier
+            .build_with_pkcs11_token_with(&provider, 1)
+            .unwrap();
+        let input = format!("{}:uid:v3:test[RAM(t=1024)]", "ab".repeat(256));
+
+        assert_eq!(
+            hash,
+            to_hex(&HashAlgorithm::SHA3_512.digest(input.as_bytes()))
+        );
+        assert_ne!(hash, identifier.to_string_with(&provider, true));
+
+        // A different signature gives a different fingerprint.
+        provider.update(|data| {
+            data.pkcs11_signatures.insert(1, vec![0xCD; 256]);
+        });
+        assert_ne!(
+            identifier.build_with_pkcs11_token_with(&provider, 1),
+            Ok(hash)
+        );
+
+        // No token in the slot.
+        assert_eq!(
+            identifier.build_with_pkcs11_token_with(&provider, 2),
+            Err(UniqueIdError::TokenUnavailable(2))
+        );
+    }
+
+    #[test]
+    fn test_sign_with_token() {
+        assert_eq!(
+            sign_with_token("/nonexistent/uniqueid-pkcs11.so", 0, b"message"),
+            None
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use crate::battery::LINUX_POWER_SUPPLY_DIR;
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// Returns true if the description of a FRU names a power supply. (e.g. PS1, PSU 2, Power Supply 1)
+fn is_power_supply(description: &str) -> bool {
+    let description = description.to_uppercase();
+
+    description.contains("POWER SUPPLY")
+        || description.starts_with("PSU")
+        || description
+            .strip_prefix("PS")
+            .is_some_and(|rest| rest.trim_start().starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Parses the model of every power supply FRU in `ipmitool fru print` output, preferring the part
+/// number over the product name.
+pub(crate) fn parse_ipmitool_fru(output: &str) -> Vec<String> {
+    let mut result = Vec::new();
+
+    for device in output.split("FRU Device Description").skip(1) {
+        let field = |name: &str| {
+            device.lines().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                (key.trim() == name && !value.trim().is_empty()).then(|| value.trim().to_string())
+            })
+        };
+
+        let description = device
+            .split_once(':')
+            .map(|(_, rest)| rest.lines().next().unwrap_or_default())
+            .unwrap_or_default();
+
+        if !is_power_supply(description.trim()) {
+            continue;
+        }
+
+        let model = field("Product Part Number")
+            .or_else(|| field("Board Part Number"))
+            .or_else(|| field("Product Name"))
+            .or_else(|| field("Board Product"));
+
+        result.extend(model);
+    }
+
+    result
+}
+
+/// Reads the model of every mains power supply in sysfs. Batteries and UPS are skipped.
+pub(crate) fn linux_power_supplies(provider: &dyn HardwareProvider) -> Vec<String> {
+    provider
+        .read_dir(LINUX_POWER_SUPPLY_DIR)
+        .into_iter()
+        .filter_map(|supply| {
+            let read = |file: &str| {
+                provider.read_to_string(&format!("{}/{}/{}", LINUX_POWER_SUPPLY_DIR, supply, file))
+            };
+
+            if read("type")?.trim() != "Mains" {
+                return None;
+            }
+
+            Some(read("model_name")?.trim().to_string()).filter(|model| !model.is_empty())
+        })
+        .collect()
+}
+
+/// Reads the power supply models of the system. The FRU inventory of the BMC is preferred, sysfs
+/// only exposes the power supplies of some servers.
+fn power_supplies(provider: &dyn HardwareProvider) -> Vec<String> {
+    let fru = provider
+        .run_command("ipmitool", &["fru", "print"])
+        .map(|output| parse_ipmitool_fru(&output))
+        .unwrap_or_default();
+
+    match fru.is_empty() && cfg!(target_os = "linux") {
+        true => linux_power_supplies(provider),
+        false => fru,
+    }
+}
+
+impl IdentifierTypeDataList {
+    /// Collects one block per power supply with its model, sorted by model, or nothing if no
+    /// power supply is readable, such as on desktops or without access to the BMC.
+    pub(crate) fn collect_power_supply(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        let mut models = power_supplies(provider);
+        models.sort();
+
+        models
+            .into_iter()
+            .map(|model| {
+                let mut block = IdentifierTypeDataList::new(IdentifierType::POWER_SUPPLY);
+                block.push("m", &model);
+                block
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierBuilder};
+
+    const IPMITOOL_FRU: &str = "FRU Device Description : Builtin FRU Device (ID 0)
+ Chassis Type          : Rack Mount Chassis
+ Board Mfg             : DELL
+ Board Product         : PowerEdge R640
+ Product Name          : PowerEdge R640
+
+FRU Device Description : PS2 (ID 3)
+ Board Mfg             : DELL
+ Board Product         : PWR SPLY,750W,RDNT,DELTA
+ Board Part Number     : 0N7HF9A02
+
+FRU Device Description : PS1 (ID 2)
+ Board Mfg             : DELL
+ Board Product         : PWR SPLY,750W,RDNT,DELTA
+ Board Part Number     : 0N7HF9A01
+
+FRU Device Description : PERC H730P Mini (ID 4)
+ Board Product         : PERC H730P Mini
+";
+
+    #[test]
+    fn test_parse_ipmitool_fru() {
+        assert_eq!(
+            parse_ipmitool_fru(IPMITOOL_FRU),
+            vec!["0N7HF9A02".to_string(), "0N7HF9A01".to_string()]
+        );
+        assert!(is_power_supply("Power Supply 1"));
+        assert!(!is_power_supply("PSoC"));
+    }
+
+    #[test]
+    fn test_power_supply() {
+        let provider = FakeProvider::new(FakeData {
+            commands: [("ipmitool fru print".to_string(), IPMITOOL_FRU.to_string())].into(),
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::POWER_SUPPLY);
+
+        assert_eq!(
+            builder.build().to_string_with(&provider, false),
+            "[POWER_SUPPLY(m=0N7HF9A01)POWER_SUPPLY(m=0N7HF9A02)]"
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_linux_power_supplies() {
+        let file = |path: &str, contents: &str| {
+            (
+                format!("{}/{}", LINUX_POWER_SUPPLY_DIR, path),
+                contents.as_bytes().to_vec(),
+            )
+        };
+
+        let provider = FakeProvider::new(FakeData {
+            files: [
+                file("PSU1/type", "Mains\n"),
+                file("PSU1/model_name", "DPS-800AB-5 A\n"),
+                file("AC/type", "Mains\n"),
+                file("BAT0/type", "Battery\n"),
+                file("BAT0/model_name", "5B10W13975\n"),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(linux_power_supplies(&provider), vec!["DPS-800AB-5 A"]);
+    }
+}
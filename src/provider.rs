@@ -0,0 +1,533 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use sysinfo::{self, DiskExt, ProcessorExt, System, SystemExt};
+
+/// A struct representing the information of a single processor.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ProcessorInfo {
+    /// The brand of the processor. (e.g. Intel(R) Core(TM) i7-8750H CPU @ 2.20GHz)
+    pub brand: String,
+    /// The vendor id of the processor. (e.g. GenuineIntel)
+    pub vendor: String,
+    /// The frequency of the processor in MHz.
+    pub frequency: u64,
+}
+
+/// Enum representing the kind of drive holding a disk, as reported by the platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiskKind {
+    /// A rotational hard disk drive.
+    Hdd,
+    /// A solid state drive.
+    Ssd,
+    /// A drive of an unknown kind.
+    #[default]
+    Unknown,
+}
+
+impl DiskKind {
+    /// Returns the name of the kind, as emitted in `DISK(k=...)`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiskKind::Hdd => "hdd",
+            DiskKind::Ssd => "ssd",
+            DiskKind::Unknown => "unknown",
+        }
+    }
+}
+
+impl From<sysinfo::DiskType> for DiskKind {
+    fn from(kind: sysinfo::DiskType) -> Self {
+        match kind {
+            sysinfo::DiskType::HDD => DiskKind::Hdd,
+            sysinfo::DiskType::SSD => DiskKind::Ssd,
+            // The raw value differs between platforms, so it is not kept.
+            sysinfo::DiskType::Unknown(_) => DiskKind::Unknown,
+        }
+    }
+}
+
+/// A struct representing the information of a single disk.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DiskInfo {
+    /// The device path of the disk. (e.g. /dev/sda1 or /dev/nvme0n1p2)
+    pub device: String,
+    /// The mount point of the disk. (e.g. / or C:\\)
+    pub mount_point: String,
+    /// The file system of the disk, as reported by the platform. (e.g. ext4, NTFS, apfs)
+    pub file_system: String,
+    /// The total space of the disk in bytes.
+    pub total_space: u64,
+    /// Whether the disk is removable.
+    pub removable: bool,
+    /// The kind of drive holding the disk.
+    pub kind: DiskKind,
+}
+
+/// A struct representing the information of the operating system.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OsInfo {
+    /// The name of the operating system, if known. (e.g. Ubuntu, Windows)
+    pub name: Option<String>,
+    /// The version of the operating system, if known. (e.g. 22.04, 10)
+    pub version: Option<String>,
+    /// The version of the kernel, if known. (e.g. 5.15.0-91-generic, 19045)
+    pub kernel_version: Option<String>,
+    /// The CPU architecture the operating system runs on, if known. (e.g. x86_64, aarch64)
+    pub architecture: Option<String>,
+    /// The host name of the system, if known. (e.g. build-server-01)
+    pub host_name: Option<String>,
+}
+
+/// A struct representing the registers returned by a CPUID leaf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CpuidLeaf {
+    /// The leaf queried. (EAX input)
+    pub leaf: u32,
+    /// The subleaf queried. (ECX input)
+    pub subleaf: u32,
+    /// The EAX output.
+    pub eax: u32,
+    /// The EBX output.
+    pub ebx: u32,
+    /// The ECX output.
+    pub ecx: u32,
+    /// The EDX output.
+    pub edx: u32,
+}
+
+/// A source of the system information read by the IdentifierType collectors.
+pub trait HardwareProvider: Send + Sync {
+    /// Returns the logical processors of the system.
+    fn processors(&self) -> Vec<ProcessorInfo>;
+
+    /// Returns the number of physical cores of the system, if known.
+    fn physical_core_count(&self) -> Option<usize>;
+
+    /// Returns the total memory of the system in KB.
+    fn total_memory(&self) -> u64;
+
+    /// Returns the disks of the system.
+    fn disks(&self) -> Vec<DiskInfo>;
+
+    /// Returns the information of the operating system.
+    fn os(&self) -> OsInfo {
+        OsInfo::default()
+    }
+
+    /// Executes the CPUID instruction with the given leaf and subleaf, if the CPU supports it.
+    /// The SysinfoProvider requires the `cpuid` feature on x86_64, or on 32 bit x86 with SSE, and
+    /// returns None elsewhere.
+    fn cpuid(&self, _leaf: u32, _subleaf: u32) -> Option<CpuidLeaf> {
+        None
+    }
+
+    /// Returns the canonical (symlink-resolved) path of the running executable, if known.
+    fn current_exe(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the contents of the file at the given path, if it can be read.
+    fn read_file(&self, _path: &str) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Returns the contents of the file at the given path as a string, if it can be read as UTF-8.
+    fn read_to_string(&self, path: &str) -> Option<String> {
+        String::from_utf8(self.read_file(path)?).ok()
+    }
+
+    /// Returns the names of the entries of the directory at the given path, sorted.
+    fn read_dir(&self, _path: &str) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Runs a command and returns its standard output, if it exited successfully.
+    fn run_command(&self, _program: &str, _args: &[&str]) -> Option<String> {
+        None
+    }
+
+    /// Sends a plain HTTP request and returns the response body, if the status is 200 OK.
+    /// The request is abandoned once the timeout has elapsed.
+    fn http_request(
+        &self,
+        _method: &str,
+        _url: &str,
+        _headers: &[(&str, &str)],
+        _timeout: Duration,
+    ) -> Option<String> {
+        None
+    }
+
+    /// Signs the message with the first RSA key of the PKCS#11 token in the slot, accessed through
+    /// the module at the given path, and returns the signature if the token can sign.
+    #[cfg(feature = "pkcs11")]
+    fn sign_with_pkcs11_token(
+        &self,
+        _module: &str,
+        _slot: u64,
+        _message: &[u8],
+    ) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Returns the public key of the TPM Endorsement Key, the RSA modulus or the concatenated
+    /// coordinates of the ECC point, if the TPM can be opened.
+    #[cfg(feature = "tpm")]
+    fn tpm_endorsement_key(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Refreshes the information returned by the provider.
+    fn refresh(&self) {}
+}
+
+/// A HardwareProvider reading from a single shared sysinfo System.
+/// Creating a System is expensive, so a SysinfoProvider should be reused and refreshed.
+pub struct SysinfoProvider {
+    system: Mutex<System>,
+}
+
+impl SysinfoProvider {
+    /// Creates a new SysinfoProvider object.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{HardwareProvider, SysinfoProvider};
+    ///
+    /// let provider = SysinfoProvider::new();
+    ///
+    /// assert!(provider.total_memory() > 0);
+    /// ```
+    pub fn new() -> Self {
+        let mut system = System::new_all();
+
+        system.refresh_all();
+
+        SysinfoProvider {
+            system: Mutex::new(system),
+        }
+    }
+}
+
+impl Default for SysinfoProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HardwareProvider for SysinfoProvider {
+    fn processors(&self) -> Vec<ProcessorInfo> {
+        let system = self.system.lock().unwrap();
+
+        system
+            .processors()
+            .iter()
+            .map(|processor| ProcessorInfo {
+                brand: processor.brand().to_string(),
+                vendor: processor.vendor_id().to_string(),
+                frequency: processor.frequency(),
+            })
+            .collect()
+    }
+
+    fn physical_core_count(&self) -> Option<usize> {
+        self.system.lock().unwrap().physical_core_count()
+    }
+
+    fn total_memory(&self) -> u64 {
+        self.system.lock().unwrap().total_memory()
+    }
+
+    fn disks(&self) -> Vec<DiskInfo> {
+        let system = self.system.lock().unwrap();
+
+        system
+            .disks()
+            .iter()
+            .map(|disk| DiskInfo {
+                device: disk.name().to_string_lossy().to_string(),
+                mount_point: disk.mount_point().to_string_lossy().to_string(),
+                file_system: String::from_utf8_lossy(disk.file_system()).to_string(),
+                total_space: disk.total_space(),
+                removable: disk.is_removable(),
+                kind: disk.type_().into(),
+            })
+            .collect()
+    }
+
+    fn os(&self) -> OsInfo {
+        let system = self.system.lock().unwrap();
+
+        OsInfo {
+            name: system.name(),
+            version: system.os_version(),
+            kernel_version: system.kernel_version(),
+            architecture: Some(std::env::consts::ARCH.to_string()),
+            host_name: system.host_name(),
+        }
+    }
+
+    #[cfg(all(
+        feature = "cpuid",
+        not(target_env = "sgx"),
+        any(
+            target_arch = "x86_64",
+            all(target_arch = "x86", target_feature = "sse")
+        )
+    ))]
+    fn cpuid(&self, leaf: u32, subleaf: u32) -> Option<CpuidLeaf> {
+        let result = raw_cpuid::native_cpuid::cpuid_count(leaf, subleaf);
+
+        Some(CpuidLeaf {
+            leaf,
+            subleaf,
+            eax: result.eax,
+            ebx: result.ebx,
+            ecx: result.ecx,
+            edx: result.edx,
+        })
+    }
+
+    fn current_exe(&self) -> Option<String> {
+        let path = std::env::current_exe().ok()?;
+
+        Some(
+            path.canonicalize()
+                .unwrap_or(path)
+                .to_string_lossy()
+                .to_string(),
+        )
+    }
+
+    fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        std::fs::read(path).ok()
+    }
+
+    fn read_dir(&self, path: &str) -> Vec<String> {
+        let mut entries: Vec<String> = std::fs::read_dir(path)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+
+        entries.sort();
+        entries
+    }
+
+    fn run_command(&self, program: &str, args: &[&str]) -> Option<String> {
+        let output = std::process::Command::new(program)
+            .args(args)
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        Some(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    #[cfg(feature = "cloud")]
+    fn http_request(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &[(&str, &str)],
+        timeout: Duration,
+    ) -> Option<String> {
+        crate::cloud::http_request(method, url, headers, timeout)
+    }
+
+    #[cfg(feature = "pkcs11")]
+    fn sign_with_pkcs11_token(&self, module: &str, slot: u64, message: &[u8]) -> Option<Vec<u8>> {
+        crate::pkcs11::sign_with_token(module, slot, message)
+    }
+
+    #[cfg(all(feature = "tpm", target_os = "linux"))]
+    fn tpm_endorsement_key(&self) -> Option<Vec<u8>> {
+        crate::tpm::read_endorsement_key()
+    }
+
+    fn refresh(&self) {
+        self.system.lock().unwrap().refresh_all();
+    }
+}
+
+/// The data returned by a FakeProvider.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FakeData {
+    /// The processors returned by the provider.
+    pub processors: Vec<ProcessorInfo>,
+    /// The number of physical cores returned by the provider.
+    pub physical_core_count: Option<usize>,
+    /// The total memory returned by the provider.
+    pub total_memory: u64,
+    /// The disks returned by the provider.
+    pub disks: Vec<DiskInfo>,
+    /// The operating system information returned by the provider.
+    pub os: OsInfo,
+    /// The CPUID leaves returned by the provider.
+    pub cpuid: Vec<CpuidLeaf>,
+    /// The path of the running executable returned by the provider.
+    pub current_exe: Option<String>,
+    /// The files readable through the provider, by path. Directories are implied by the paths.
+    pub files: BTreeMap<String, Vec<u8>>,
+    /// The output of the commands runnable through the provider, by command line. (program args...)
+    pub commands: BTreeMap<String, String>,
+    /// The bodies of the HTTP responses returned by the provider, by request. (METHOD url)
+    pub http: BTreeMap<String, String>,
+    /// The signatures made by the PKCS#11 token in each slot, by slot id, whatever the message.
+    #[cfg(feature = "pkcs11")]
+    pub pkcs11_signatures: BTreeMap<u64, Vec<u8>>,
+    /// The public key of the TPM Endorsement Key.
+    #[cfg(feature = "tpm")]
+    pub tpm_endorsement_key: Option<Vec<u8>>,
+}
+
+/// A HardwareProvider returning fixed data, useful for tests.
+/// The data can be mutated through a shared reference with `update`.
+#[derive(Debug, Default)]
+pub struct FakeProvider {
+    data: RwLock<FakeData>,
+}
+
+impl FakeProvider {
+    /// Creates a new FakeProvider object.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{FakeData, FakeProvider, HardwareProvider};
+    ///
+    /// let provider = FakeProvider::new(FakeData {
+    ///     total_memory: 1024,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// assert_eq!(provider.total_memory(), 1024);
+    /// ```
+    pub fn new(data: FakeData) -> Self {
+        FakeProvider {
+            data: RwLock::new(data),
+        }
+    }
+
+    /// Mutates the data returned by the FakeProvider.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{FakeData, FakeProvider, HardwareProvider};
+    ///
+    /// let provider = FakeProvider::default();
+    /// provider.update(|data| data.total_memory = 2048);
+    ///
+    /// assert_eq!(provider.total_memory(), 2048);
+    /// ```
+    pub fn update<F: FnOnce(&mut FakeData)>(&self, f: F) {
+        f(&mut self.data.write().unwrap());
+    }
+}
+
+impl HardwareProvider for FakeProvider {
+    fn processors(&self) -> Vec<ProcessorInfo> {
+        self.data.read().unwrap().processors.clone()
+    }
+
+    fn physical_core_count(&self) -> Option<usize> {
+        self.data.read().unwrap().physical_core_count
+    }
+
+    fn total_memory(&self) -> u64 {
+        self.data.read().unwrap().total_memory
+    }
+
+    fn disks(&self) -> Vec<DiskInfo> {
+        self.data.read().unwrap().disks.clone()
+    }
+
+    fn os(&self) -> OsInfo {
+        self.data.read().unwrap().os.clone()
+    }
+
+    fn cpuid(&self, leaf: u32, subleaf: u32) -> Option<CpuidLeaf> {
+        let data = self.data.read().unwrap();
+
+        data.cpuid
+            .iter()
+            .find(|entry| entry.leaf == leaf && entry.subleaf == subleaf)
+            .copied()
+    }
+
+    fn current_exe(&self) -> Option<String> {
+        self.data.read().unwrap().current_exe.clone()
+    }
+
+    fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        self.data.read().unwrap().files.get(path).cloned()
+    }
+
+    fn read_dir(&self, path: &str) -> Vec<String> {
+        let prefix = format!("{}/", path.trim_end_matches('/'));
+
+        let mut entries: Vec<String> = self
+            .data
+            .read()
+            .unwrap()
+            .files
+            .keys()
+            .filter_map(|file| file.strip_prefix(&prefix))
+            .filter_map(|rest| rest.split('/').next())
+            .map(|entry| entry.to_string())
+            .collect();
+
+        entries.dedup();
+        entries
+    }
+
+    fn run_command(&self, program: &str, args: &[&str]) -> Option<String> {
+        let command = std::iter::once(program)
+            .chain(args.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        self.data.read().unwrap().commands.get(&command).cloned()
+    }
+
+    fn http_request(
+        &self,
+        method: &str,
+        url: &str,
+        _headers: &[(&str, &str)],
+        _timeout: Duration,
+    ) -> Option<String> {
+        let request = format!("{} {}", method, url);
+
+        self.data.read().unwrap().http.get(&request).cloned()
+    }
+
+    #[cfg(feature = "pkcs11")]
+    fn sign_with_pkcs11_token(&self, _module: &str, slot: u64, _message: &[u8]) -> Option<Vec<u8>> {
+        self.data
+            .read()
+            .unwrap()
+            .pkcs11_signatures
+            .get(&slot)
+            .cloned()
+    }
+
+    #[cfg(feature = "tpm")]
+    fn tpm_endorsement_key(&self) -> Option<Vec<u8>> {
+        self.data.read().unwrap().tpm_endorsement_key.clone()
+    }
+}
@@ -0,0 +1,105 @@
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+
+use crate::{Identifier, UniqueIdError};
+
+/// The process-global limiter shared by every `Identifier::build_with_rate_limit` call.
+static LIMITER: OnceLock<Arc<Mutex<TokenBucket>>> = OnceLock::new();
+
+/// A token bucket holding up to one second worth of builds, refilled continuously.
+#[derive(Debug, Clone)]
+pub(crate) struct TokenBucket {
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full TokenBucket.
+    pub(crate) fn new(now: Instant, rate: u32) -> Self {
+        TokenBucket {
+            tokens: rate as f64,
+            last: now,
+        }
+    }
+
+    /// Takes a token if one is available, refilling `rate` tokens per second since the last call.
+    pub(crate) fn try_acquire(&mut self, now: Instant, rate: u32) -> bool {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * rate as f64).min(rate as f64);
+        self.last = now;
+
+        if self.tokens < 1.0 {
+            return false;
+        }
+
+        self.tokens -= 1.0;
+        true
+    }
+}
+
+impl Identifier {
+    /// Builds the Identifier and returns its SHA3-512 hash, like `to_string(true)`, unless more than
+    /// `max_per_second` builds were made in the last second by this process.
+    ///
+    /// The limit is shared by every Identifier of the process, which slows down grinding through
+    /// many hardware configurations in search of a matching fingerprint.
+    /// # Arguments
+    /// * `max_per_second` - The number of builds allowed per second.
+    /// # Errors
+    /// Returns `UniqueIdError::RateLimited` if the limit is exceeded.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType, UniqueIdError};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// assert!(identifier.build_with_rate_limit(1).is_ok());
+    /// assert_eq!(identifier.build_with_rate_limit(1), Err(UniqueIdError::RateLimited));
+    /// ```
+    pub fn build_with_rate_limit(&self, max_per_second: u32) -> Result<String, UniqueIdError> {
+        let now = Instant::now();
+        let limiter =
+            LIMITER.get_or_init(|| Arc::new(Mutex::new(TokenBucket::new(now, max_per_second))));
+
+        let allowed = limiter
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .try_acquire(now, max_per_second);
+
+        match allowed {
+            true => Ok(self.to_string(true)),
+            false => Err(UniqueIdError::RateLimited),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_token_bucket() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(start, 2);
+
+        assert!(bucket.try_acquire(start, 2));
+        assert!(bucket.try_acquire(start, 2));
+        assert!(!bucket.try_acquire(start, 2));
+
+        assert!(bucket.try_acquire(start + Duration::from_millis(500), 2));
+        assert!(!bucket.try_acquire(start + Duration::from_millis(600), 2));
+
+        // Tokens do not accumulate beyond one second worth of builds.
+        let later = start + Duration::from_secs(60);
+        assert!(bucket.try_acquire(later, 2));
+        assert!(bucket.try_acquire(later, 2));
+        assert!(!bucket.try_acquire(later, 2));
+
+        assert!(!TokenBucket::new(start, 0).try_acquire(later, 0));
+    }
+}
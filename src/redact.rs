@@ -0,0 +1,196 @@
+use crate::{IdentifierType, IdentifierTypeData, IdentifierTypeDataList};
+
+/// The keys holding privacy-sensitive values in the built-in components, as (component, key).
+const SENSITIVE_KEYS: [(&str, &str); 17] = [
+    // The MAC addresses of the network adapters.
+    ("NET", "m"),
+    // The serial numbers of the hardware.
+    ("BOARD", "s"),
+    ("DISK", "s"),
+    ("BATTERY", "s"),
+    ("VOLUME", "s"),
+    ("CHASSIS_SERIAL", "s"),
+    ("SERIAL", "s"),
+    ("CPU", "sn"),
+    // The ids of the installation, the system, the boot loader, the cloud instance, the container
+    // and the Kubernetes pod.
+    ("MACHINE", "id"),
+    ("SYSUUID", "id"),
+    ("BOOT_LOADER", "id"),
+    ("CLOUD", "id"),
+    ("CONTAINER", "id"),
+    ("CONTAINER", "pod"),
+    // The TPM endorsement key.
+    ("TPM", "ek"),
+    // The host name.
+    ("HOST", "n"),
+    // The default gateways of the network.
+    ("ROUTING_TABLE", "g"),
+];
+
+/// The value sensitive values are replaced with by `IdentifierTypeDataList::redact`.
+pub const REDACTED: &str = "[redacted]";
+
+/// Returns true if the key of the RAM component holds the serial number of a DIMM. (d0s, d1s, ...)
+fn is_dimm_serial(key: &str) -> bool {
+    key.strip_prefix('d')
+        .and_then(|key| key.strip_suffix('s'))
+        .is_some_and(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+}
+
+impl IdentifierType {
+    /// Returns true if the key of this IdentifierType is known to hold privacy-sensitive data, such
+    /// as a MAC address or a serial number.
+    /// # Arguments
+    /// * `key` - The key of the data point, e.g. `s` for the serial number of a disk.
+    /// # Examples
+    /// ```
+    /// use uniqueid::IdentifierType;
+    ///
+    /// assert!(IdentifierType::DISK.is_sensitive("s"));
+    /// assert!(!IdentifierType::CPU.is_sensitive("s"));
+    /// assert!(!IdentifierType::RAM.is_sensitive("t"));
+    /// ```
+    pub fn is_sensitive(&self, key: &str) -> bool {
+        match self {
+            IdentifierType::RAM => is_dimm_serial(key),
+            identifier => SENSITIVE_KEYS.contains(&(identifier.as_str(), key)),
+        }
+    }
+}
+
+impl IdentifierTypeData {
+    /// Returns true if the key holds privacy-sensitive data in any built-in component. The
+    /// component is unknown here, so `id` also matches the non-sensitive keys of Custom components;
+    /// prefer `IdentifierType::is_sensitive` when the component is known.
+    /// # Examples
+    /// ```
+    /// use uniqueid::IdentifierTypeData;
+    ///
+    /// assert!(IdentifierTypeData::new("s", "210686720601234").is_sensitive());
+    /// assert!(IdentifierTypeData::new("d0s", "0x1A2B3C4D").is_sensitive());
+    /// assert!(!IdentifierTypeData::new("t", "1024").is_sensitive());
+    /// ```
+    pub fn is_sensitive(&self) -> bool {
+        IdentifierType::RAM.is_sensitive(&self.key)
+            || SENSITIVE_KEYS
+                .iter()
+                .any(|(identifier, _)| IdentifierType::from(*identifier).is_sensitive(&self.key))
+    }
+}
+
+impl IdentifierTypeDataList {
+    /// Returns a copy of the IdentifierTypeDataList with every sensitive value replaced by
+    /// `REDACTED`, for logging or displaying collected data.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierType, IdentifierTypeDataList, REDACTED};
+    ///
+    /// let list: IdentifierTypeDataList = "NET(m=3c:ec:ef:00:00:01)".parse().unwrap();
+    ///
+    /// assert_eq!(list.redact().data[0].value, REDACTED);
+    /// ```
+    pub fn redact(&self) -> IdentifierTypeDataList {
+        let mut list = self.clone();
+
+        for item in &mut list.data {
+            if self.identifier.is_sensitive(&item.key) {
+                item.value = REDACTED.to_string();
+            }
+        }
+
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeProvider, IdentifierOptions};
+
+    #[test]
+    fn test_redact() {
+        let list: IdentifierTypeDataList =
+            "BOARD(m=ASUSTeK COMPUTER INC., p=PRIME B450M-A, s=MT7012345)"
+                .parse()
+                .unwrap();
+
+        let redacted = list.redact();
+
+        assert_eq!(redacted.identifier, list.identifier);
+        assert_eq!(
+            redacted
+                .data
+                .iter()
+                .map(|item| item.to_string())
+                .collect::<Vec<_>>(),
+            vec!["m=ASUSTeK COMPUTER INC.", "p=PRIME B450M-A", "s=[redacted]"]
+        );
+    }
+
+    #[test]
+    fn test_is_sensitive() {
+        assert!(IdentifierType::NET.is_sensitive("m"));
+        assert!(IdentifierType::CPU.is_sensitive("sn"));
+        assert!(IdentifierType::HOST.is_sensitive("n"));
+        assert!(IdentifierType::MACHINE.is_sensitive("id"));
+        assert!(IdentifierType::RAM.is_sensitive("d0s"));
+        assert!(IdentifierType::RAM.is_sensitive("d12s"));
+        assert!(IdentifierType::from("BOOT_LOADER").is_sensitive("id"));
+        assert!(IdentifierType::CONTAINER.is_sensitive("id"));
+        assert!(IdentifierType::CONTAINER.is_sensitive("pod"));
+        assert!(IdentifierType::ROUTING_TABLE.is_sensitive("g"));
+
+        // The same keys of other components are not sensitive.
+        assert!(!IdentifierType::CPU.is_sensitive("s"));
+        assert!(!IdentifierType::DISK.is_sensitive("m"));
+        assert!(!IdentifierType::BOARD.is_sensitive("m"));
+        assert!(!IdentifierType::OS.is_sensitive("n"));
+        assert!(!IdentifierType::RAM.is_sensitive("d0m"));
+        assert!(!IdentifierType::RAM.is_sensitive("ds"));
+        assert!(!IdentifierType::Custom("NET2".to_string()).is_sensitive("m"));
+
+        let list: IdentifierTypeDataList = "CPU(b=cortex-a72, s=1, sn=10000000abcdef01)"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            list.redact()
+                .build_with(&FakeProvider::default(), &IdentifierOptions::default()),
+            "CPU(b=cortex-a72, s=1, sn=[redacted])"
+        );
+    }
+
+    #[test]
+    fn test_redact_ids() {
+        let redact = |input: &str| {
+            input
+                .parse::<IdentifierTypeDataList>()
+                .unwrap()
+                .redact()
+                .build_with(&FakeProvider::default(), &IdentifierOptions::default())
+        };
+
+        assert_eq!(
+            redact("BOOT_LOADER(id=3f2a-11c4)"),
+            "BOOT_LOADER(id=[redacted])"
+        );
+        assert_eq!(
+            redact("CONTAINER(id=4f1c2a9b7d3e, pod=7c1a5c2e-8d33-4b6f-9e55-0a2b4c6d8e10)"),
+            "CONTAINER(id=[redacted], pod=[redacted])"
+        );
+        assert_eq!(
+            redact("ROUTING_TABLE(g=192.168.1.1, i=eth0)"),
+            "ROUTING_TABLE(g=[redacted], i=eth0)"
+        );
+    }
+
+    #[test]
+    fn test_data_is_sensitive() {
+        for key in ["m", "s", "sn", "id", "pod", "ek", "n", "g", "d3s"] {
+            assert!(IdentifierTypeData::new(key, "x").is_sensitive(), "{}", key);
+        }
+        for key in ["t", "b", "c", "i", "ds"] {
+            assert!(!IdentifierTypeData::new(key, "x").is_sensitive(), "{}", key);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+
+use crate::HardwareProvider;
+
+/// The values of a single registry key as (name, data) pairs.
+pub(crate) type RegistryKey = BTreeMap<String, String>;
+
+/// Parses the output of `reg query` into the values of every listed key, by key path.
+pub(crate) fn parse_query(output: &str) -> BTreeMap<String, RegistryKey> {
+    let mut result: BTreeMap<String, RegistryKey> = BTreeMap::new();
+    let mut current = None;
+
+    for line in output.lines().map(|line| line.trim_end_matches('\r')) {
+        if line.starts_with("HKEY_") {
+            current = Some(line.trim().to_string());
+            continue;
+        }
+
+        // Values are printed as `    Name    REG_TYPE    Data`, the data can contain spaces.
+        let mut parts = line.trim().splitn(3, "    ");
+
+        if let (Some(key), Some(name), Some(kind)) = (current.as_ref(), parts.next(), parts.next())
+        {
+            if kind.starts_with("REG_") {
+                let data = parts.next().unwrap_or_default().trim().to_string();
+                result
+                    .entry(key.clone())
+                    .or_default()
+                    .insert(name.to_string(), data);
+            }
+        }
+    }
+
+    result
+}
+
+/// Queries a registry value, of the key itself or with `recursive` of every subkey.
+/// Returns an empty map if the value does not exist or `reg` is not available.
+pub(crate) fn query(
+    provider: &dyn HardwareProvider,
+    key: &str,
+    value: &str,
+    recursive: bool,
+) -> BTreeMap<String, RegistryKey> {
+    let mut args = vec!["query", key, "/v", value];
+
+    if recursive {
+        args.push("/s");
+    }
+
+    provider
+        .run_command("reg", &args)
+        .map(|output| parse_query(&output))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        let output = "\r\nHKEY_LOCAL_MACHINE\\SOFTWARE\\Test\\0000\r\n    \
+                      ProviderName    REG_SZ    Advanced Micro Devices, Inc.\r\n    \
+                      DriverVersion    REG_SZ    31.0.21912.14\r\n\r\n\
+                      HKEY_LOCAL_MACHINE\\SOFTWARE\\Test\\0001\r\n    \
+                      Enabled    REG_DWORD    0x1\r\n\r\n\
+                      End of search: 3 match(es) found.\r\n";
+
+        let keys = parse_query(output);
+
+        assert_eq!(keys.len(), 2);
+        assert_eq!(
+            keys["HKEY_LOCAL_MACHINE\\SOFTWARE\\Test\\0000"]["ProviderName"],
+            "Advanced Micro Devices, Inc."
+        );
+        assert_eq!(
+            keys["HKEY_LOCAL_MACHINE\\SOFTWARE\\Test\\0001"]["Enabled"],
+            "0x1"
+        );
+        assert!(parse_query(
+            "ERROR: The system was unable to find the specified registry key or value."
+        )
+        .is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+use crate::hash::to_hex;
+use crate::{is_metadata, HardwareProvider, HashAlgorithm, Identifier, SysinfoProvider, LABEL_KEY};
+
+/// Escapes the characters with a meaning in HTML text and attribute values.
+pub(crate) fn escape_html(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+impl Identifier {
+    /// Builds a self-contained HTML fragment showing the components of the Identifier in a table
+    /// and its hash in a code block, for web-based license portals.
+    ///
+    /// The fragment is a single `<div class="uniqueid-report">` with inline styles only, no scripts
+    /// and every collected value escaped, so it is safe to embed in a larger page.
+    /// # Arguments
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{FakeData, FakeProvider, HashAlgorithm, IdentifierBuilder, IdentifierType};
+    ///
+    /// let provider = FakeProvider::new(FakeData {
+    ///     total_memory: 1024,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    ///
+    /// let html = builder.build().build_html_report_with(&provider, HashAlgorithm::SHA3_256);
+    ///
+    /// assert!(html.starts_with("<div class=\"uniqueid-report\">"));
+    /// assert!(html.contains("<td>RAM</td><td>t</td><td>1024</td>"));
+    /// ```
+    pub fn build_html_report(&self, algorithm: HashAlgorithm) -> String {
+        self.build_html_report_with(&SysinfoProvider::new(), algorithm)
+    }
+
+    /// Builds the HTML report of the Identifier from the data of the given provider, see
+    /// `Identifier::build_html_report`.
+    /// # Arguments
+    /// * `provider` - The HardwareProvider to collect the data from, shared by all components.
+    /// * `algorithm` - The HashAlgorithm the Identifier will be hashed with.
+    pub fn build_html_report_with(
+        &self,
+        provider: &dyn HardwareProvider,
+        algorithm: HashAlgorithm,
+    ) -> String {
+        const CELL: &str = "border:1px solid #ccc;padding:2px 6px;text-align:left";
+
+        let mut result = String::from("<div class=\"uniqueid-report\">\n");
+
+        if let Some(name) = &self.name {
+            result.push_str(&format!("<h3>{}</h3>\n", escape_html(name)));
+        }
+        if let Some(description) = &self.description {
+            result.push_str(&format!("<p>{}</p>\n", escape_html(description)));
+        }
+
+        result.push_str("<table style=\"border-collapse:collapse\">\n");
+        result.push_str(&format!(
+            "<thead><tr><th style=\"{0}\">Component</th><th style=\"{0}\">Key</th>\
+             <th style=\"{0}\">Value</th></tr></thead>\n<tbody>\n",
+            CELL
+        ));
+
+        for i in &self.data {
+            for block in i.collect_with(provider, &self.options) {
+                let mut component = block.identifier.as_str().to_string();
+
+                if let Some(label) = block.data.iter().find(|item| item.key == LABEL_KEY) {
+                    component.push_str(&format!(" [{}]", label.value));
+                }
+
+                for item in block.data.iter().filter(|item| !is_metadata(&item.key)) {
+                    result.push_str(&format!(
+                        "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                        escape_html(&component),
+                        escape_html(&item.key),
+                        escape_html(&item.value)
+                    ));
+                }
+            }
+        }
+
+        result.push_str("</tbody>\n</table>\n");
+        result.push_str(&format!(
+            "<p>{}</p>\n<pre><code>{}</code></pre>\n</div>\n",
+            algorithm.as_str(),
+            to_hex(&self.hash_with(provider, algorithm))
+        ));
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierBuilder, IdentifierType};
+
+    #[test]
+    fn test_build_html_report() {
+        let provider = FakeProvider::new(FakeData {
+            total_memory: 1024,
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.name("<script>alert(1)</script>");
+        builder.add_with_label(IdentifierType::RAM, "main");
+        builder
+            .add_custom("SITE", &[("n", "O'Brien & \"Sons\" <b>")])
+            .unwrap();
+        let identifier = builder.build();
+
+        let html = identifier.build_html_report_with(&provider, HashAlgorithm::SHA3_256);
+
+        assert!(html.contains("<h3>&lt;script&gt;alert(1)&lt;/script&gt;</h3>"));
+        assert!(html.contains("<tr><td>RAM [main]</td><td>t</td><td>1024</td></tr>"));
+        assert!(html.contains("<td>O&#39;Brien &amp; &quot;Sons&quot; &lt;b&gt;</td>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains(&format!(
+            "<pre><code>{}</code></pre>",
+            to_hex(&identifier.hash_with(&provider, HashAlgorithm::SHA3_256))
+        )));
+    }
+
+    #[test]
+    fn test_build_html_report_description() {
+        let provider = FakeProvider::new(FakeData {
+            total_memory: 1024,
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.name("web-01");
+        builder.with_description("License <v2> fingerprint");
+        builder.add(IdentifierType::RAM);
+        let identifier = builder.build();
+
+        let html = identifier.build_html_report_with(&provider, HashAlgorithm::SHA3_256);
+
+        assert!(html.contains("<h3>web-01</h3>\n<p>License &lt;v2&gt; fingerprint</p>\n"));
+        assert_eq!(
+            identifier.to_pretty_string_with(&provider),
+            "web-01\n  License <v2> fingerprint\n  RAM: t=1024\n"
+        );
+        assert_eq!(
+            identifier.hash_with(&provider, HashAlgorithm::SHA3_256),
+            Identifier {
+                description: None,
+                ..identifier.clone()
+            }
+            .hash_with(&provider, HashAlgorithm::SHA3_256)
+        );
+    }
+}
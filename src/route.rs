@@ -0,0 +1,139 @@
+use std::net::Ipv4Addr;
+
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// The IPv4 routing table of the kernel on Linux.
+const LINUX_ROUTE_FILE: &str = "/proc/net/route";
+
+/// The flag of a route that is up. (RTF_UP)
+const RTF_UP: u32 = 0x1;
+
+/// A struct representing a default route of the routing table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DefaultRoute {
+    /// The name of the output interface. (e.g. eth0)
+    pub interface: String,
+    /// The IPv4 address of the gateway.
+    pub gateway: Ipv4Addr,
+    /// The metric of the route, lower is preferred.
+    pub metric: u32,
+}
+
+/// Parses an address of `/proc/net/route`, hex encoded in network byte order as a little-endian
+/// number. (0101A8C0 -> 192.168.1.1)
+fn parse_route_address(hex: &str) -> Option<Ipv4Addr> {
+    u32::from_str_radix(hex, 16)
+        .ok()
+        .map(|address| Ipv4Addr::from(address.to_le_bytes()))
+}
+
+/// Parses the default routes (destination and mask 0.0.0.0) that are up from `/proc/net/route`,
+/// sorted by metric and interface.
+pub(crate) fn parse_default_routes(table: &str) -> Vec<DefaultRoute> {
+    let mut routes: Vec<DefaultRoute> = table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [interface, destination, gateway, flags, _, _, metric, mask, ..] = fields[..]
+            else {
+                return None;
+            };
+
+            let flags = u32::from_str_radix(flags, 16).ok()?;
+            let default = parse_route_address(destination)?.is_unspecified()
+                && parse_route_address(mask)?.is_unspecified();
+
+            if !default || flags & RTF_UP == 0 {
+                return None;
+            }
+
+            Some(DefaultRoute {
+                interface: interface.to_string(),
+                gateway: parse_route_address(gateway)?,
+                metric: metric.parse().ok()?,
+            })
+        })
+        .collect();
+
+    routes.sort_by(|a, b| (a.metric, &a.interface).cmp(&(b.metric, &b.interface)));
+    routes
+}
+
+impl IdentifierTypeDataList {
+    /// Collects the default routes: one block per route with its gateway and output interface,
+    /// preferred route first. Only collected on Linux.
+    pub(crate) fn collect_routing_table(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        if !cfg!(target_os = "linux") {
+            return Vec::new();
+        }
+
+        let table = provider
+            .read_to_string(LINUX_ROUTE_FILE)
+            .unwrap_or_default();
+
+        parse_default_routes(&table)
+            .into_iter()
+            .map(|route| {
+                let mut block = IdentifierTypeDataList::new(IdentifierType::ROUTING_TABLE);
+                block.push("g", &route.gateway.to_string());
+                block.push("i", &route.interface);
+                block
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierOptions, UniqueIdError};
+
+    const NET_ROUTE: &str = include_str!("../tests/fixtures/proc/net_route.txt");
+
+    #[test]
+    fn test_parse_default_routes() {
+        assert_eq!(
+            parse_default_routes(NET_ROUTE),
+            vec![
+                DefaultRoute {
+                    interface: "eth0".to_string(),
+                    gateway: Ipv4Addr::new(192, 168, 1, 1),
+                    metric: 100,
+                },
+                DefaultRoute {
+                    interface: "wlan0".to_string(),
+                    gateway: Ipv4Addr::new(192, 168, 1, 254),
+                    metric: 600,
+                },
+            ]
+        );
+        assert_eq!(parse_default_routes(""), vec![]);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_routing_table() {
+        let data = IdentifierTypeDataList::new(IdentifierType::ROUTING_TABLE);
+        let options = IdentifierOptions::default();
+
+        let provider = FakeProvider::new(FakeData {
+            files: [(LINUX_ROUTE_FILE.to_string(), NET_ROUTE.as_bytes().to_vec())].into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            data.build_with(&provider, &options),
+            "ROUTING_TABLE(g=192.168.1.1, i=eth0)ROUTING_TABLE(g=192.168.1.254, i=wlan0)"
+        );
+        assert_eq!(
+            data.try_collect_with(&FakeProvider::default(), &options),
+            Err(UniqueIdError::SourceUnavailable(
+                IdentifierType::ROUTING_TABLE
+            ))
+        );
+    }
+}
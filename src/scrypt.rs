@@ -0,0 +1,149 @@
+use ::scrypt::Params;
+
+use crate::hash::{kdf_salt, to_hex};
+use crate::{HashAlgorithm, Identifier};
+
+/// The length of the derived key in bytes.
+const KEY_LENGTH: usize = 64;
+
+/// Derives a key with scrypt. (RFC 7914)
+///
+/// Panics if `n` is not a power of two greater than 1 or `r` or `p` is 0.
+pub(crate) fn scrypt(
+    password: &[u8],
+    salt: &[u8],
+    n: u32,
+    r: u32,
+    p: u32,
+    length: usize,
+) -> Vec<u8> {
+    assert!(
+        n > 1 && n.is_power_of_two(),
+        "n must be a power of two greater than 1"
+    );
+    assert!(r > 0 && p > 0, "r and p must be greater than 0");
+
+    let params = Params::new(n.trailing_zeros() as u8, r, p).expect("invalid scrypt parameters");
+
+    let mut key = vec![0; length];
+    ::scrypt::scrypt(password, salt, &params, &mut key).expect("invalid key length");
+    key
+}
+
+impl Identifier {
+    /// Applies scrypt to the SHA3-512 hash of the Identifier and returns the hex encoded 512 bit
+    /// key, so every guess of a brute-force search costs `128 * n * r` bytes of memory.
+    ///
+    /// The salt is `uniqueid` followed by the name of the Identifier, if it has one.
+    /// # Arguments
+    /// * `n` - The CPU and memory cost, a power of two greater than 1. (e.g. 2^15)
+    /// * `r` - The block size. (e.g. 8)
+    /// * `p` - The parallelization. (e.g. 1)
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.name("LIC-2024-0042");
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// let key = identifier.build_with_scrypt(1024, 8, 1);
+    ///
+    /// assert_eq!(key.len(), 128);
+    /// assert_eq!(key, identifier.build_with_scrypt(1024, 8, 1));
+    /// ```
+    /// # Panics
+    /// Panics if `n` is not a power of two greater than 1, or `r` or `p` is 0.
+    pub fn build_with_scrypt(&self, n: u32, r: u32, p: u32) -> String {
+        to_hex(&scrypt(
+            &self.hash(HashAlgorithm::SHA3_512),
+            &kdf_salt(self.name.as_deref()),
+            n,
+            r,
+            p,
+            KEY_LENGTH,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IdentifierType, IdentifierTypeDataList};
+
+    #[test]
+    fn test_scrypt() {
+        // RFC 7914, section 12
+        assert_eq!(
+            to_hex(&scrypt(b"", b"", 16, 1, 1, 64)),
+            "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442\
+             fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906"
+        );
+        assert_eq!(
+            to_hex(&scrypt(b"password", b"NaCl", 1024, 8, 16, 64)),
+            "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b373162\
+             2eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640"
+        );
+    }
+
+    #[test]
+    fn test_build_with_scrypt() {
+        let mut ram = IdentifierTypeDataList::new(IdentifierType::RAM);
+        ram.push("t", "1024");
+
+        let unnamed = Identifier {
+            data: vec![ram],
+            ..Default::default()
+        };
+        let named = Identifier {
+            name: Some("LIC-1".to_string()),
+            ..unnamed.clone()
+        };
+        let named_uniqueid = Identifier {
+            name: Some("uniqueid".to_string()),
+            ..unnamed.clone()
+        };
+
+        assert_eq!(
+            named.build_with_scrypt(16, 1, 1),
+            to_hex(&scrypt(
+                &named.hash(HashAlgorithm::SHA3_512),
+                b"uniqueidLIC-1",
+                16,
+                1,
+                1,
+                64
+            ))
+        );
+        assert_eq!(
+            unnamed.build_with_scrypt(16, 1, 1),
+            to_hex(&scrypt(
+                &unnamed.hash(HashAlgorithm::SHA3_512),
+                b"uniqueid",
+                16,
+                1,
+                1,
+                64
+            ))
+        );
+        assert_ne!(
+            named.build_with_scrypt(16, 1, 1),
+            unnamed.build_with_scrypt(16, 1, 1)
+        );
+        assert_ne!(
+            named_uniqueid.build_with_scrypt(16, 1, 1),
+            unnamed.build_with_scrypt(16, 1, 1)
+        );
+        assert_ne!(
+            unnamed.build_with_scrypt(16, 1, 1),
+            unnamed.build_with_scrypt(32, 1, 1)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "power of two")]
+    fn test_scrypt_invalid_n() {
+        Identifier::default().build_with_scrypt(1000, 8, 1);
+    }
+}
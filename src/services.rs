@@ -0,0 +1,157 @@
+use crate::hash::hash_value;
+use crate::{registry, HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// The registry key holding one subkey per service on Windows.
+const SERVICES_KEY: &str = "HKLM\\SYSTEM\\CurrentControlSet\\Services";
+
+/// The `Start` value of the services started automatically at boot on Windows.
+const AUTOMATIC_START: &str = "0x2";
+
+/// Parses the enabled services listed by `systemctl list-unit-files --state=enabled`.
+/// The header, the footer and units of other types, like timers and sockets, are skipped.
+fn parse_unit_files(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (unit, state) = (fields.next()?, fields.next()?);
+
+            (unit.ends_with(".service") && state == "enabled").then(|| unit.to_string())
+        })
+        .collect()
+}
+
+/// Returns the services started automatically by Windows from the `Start` values of the
+/// services key. Values of nested keys, e.g. the `Parameters` of a service, are skipped.
+fn enabled_windows_services(provider: &dyn HardwareProvider) -> Vec<String> {
+    let prefix = format!(
+        "{}\\",
+        SERVICES_KEY.replacen("HKLM", "HKEY_LOCAL_MACHINE", 1)
+    );
+
+    registry::query(provider, SERVICES_KEY, "Start", true)
+        .into_iter()
+        .filter_map(|(key, values)| {
+            let name = key.strip_prefix(&prefix)?;
+
+            (!name.contains('\\') && values.get("Start")? == AUTOMATIC_START)
+                .then(|| name.to_lowercase())
+        })
+        .collect()
+}
+
+/// Lists the enabled system services, or an empty list on other platforms.
+fn enabled_services(provider: &dyn HardwareProvider) -> Vec<String> {
+    if cfg!(target_os = "linux") {
+        provider
+            .run_command("systemctl", &["list-unit-files", "--state=enabled"])
+            .map(|output| parse_unit_files(&output))
+            .unwrap_or_default()
+    } else if cfg!(windows) {
+        enabled_windows_services(provider)
+    } else {
+        Vec::new()
+    }
+}
+
+impl IdentifierTypeDataList {
+    /// Collects a hash of the enabled system services, or nothing without any.
+    pub(crate) fn collect_installed_services(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        let mut services = enabled_services(provider);
+
+        if services.is_empty() {
+            return Vec::new();
+        }
+
+        services.sort();
+        services.dedup();
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::INSTALLED_SERVICES);
+        block.push("n", &services.len().to_string());
+        block.push("h", &hash_value(services.join("\n")));
+        vec![block]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierOptions};
+
+    const UNIT_FILES: &str = "\
+UNIT FILE                          STATE   PRESET
+ssh.service                        enabled enabled
+cron.service                       enabled enabled
+getty@.service                     enabled enabled
+remote-fs.target                   enabled enabled
+apt-daily.timer                    enabled enabled
+
+5 unit files listed.
+";
+
+    #[test]
+    fn test_parse_unit_files() {
+        assert_eq!(
+            parse_unit_files(UNIT_FILES),
+            vec!["ssh.service", "cron.service", "getty@.service"]
+        );
+        assert!(parse_unit_files("0 unit files listed.\n").is_empty());
+    }
+
+    #[test]
+    fn test_enabled_windows_services() {
+        let output = "\r\nHKEY_LOCAL_MACHINE\\SYSTEM\\CurrentControlSet\\Services\\Dhcp\r\n    \
+                      Start    REG_DWORD    0x2\r\n\r\n\
+                      HKEY_LOCAL_MACHINE\\SYSTEM\\CurrentControlSet\\Services\\Fax\r\n    \
+                      Start    REG_DWORD    0x3\r\n\r\n\
+                      HKEY_LOCAL_MACHINE\\SYSTEM\\CurrentControlSet\\Services\\Dhcp\\Parameters\r\n    \
+                      Start    REG_DWORD    0x2\r\n\r\n\
+                      HKEY_LOCAL_MACHINE\\SYSTEM\\CurrentControlSet\\Services\\EventLog\r\n    \
+                      Start    REG_DWORD    0x2\r\n\r\n\
+                      End of search: 4 match(es) found.\r\n";
+        let provider = FakeProvider::new(FakeData {
+            commands: [(
+                format!("reg query {} /v Start /s", SERVICES_KEY),
+                output.to_string(),
+            )]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            enabled_windows_services(&provider),
+            vec!["dhcp", "eventlog"]
+        );
+        assert!(enabled_windows_services(&FakeProvider::default()).is_empty());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_installed_services() {
+        let data = IdentifierTypeDataList::new(IdentifierType::INSTALLED_SERVICES);
+        let options = IdentifierOptions::default();
+
+        let provider = FakeProvider::new(FakeData {
+            commands: [(
+                "systemctl list-unit-files --state=enabled".to_string(),
+                UNIT_FILES.to_string(),
+            )]
+            .into(),
+            ..Default::default()
+        });
+        assert_eq!(
+            data.build_with(&provider, &options),
+            format!(
+                "INSTALLED_SERVICES(n=3, h={})",
+                hash_value("cron.service\ngetty@.service\nssh.service")
+            )
+        );
+
+        assert!(data
+            .collect_with(&FakeProvider::default(), &options)
+            .is_empty());
+    }
+}
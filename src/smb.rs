@@ -0,0 +1,149 @@
+use crate::hash::hash_value;
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// The configuration file of Samba on Linux.
+const SMB_CONF: &str = "/etc/samba/smb.conf";
+
+/// Parses the shares of a Samba configuration as (name, path) pairs, sorted by name.
+///
+/// Every section except `[global]` is a share. Share names are case-insensitive and lowercased,
+/// shares without a `path` (e.g. `[homes]`) have an empty path. `directory` is a synonym of `path`.
+fn parse_smb_conf(conf: &str) -> Vec<(String, String)> {
+    let mut shares: Vec<(String, String)> = Vec::new();
+
+    for line in conf.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(section) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            shares.push((section.trim().to_lowercase(), String::new()));
+            continue;
+        }
+
+        let (Some((key, value)), Some(share)) = (line.split_once('='), shares.last_mut()) else {
+            continue;
+        };
+
+        if matches!(key.trim().to_lowercase().as_str(), "path" | "directory") {
+            share.1 = value.trim().to_string();
+        }
+    }
+
+    shares.retain(|(name, _)| name != "global");
+    shares.sort();
+    shares.dedup_by(|a, b| a.0 == b.0);
+    shares
+}
+
+impl IdentifierTypeDataList {
+    /// Collects a hash of the configured Samba shares, or nothing without a share.
+    pub(crate) fn collect_smb_shares(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        if !cfg!(target_os = "linux") {
+            return Vec::new();
+        }
+
+        let shares = provider
+            .read_to_string(SMB_CONF)
+            .map(|conf| parse_smb_conf(&conf))
+            .unwrap_or_default();
+
+        if shares.is_empty() {
+            return Vec::new();
+        }
+
+        let list = shares
+            .iter()
+            .map(|(name, path)| format!("{}={}", name, path))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::SMB_SHARES);
+        block.push("n", &shares.len().to_string());
+        block.push("h", &hash_value(list));
+        vec![block]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierOptions};
+
+    const CONF: &str = "\
+# Global parameters
+[global]
+   workgroup = CORP
+   path = /ignored
+
+[Public]
+   path = /srv/samba/public
+   read only = no
+
+; [disabled]
+[homes]
+   browseable = no
+
+[backup]
+   directory = /srv/samba/backup
+";
+
+    #[test]
+    fn test_parse_smb_conf() {
+        assert_eq!(
+            parse_smb_conf(CONF),
+            vec![
+                ("backup".to_string(), "/srv/samba/backup".to_string()),
+                ("homes".to_string(), String::new()),
+                ("public".to_string(), "/srv/samba/public".to_string()),
+            ]
+        );
+        assert!(parse_smb_conf("[global]\n   workgroup = CORP\n").is_empty());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_smb_shares() {
+        let data = IdentifierTypeDataList::new(IdentifierType::SMB_SHARES);
+        let options = IdentifierOptions::default();
+        let provider = FakeProvider::new(FakeData {
+            files: [(SMB_CONF.to_string(), CONF.as_bytes().to_vec())].into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            data.build_with(&provider, &options),
+            format!(
+                "SMB_SHARES(n=3, h={})",
+                hash_value("backup=/srv/samba/backup\nhomes=\npublic=/srv/samba/public")
+            )
+        );
+
+        // The order of the sections does not matter, their content does.
+        let expected = data.build_with(&provider, &options);
+        let (head, backup) = CONF.split_at(CONF.find("[backup]").unwrap());
+        let reordered = format!("{}{}", backup, head);
+        provider.update(|data| {
+            data.files
+                .insert(SMB_CONF.to_string(), reordered.as_bytes().to_vec());
+        });
+        assert_eq!(data.build_with(&provider, &options), expected);
+
+        let moved = CONF.replace("/srv/samba/public", "/srv/public");
+        provider.update(|data| {
+            data.files
+                .insert(SMB_CONF.to_string(), moved.as_bytes().to_vec());
+        });
+        assert_ne!(data.build_with(&provider, &options), expected);
+
+        assert!(data
+            .collect_with(&FakeProvider::default(), &options)
+            .is_empty());
+    }
+}
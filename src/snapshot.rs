@@ -0,0 +1,240 @@
+use crate::{
+    FakeProvider, HardwareProvider, Identifier, IdentifierDiff, IdentifierTypeDataList,
+    RawComponent, StringNormalization, SysinfoProvider, FORMAT_VERSION,
+};
+
+/// The format version of snapshots stored before the version was recorded.
+#[cfg(feature = "serde")]
+fn legacy_format_version() -> u32 {
+    1
+}
+
+/// A struct representing an Identifier together with the data collected for it at a point in time.
+/// With the `serde` feature a Snapshot can be stored and compared against the machine later on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot {
+    /// The Identifier the data was collected for, used to collect the data again.
+    pub identifier: Identifier,
+    /// The collected components, one per block.
+    pub components: Vec<IdentifierTypeDataList>,
+    /// The SHA3-512 hash of the Identifier. (Identifier::to_string(true))
+    pub hash: String,
+    /// The format version the hash was built with, see `FORMAT_VERSION`.
+    #[cfg_attr(feature = "serde", serde(default = "legacy_format_version"))]
+    pub format_version: u32,
+}
+
+impl Snapshot {
+    /// Collects the data of the Identifier from the system and creates a new Snapshot object.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType, Snapshot};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    ///
+    /// let snapshot = Snapshot::new(&builder.build());
+    ///
+    /// assert_eq!(snapshot.components.len(), 1);
+    /// ```
+    pub fn new(identifier: &Identifier) -> Self {
+        Self::new_with(identifier, &SysinfoProvider::new())
+    }
+
+    /// Collects the data of the Identifier from the given provider and creates a new Snapshot object.
+    pub fn new_with(identifier: &Identifier, provider: &dyn HardwareProvider) -> Self {
+        let components = identifier
+            .data
+            .iter()
+            .flat_map(|i| i.collect_with(provider, &identifier.options))
+            .collect();
+
+        Snapshot {
+            identifier: identifier.clone(),
+            components,
+            hash: identifier.to_string_with(provider, true),
+            format_version: identifier.options.string_normalization.format_version(),
+        }
+    }
+
+    /// Returns the collected components as (type, key-value map) tuples.
+    pub fn raw_components(&self) -> Vec<RawComponent> {
+        self.components
+            .iter()
+            .map(|block| {
+                let map = block
+                    .data
+                    .iter()
+                    .map(|item| (item.key.clone(), item.value.clone()))
+                    .collect();
+                (block.identifier.clone(), map)
+            })
+            .collect()
+    }
+
+    /// Returns true if the other Snapshot has the same hash. If the Snapshots were built with
+    /// different format versions, the hash of the older one is rebuilt from its components in the
+    /// version of the newer one, or the other way round where possible.
+    pub fn matches(&self, other: &Snapshot) -> bool {
+        other.hash_for_version(self.format_version).as_ref() == Some(&self.hash)
+            || self.hash_for_version(other.format_version).as_ref() == Some(&other.hash)
+    }
+
+    /// Returns the hash of the collected components in the given format version, or None if they
+    /// can not be rebuilt in that version.
+    fn hash_for_version(&self, version: u32) -> Option<String> {
+        if version == self.format_version {
+            return Some(self.hash.clone());
+        }
+
+        // The collected values can be normalized, but not recovered from the normalized ones.
+        let normalize = match (self.format_version, version) {
+            (1 | 2, 1 | 2) => false,
+            (1 | 2, FORMAT_VERSION) => true,
+            _ => return None,
+        };
+
+        // The components are already populated, so they are rendered without collecting again.
+        let mut identifier = Identifier {
+            name: self.identifier.name.clone(),
+            data: self.components.clone(),
+            options: self.identifier.options.clone(),
+            description: self.identifier.description.clone(),
+        };
+        let provider = FakeProvider::default();
+
+        if normalize {
+            identifier.options.string_normalization = StringNormalization::Strict;
+            for block in &mut identifier.data {
+                block.normalize_values(&identifier.options);
+            }
+        }
+
+        match version {
+            1 => Some(identifier.to_string_v1_with(&provider, true)),
+            2 => {
+                identifier.options.string_normalization = StringNormalization::Legacy;
+                Some(identifier.to_string_with(&provider, true))
+            }
+            _ => Some(identifier.to_string_with(&provider, true)),
+        }
+    }
+
+    /// Returns the differences between the components of this Snapshot and a newer Snapshot.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{FakeData, FakeProvider, IdentifierBuilder, IdentifierType, Snapshot};
+    ///
+    /// let provider = FakeProvider::new(FakeData {
+    ///     total_memory: 1024,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::RAM);
+    /// let identifier = builder.build();
+    ///
+    /// let old = Snapshot::new_with(&identifier, &provider);
+    /// provider.update(|data| data.total_memory = 2048);
+    /// let new = Snapshot::new_with(&identifier, &provider);
+    ///
+    /// assert!(!old.matches(&new));
+    /// assert_eq!(old.diff(&new).changed.len(), 1);
+    /// ```
+    pub fn diff(&self, other: &Snapshot) -> IdentifierDiff {
+        IdentifierDiff::new(&self.raw_components(), &other.raw_components())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, IdentifierBuilder, IdentifierType, ProcessorInfo};
+
+    #[test]
+    fn test_matches_legacy_snapshot() {
+        let provider = FakeProvider::new(FakeData {
+            total_memory: 1024,
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.name("test");
+        builder.add(IdentifierType::RAM);
+        let identifier = builder.build();
+
+        let mut legacy = Snapshot::new_with(&identifier, &provider);
+        legacy.hash = identifier.to_string_v1_with(&provider, true);
+        legacy.format_version = 1;
+
+        let current = Snapshot::new_with(&identifier, &provider);
+
+        assert_ne!(legacy.hash, current.hash);
+        assert!(legacy.matches(&current));
+        assert!(current.matches(&legacy));
+
+        // Version 2 hashes are built without the strict normalization.
+        let mut unnormalized = identifier.clone();
+        unnormalized.options.string_normalization = StringNormalization::Legacy;
+        let unnormalized = Snapshot::new_with(&unnormalized, &provider);
+
+        assert_eq!(unnormalized.format_version, 2);
+        assert_eq!(current.format_version, FORMAT_VERSION);
+        assert_ne!(unnormalized.hash, current.hash);
+        assert!(unnormalized.matches(&current));
+        assert!(current.matches(&unnormalized));
+
+        provider.update(|data| data.total_memory = 2048);
+        assert!(!legacy.matches(&Snapshot::new_with(&identifier, &provider)));
+
+        // A hash in an unknown format version can not be rebuilt.
+        let mut future = current.clone();
+        future.format_version = FORMAT_VERSION + 1;
+        assert!(!future.matches(&current));
+    }
+
+    #[test]
+    fn test_matches_legacy_snapshot_normalized() {
+        let provider = FakeProvider::new(FakeData {
+            processors: vec![ProcessorInfo {
+                brand: "Intel(R) Core(TM) i7-8750H CPU @ 2.20GHz".to_string(),
+                vendor: "GenuineIntel".to_string(),
+                frequency: 2208,
+            }],
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder.name("test");
+        builder.add(IdentifierType::CPU);
+        let identifier = builder.build();
+
+        // Snapshots of version 1 and 2 hold the values as collected before the normalization.
+        let mut unnormalized = identifier.clone();
+        unnormalized.options.string_normalization = StringNormalization::Legacy;
+        let mut legacy = Snapshot::new_with(&unnormalized, &provider);
+        legacy.hash = identifier.to_string_v1_with(&provider, true);
+        legacy.format_version = 1;
+        let unnormalized = Snapshot::new_with(&unnormalized, &provider);
+
+        let current = Snapshot::new_with(&identifier, &provider);
+        assert_eq!(
+            current.components[0].data[0].value,
+            "intel core i7-8750h cpu @ 2.20ghz"
+        );
+
+        for old in [&legacy, &unnormalized] {
+            assert!(old.matches(&current));
+            assert!(current.matches(old));
+        }
+        assert!(legacy.matches(&unnormalized));
+
+        // The normalized values of the current Snapshot can not be turned back.
+        assert_eq!(current.hash_for_version(1), None);
+        assert_eq!(current.hash_for_version(2), None);
+
+        provider.update(|data| data.processors[0].frequency = 3000);
+        assert!(!legacy.matches(&Snapshot::new_with(&identifier, &provider)));
+    }
+}
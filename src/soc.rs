@@ -0,0 +1,112 @@
+use crate::HardwareProvider;
+
+/// The serial number and model of an ARM system on a chip, read on boards like the Raspberry Pi
+/// where the CPU brand and frequency are the same for every board.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct SocInfo {
+    /// The serial number, lowercase. (e.g. 10000000a3b4c5d6)
+    pub serial: Option<String>,
+    /// The model of the board. (e.g. Raspberry Pi 4 Model B Rev 1.4)
+    pub model: Option<String>,
+}
+
+/// Returns the value of a `key : value` line of `/proc/cpuinfo`.
+fn cpuinfo_field(cpuinfo: &str, key: &str) -> Option<String> {
+    cpuinfo.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        let value = value.trim();
+
+        (name.trim() == key && !value.is_empty()).then(|| value.to_string())
+    })
+}
+
+/// Normalizes a serial number to lowercase, returning None for a missing (all zeros) serial.
+fn normalize_serial(serial: &str) -> Option<String> {
+    let serial = serial.trim().to_lowercase();
+
+    (!serial.chars().all(|c| c == '0')).then_some(serial)
+}
+
+/// Reads a device tree string property, which is NUL terminated.
+fn device_tree_string(provider: &dyn HardwareProvider, name: &str) -> Option<String> {
+    let value = provider.read_to_string(&format!("/proc/device-tree/{}", name))?;
+    let value = value.trim_end_matches('\0').trim();
+
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Parses the serial number and model of `/proc/cpuinfo`. Only some ARM kernels print them.
+pub(crate) fn parse_cpuinfo(cpuinfo: &str) -> SocInfo {
+    SocInfo {
+        serial: cpuinfo_field(cpuinfo, "Serial").and_then(|serial| normalize_serial(&serial)),
+        model: cpuinfo_field(cpuinfo, "Model"),
+    }
+}
+
+/// Reads the serial number and model of the SoC from `/proc/cpuinfo`, falling back to the
+/// device tree. Boards without a serial number, or any other platform, yield no fields.
+pub(crate) fn soc_info(provider: &dyn HardwareProvider) -> SocInfo {
+    let cpuinfo = provider
+        .read_to_string("/proc/cpuinfo")
+        .map(|cpuinfo| parse_cpuinfo(&cpuinfo))
+        .unwrap_or_default();
+
+    SocInfo {
+        serial: cpuinfo.serial.or_else(|| {
+            device_tree_string(provider, "serial-number").and_then(|s| normalize_serial(&s))
+        }),
+        model: cpuinfo
+            .model
+            .or_else(|| device_tree_string(provider, "model")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider};
+
+    const CPUINFO_RASPBERRY_PI4: &str =
+        include_str!("../tests/fixtures/proc/cpuinfo_raspberry_pi4.txt");
+
+    fn file(path: &str, contents: &str) -> (String, Vec<u8>) {
+        (path.to_string(), contents.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_parse_cpuinfo() {
+        assert_eq!(
+            parse_cpuinfo(CPUINFO_RASPBERRY_PI4),
+            SocInfo {
+                serial: Some("10000000a3b4c5d6".to_string()),
+                model: Some("Raspberry Pi 4 Model B Rev 1.4".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_cpuinfo("processor\t: 0\nSerial\t\t: 0000000000000000\n"),
+            SocInfo::default()
+        );
+    }
+
+    #[test]
+    fn test_soc_info() {
+        let provider = FakeProvider::new(FakeData {
+            files: [
+                file("/proc/cpuinfo", "processor\t: 0\nBogoMIPS\t: 48.00\n"),
+                file("/proc/device-tree/serial-number", "02c00081A1B2C3D4\0"),
+                file("/proc/device-tree/model", "Pine64 RockPro64 v2.1\0"),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            soc_info(&provider),
+            SocInfo {
+                serial: Some("02c00081a1b2c3d4".to_string()),
+                model: Some("Pine64 RockPro64 v2.1".to_string()),
+            }
+        );
+        assert_eq!(soc_info(&FakeProvider::default()), SocInfo::default());
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{is_metadata, HardwareProvider, Identifier, IdentifierType, SysinfoProvider};
+
+/// How stable a collected data point is over the lifetime of a machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Stability {
+    /// Burned into the hardware, only changes when the component is replaced.
+    Hardware,
+    /// Reported by the firmware or the kernel, can change with an update or a setting.
+    Firmware,
+    /// Changes with the software, the configuration or the load of the machine.
+    Volatile,
+}
+
+impl Stability {
+    /// Returns the lowercase name of the Stability.
+    /// # Examples
+    /// ```
+    /// use uniqueid::Stability;
+    ///
+    /// assert_eq!(Stability::Firmware.as_str(), "firmware");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Stability::Hardware => "hardware",
+            Stability::Firmware => "firmware",
+            Stability::Volatile => "volatile",
+        }
+    }
+}
+
+impl IdentifierType {
+    /// Returns the Stability of the data point stored under the given key by this IdentifierType.
+    /// # Arguments
+    /// * `key` - The key of the data point, e.g. `f` for the frequency of the CPU.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierType, Stability};
+    ///
+    /// assert_eq!(IdentifierType::CPU.stability("b"), Stability::Hardware);
+    /// assert_eq!(IdentifierType::CPU.stability("f"), Stability::Volatile);
+    /// assert_eq!(IdentifierType::DISK.stability("mp"), Stability::Volatile);
+    /// ```
+    pub fn stability(&self, key: &str) -> Stability {
+        match self {
+            // The frequency follows the power plan, and the core count and feature flags can be
+            // limited in the firmware settings.
+            IdentifierType::CPU => match key {
+                "f" => Stability::Volatile,
+                "c" | "pc" | "lc" | "fl" => Stability::Firmware,
+                _ => Stability::Hardware,
+            },
+            // The memory reserved by the kernel and the shared video memory vary between boots
+            // and firmware versions.
+            IdentifierType::RAM | IdentifierType::VIRTUAL_ADDRESS_SIZE => Stability::Firmware,
+            IdentifierType::GPU => match key {
+                "m" => Stability::Firmware,
+                _ => Stability::Hardware,
+            },
+            IdentifierType::DISK => match key {
+                "t" => Stability::Firmware,
+                "fs" | "mp" => Stability::Volatile,
+                _ => Stability::Hardware,
+            },
+            IdentifierType::INSTALLED_RAM_MODULES => match key {
+                "f" => Stability::Firmware,
+                _ => Stability::Hardware,
+            },
+            IdentifierType::MEMORY_TOPOLOGY => match key {
+                "m" => Stability::Firmware,
+                _ => Stability::Hardware,
+            },
+            IdentifierType::NET
+            | IdentifierType::BOARD
+            | IdentifierType::PCI
+            | IdentifierType::BATTERY => Stability::Hardware,
+            #[cfg(feature = "tpm")]
+            IdentifierType::TPM => Stability::Hardware,
+            #[cfg(feature = "server")]
+            IdentifierType::POWER_SUPPLY => Stability::Hardware,
+            IdentifierType::BIOS
+            | IdentifierType::VIRT
+            | IdentifierType::CHASSIS_SERIAL
+            | IdentifierType::SYSUUID
+            | IdentifierType::SERIAL
+            | IdentifierType::CPU_MICROCODE => Stability::Firmware,
+            IdentifierType::APP
+            | IdentifierType::ENROLLED_CERTIFICATES
+            | IdentifierType::OS
+            | IdentifierType::MACHINE
+            | IdentifierType::HOST
+            | IdentifierType::CONTAINER
+            | IdentifierType::ROUTING_TABLE
+            | IdentifierType::SYSTEM_ENTROPY
+            | IdentifierType::INSTALLED_PACKAGES_HASH
+            | IdentifierType::Custom(_) => Stability::Volatile,
+            #[cfg(feature = "gpu")]
+            IdentifierType::GRAPHICS_DRIVER_VERSION => Stability::Volatile,
+            #[cfg(windows)]
+            IdentifierType::VOLUME => Stability::Volatile,
+            #[cfg(feature = "cloud")]
+            IdentifierType::CLOUD => Stability::Volatile,
+            #[cfg(feature = "smb")]
+            IdentifierType::SMB_SHARES => Stability::Volatile,
+            #[cfg(feature = "services")]
+            IdentifierType::INSTALLED_SERVICES => Stability::Volatile,
+        }
+    }
+}
+
+impl Identifier {
+    /// Builds the Identifier from the data points tagged `Stability::Hardware` only. Components
+    /// left without any such data point are dropped.
+    /// # Arguments
+    /// * `hash` - Whether the Identifier should be hashed or not.
+    /// # Examples
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::CPU);
+    /// let identifier = builder.build();
+    ///
+    /// assert_eq!(identifier.stable_only(true), identifier.stable_only(true));
+    /// ```
+    pub fn stable_only(&self, hash: bool) -> String {
+        self.stable_only_with(&SysinfoProvider::new(), hash)
+    }
+
+    /// Builds the Identifier from the data points tagged `Stability::Hardware` only, collected from
+    /// the given provider.
+    /// # Arguments
+    /// * `provider` - The HardwareProvider the data is collected from.
+    /// * `hash` - Whether the Identifier should be hashed or not.
+    pub fn stable_only_with(&self, provider: &dyn HardwareProvider, hash: bool) -> String {
+        let data = self
+            .data
+            .iter()
+            .flat_map(|i| i.collect_with(provider, &self.options))
+            .filter_map(|mut block| {
+                let identifier = block.identifier.clone();
+                block.data.retain(|item| {
+                    is_metadata(&item.key) || identifier.stability(&item.key) == Stability::Hardware
+                });
+                block
+                    .data
+                    .iter()
+                    .any(|item| !is_metadata(&item.key))
+                    .then_some(block)
+            })
+            .collect();
+
+        // The blocks are already populated, so they are rendered without collecting again.
+        let identifier = Identifier {
+            name: self.name.clone(),
+            data,
+            options: self.options.clone(),
+            description: self.description.clone(),
+        };
+
+        identifier.to_string_with(provider, hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DiskInfo, FakeData, FakeProvider, IdentifierBuilder, IdentifierOptions, ProcessorInfo,
+    };
+
+    fn provider() -> FakeProvider {
+        FakeProvider::new(FakeData {
+            processors: vec![ProcessorInfo {
+                brand: "Intel(R) Core(TM) i7-8700K".to_string(),
+                vendor: "GenuineIntel".to_string(),
+                frequency: 3700,
+            }],
+            total_memory: 16 * 1024 * 1024 * 1024,
+            disks: vec![DiskInfo {
+                device: "/dev/sda1".to_string(),
+                file_system: "ext4".to_string(),
+                mount_point: "/".to_string(),
+                total_space: 512 * 1024 * 1024 * 1024,
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_stability() {
+        assert_eq!(IdentifierType::CPU.stability("v"), Stability::Hardware);
+        assert_eq!(IdentifierType::CPU.stability("c"), Stability::Firmware);
+        assert_eq!(IdentifierType::RAM.stability("t"), Stability::Firmware);
+        assert_eq!(IdentifierType::DISK.stability("fs"), Stability::Volatile);
+        assert_eq!(IdentifierType::OS.stability("n"), Stability::Volatile);
+        assert_eq!(IdentifierType::BOARD.stability("s"), Stability::Hardware);
+        assert_eq!(
+            IdentifierType::Custom("SITE".to_string()).stability("n"),
+            Stability::Volatile
+        );
+    }
+
+    #[test]
+    fn test_stable_only() {
+        let options = IdentifierOptions {
+            disk_mount_info: true,
+            ..Default::default()
+        };
+
+        let mut builder = IdentifierBuilder::default();
+        builder
+            .options(options)
+            .add(IdentifierType::CPU)
+            .add(IdentifierType::RAM)
+            .add(IdentifierType::DISK);
+        let identifier = builder.build();
+
+        let provider = provider();
+        let stable = identifier.stable_only_with(&provider, false);
+        assert!(stable.contains("b=intel core i7-8700k"));
+        assert!(!stable.contains("f=3700"));
+        assert!(!stable.contains("RAM"));
+        assert!(!stable.contains("mp="));
+
+        // Perturb the frequency, the usable memory and the mount point.
+        let full = identifier.to_string_with(&provider, true);
+        provider.update(|data| {
+            data.processors[0].frequency = 4700;
+            data.total_memory -= 64 * 1024 * 1024;
+            data.disks[0].mount_point = "/mnt".to_string();
+            data.disks[0].file_system = "btrfs".to_string();
+        });
+
+        assert_ne!(identifier.to_string_with(&provider, true), full);
+        assert_eq!(identifier.stable_only_with(&provider, false), stable);
+        assert_eq!(
+            identifier.stable_only_with(&provider, true),
+            identifier.stable_only_with(&provider, true)
+        );
+    }
+}
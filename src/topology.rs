@@ -0,0 +1,480 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::wmi::{self, WmiRecord};
+use crate::HardwareProvider;
+
+/// The directory holding the logical processors on Linux.
+const LINUX_CPU_DIR: &str = "/sys/devices/system/cpu";
+
+/// Counts the distinct physical packages of the logical processors in sysfs.
+pub(crate) fn linux_socket_count(provider: &dyn HardwareProvider) -> Option<usize> {
+    let packages: BTreeSet<String> = provider
+        .read_dir(LINUX_CPU_DIR)
+        .into_iter()
+        .filter(|entry| {
+            entry
+                .strip_prefix("cpu")
+                .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        })
+        .filter_map(|cpu| {
+            provider.read_to_string(&format!(
+                "{}/{}/topology/physical_package_id",
+                LINUX_CPU_DIR, cpu
+            ))
+        })
+        .map(|id| id.trim().to_string())
+        .collect();
+
+    (!packages.is_empty()).then_some(packages.len())
+}
+
+/// Returns the number of CPU sockets (physical packages) of the system, if known.
+pub(crate) fn socket_count(provider: &dyn HardwareProvider) -> Option<usize> {
+    if cfg!(target_os = "windows") {
+        let processors = wmi::query(provider, "Win32_Processor", &["DeviceID"]);
+        (!processors.is_empty()).then_some(processors.len())
+    } else if cfg!(target_os = "macos") {
+        provider
+            .run_command("sysctl", &["-n", "hw.packages"])
+            .and_then(|output| output.trim().parse().ok())
+    } else {
+        linux_socket_count(provider)
+    }
+}
+
+/// A group of logical processors of the same kind on a hybrid CPU, such as the performance or
+/// efficiency cores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CoreCluster {
+    /// The number of logical processors in the cluster.
+    pub cores: usize,
+    /// The maximum frequency of the cluster in MHz, if known.
+    pub max_frequency: Option<u64>,
+}
+
+impl CoreCluster {
+    /// Formats the cluster as `cores@MHz`, or `cores` if the frequency is unknown.
+    pub(crate) fn as_value(&self) -> String {
+        match self.max_frequency {
+            Some(frequency) => format!("{}@{}", self.cores, frequency),
+            None => self.cores.to_string(),
+        }
+    }
+}
+
+/// The PMU devices listing the performance and efficiency cores of Intel hybrid CPUs on Linux.
+const LINUX_INTEL_HYBRID_PMUS: [&str; 2] = ["/sys/devices/cpu_core", "/sys/devices/cpu_atom"];
+
+/// Parses a sysfs CPU list into the CPU numbers. (e.g. 0-7,16)
+pub(crate) fn parse_cpu_list(list: &str) -> Vec<usize> {
+    list.trim()
+        .split(',')
+        .filter_map(|range| match range.split_once('-') {
+            Some((start, end)) => Some((start.parse().ok()?..=end.parse().ok()?).collect()),
+            None => range.parse().ok().map(|cpu| vec![cpu]),
+        })
+        .flatten()
+        .collect()
+}
+
+/// Reads the core clusters of the CPU from sysfs, fastest first.
+///
+/// Intel hybrid CPUs list their performance and efficiency cores in the `cpu_core` and `cpu_atom`
+/// PMU devices. Other CPUs, such as ARM big.LITTLE, are grouped by the maximum frequency of each
+/// logical processor (cpufreq/cpuinfo_max_freq, in kHz). On x86 the frequency is not used, as the
+/// favored cores of Turbo Boost Max 3.0 have a higher maximum frequency on uniform CPUs.
+pub(crate) fn linux_core_clusters(provider: &dyn HardwareProvider) -> Vec<CoreCluster> {
+    let max_frequency = |cpu: usize| {
+        provider
+            .read_to_string(&format!(
+                "{}/cpu{}/cpufreq/cpuinfo_max_freq",
+                LINUX_CPU_DIR, cpu
+            ))
+            .and_then(|frequency| frequency.trim().parse::<u64>().ok())
+            .map(|frequency| frequency / 1000)
+    };
+
+    let hybrid: Vec<Vec<usize>> = LINUX_INTEL_HYBRID_PMUS
+        .iter()
+        .filter_map(|pmu| provider.read_to_string(&format!("{}/cpus", pmu)))
+        .map(|list| parse_cpu_list(&list))
+        .filter(|cpus| !cpus.is_empty())
+        .collect();
+
+    if !hybrid.is_empty() {
+        let mut clusters: Vec<CoreCluster> = hybrid
+            .into_iter()
+            .map(|cpus| CoreCluster {
+                cores: cpus.len(),
+                max_frequency: cpus.into_iter().filter_map(max_frequency).max(),
+            })
+            .collect();
+        clusters.sort_by_key(|cluster| std::cmp::Reverse(cluster.max_frequency));
+        return clusters;
+    }
+
+    if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        return Vec::new();
+    }
+
+    let mut clusters: BTreeMap<u64, usize> = BTreeMap::new();
+    for cpu in provider
+        .read_dir(LINUX_CPU_DIR)
+        .iter()
+        .filter_map(|entry| entry.strip_prefix("cpu")?.parse::<usize>().ok())
+    {
+        if let Some(frequency) = max_frequency(cpu) {
+            *clusters.entry(frequency).or_default() += 1;
+        }
+    }
+
+    clusters
+        .into_iter()
+        .rev()
+        .map(|(frequency, cores)| CoreCluster {
+            cores,
+            max_frequency: Some(frequency),
+        })
+        .collect()
+}
+
+/// Reads the performance levels of Apple silicon (hw.perflevelN.logicalcpu), performance cores
+/// first. The frequencies of the levels are not exposed.
+pub(crate) fn macos_core_clusters(provider: &dyn HardwareProvider) -> Vec<CoreCluster> {
+    let sysctl = |name: &str| {
+        provider
+            .run_command("sysctl", &["-n", name])
+            .and_then(|output| output.trim().parse::<usize>().ok())
+    };
+
+    (0..sysctl("hw.nperflevels").unwrap_or_default())
+        .map_while(|level| sysctl(&format!("hw.perflevel{}.logicalcpu", level)))
+        .map(|cores| CoreCluster {
+            cores,
+            max_frequency: None,
+        })
+        .collect()
+}
+
+/// Returns the core clusters of the CPU, fastest first, or an empty list if unknown. Windows does
+/// not expose the efficiency class of the cores without the native topology API.
+pub(crate) fn core_clusters(provider: &dyn HardwareProvider) -> Vec<CoreCluster> {
+    if cfg!(target_os = "windows") {
+        Vec::new()
+    } else if cfg!(target_os = "macos") {
+        macos_core_clusters(provider)
+    } else {
+        linux_core_clusters(provider)
+    }
+}
+
+/// The cache sizes of a CPU core in KB, None for a missing level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct CacheSizes {
+    pub l1d: Option<u64>,
+    pub l1i: Option<u64>,
+    pub l2: Option<u64>,
+    pub l3: Option<u64>,
+}
+
+impl CacheSizes {
+    /// Returns the known cache sizes as (key, KB) pairs, in a fixed order.
+    pub(crate) fn pairs(&self) -> Vec<(&'static str, u64)> {
+        [
+            ("l1d", self.l1d),
+            ("l1i", self.l1i),
+            ("l2", self.l2),
+            ("l3", self.l3),
+        ]
+        .into_iter()
+        .filter_map(|(key, size)| Some((key, size?)))
+        .collect()
+    }
+
+    /// Sets the size of a cache level of the given type. (Data, Instruction or Unified)
+    fn set(&mut self, level: &str, kind: &str, size: u64) {
+        let slot = match (level, kind) {
+            ("1", "Data") => &mut self.l1d,
+            ("1", "Instruction") => &mut self.l1i,
+            ("2", _) => &mut self.l2,
+            ("3", _) => &mut self.l3,
+            _ => return,
+        };
+
+        slot.get_or_insert(size);
+    }
+}
+
+/// Parses a sysfs cache size in KB. (e.g. 48K, 2048K, 32M)
+fn parse_cache_size(size: &str) -> Option<u64> {
+    let (value, multiplier) = match size.chars().last()? {
+        'K' => (&size[..size.len() - 1], 1),
+        'M' => (&size[..size.len() - 1], 1024),
+        'G' => (&size[..size.len() - 1], 1024 * 1024),
+        _ => (size, 1),
+    };
+
+    value.parse::<u64>().ok().map(|value| value * multiplier)
+}
+
+/// Reads the caches of the first logical processor from sysfs. (index*/level, type and size)
+pub(crate) fn linux_cache_sizes(provider: &dyn HardwareProvider) -> CacheSizes {
+    let dir = format!("{}/cpu0/cache", LINUX_CPU_DIR);
+    let mut caches = CacheSizes::default();
+
+    for index in provider
+        .read_dir(&dir)
+        .iter()
+        .filter(|entry| entry.starts_with("index"))
+    {
+        let read = |file: &str| {
+            provider
+                .read_to_string(&format!("{}/{}/{}", dir, index, file))
+                .map(|value| value.trim().to_string())
+        };
+
+        if let (Some(level), Some(kind), Some(size)) = (read("level"), read("type"), read("size")) {
+            if let Some(size) = parse_cache_size(&size) {
+                caches.set(&level, &kind, size);
+            }
+        }
+    }
+
+    caches
+}
+
+/// Parses the Win32_CacheMemory WMI instances. (Level 3 is L1, 4 is L2 and 5 is L3; CacheType 3 is
+/// instruction, 4 is data; InstalledSize is in KB)
+pub(crate) fn parse_win32_cache_memory(records: &[WmiRecord]) -> CacheSizes {
+    let mut caches = CacheSizes::default();
+
+    for record in records {
+        let field = |name: &str| record.get(name).map(String::as_str).unwrap_or_default();
+
+        let level = match field("Level") {
+            "3" => "1",
+            "4" => "2",
+            "5" => "3",
+            _ => continue,
+        };
+        let kind = match field("CacheType") {
+            "3" => "Instruction",
+            "4" => "Data",
+            _ => "Unified",
+        };
+
+        if let Ok(size) = field("InstalledSize").parse() {
+            caches.set(level, kind, size);
+        }
+    }
+
+    caches
+}
+
+/// Returns the cache sizes of the CPU in KB.
+pub(crate) fn cache_sizes(provider: &dyn HardwareProvider) -> CacheSizes {
+    if cfg!(target_os = "windows") {
+        parse_win32_cache_memory(&wmi::query(
+            provider,
+            "Win32_CacheMemory",
+            &["Level", "CacheType", "InstalledSize"],
+        ))
+    } else if cfg!(target_os = "macos") {
+        // Absent levels, such as L3 on Apple silicon, are unknown to sysctl.
+        let sysctl = |name: &str| {
+            provider
+                .run_command("sysctl", &["-n", name])
+                .and_then(|output| output.trim().parse::<u64>().ok())
+                .filter(|size| *size > 0)
+                .map(|size| size / 1024)
+        };
+
+        CacheSizes {
+            l1d: sysctl("hw.l1dcachesize"),
+            l1i: sysctl("hw.l1icachesize"),
+            l2: sysctl("hw.l2cachesize"),
+            l3: sysctl("hw.l3cachesize"),
+        }
+    } else {
+        linux_cache_sizes(provider)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider};
+
+    #[test]
+    fn test_linux_socket_count() {
+        let file = |cpu: &str, package: &str| {
+            (
+                format!("{}/{}/topology/physical_package_id", LINUX_CPU_DIR, cpu),
+                package.as_bytes().to_vec(),
+            )
+        };
+
+        let provider = FakeProvider::new(FakeData {
+            files: [
+                file("cpu0", "0\n"),
+                file("cpu1", "0\n"),
+                file("cpu2", "1\n"),
+                file("cpu3", "1\n"),
+                (format!("{}/cpufreq/boost", LINUX_CPU_DIR), b"1\n".to_vec()),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(linux_socket_count(&provider), Some(2));
+        assert_eq!(linux_socket_count(&FakeProvider::default()), None);
+    }
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpu_list("\n"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_linux_core_clusters() {
+        let max_freq = |cpu: usize, khz: u64| {
+            (
+                format!("{}/cpu{}/cpufreq/cpuinfo_max_freq", LINUX_CPU_DIR, cpu),
+                format!("{}\n", khz).into_bytes(),
+            )
+        };
+
+        // A Core i5-12600K: 6 performance cores with two threads, two of them favored, and 4
+        // efficiency cores, with the efficiency cores listed first.
+        let mut files: BTreeMap<String, Vec<u8>> = (0..16)
+            .map(|cpu| match cpu {
+                0..=3 => max_freq(cpu, 3_600_000),
+                4..=7 => max_freq(cpu, 4_900_000),
+                _ => max_freq(cpu, 4_800_000),
+            })
+            .collect();
+        files.insert("/sys/devices/cpu_atom/cpus".to_string(), b"0-3\n".to_vec());
+        files.insert("/sys/devices/cpu_core/cpus".to_string(), b"4-15\n".to_vec());
+
+        let provider = FakeProvider::new(FakeData {
+            files,
+            ..Default::default()
+        });
+
+        assert_eq!(
+            linux_core_clusters(&provider),
+            vec![
+                CoreCluster {
+                    cores: 12,
+                    max_frequency: Some(4900),
+                },
+                CoreCluster {
+                    cores: 4,
+                    max_frequency: Some(3600),
+                },
+            ]
+        );
+        assert_eq!(linux_core_clusters(&FakeProvider::default()), vec![]);
+
+        // A Cortex-A76 and A55 big.LITTLE SoC, grouped by frequency on ARM only.
+        let provider = FakeProvider::new(FakeData {
+            files: (0..8)
+                .map(|cpu| max_freq(cpu, if cpu < 4 { 1_800_000 } else { 2_400_000 }))
+                .collect(),
+            ..Default::default()
+        });
+        let expected = if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+            vec![]
+        } else {
+            vec!["4@2400".to_string(), "4@1800".to_string()]
+        };
+
+        assert_eq!(
+            linux_core_clusters(&provider)
+                .iter()
+                .map(CoreCluster::as_value)
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_macos_core_clusters() {
+        let provider = FakeProvider::new(FakeData {
+            commands: [
+                ("sysctl -n hw.nperflevels", "2\n"),
+                ("sysctl -n hw.perflevel0.logicalcpu", "8\n"),
+                ("sysctl -n hw.perflevel1.logicalcpu", "4\n"),
+            ]
+            .into_iter()
+            .map(|(command, output)| (command.to_string(), output.to_string()))
+            .collect(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            macos_core_clusters(&provider)
+                .iter()
+                .map(CoreCluster::as_value)
+                .collect::<Vec<_>>(),
+            vec!["8", "4"]
+        );
+        assert_eq!(macos_core_clusters(&FakeProvider::default()), vec![]);
+    }
+
+    #[test]
+    fn test_linux_cache_sizes() {
+        let mut files = BTreeMap::new();
+        let mut cache = |index: &str, level: &str, kind: &str, size: &str| {
+            for (file, value) in [("level", level), ("type", kind), ("size", size)] {
+                files.insert(
+                    format!("{}/cpu0/cache/{}/{}", LINUX_CPU_DIR, index, file),
+                    format!("{}\n", value).into_bytes(),
+                );
+            }
+        };
+
+        // A Cortex-A72 without L3.
+        cache("index0", "1", "Data", "32K");
+        cache("index1", "1", "Instruction", "48K");
+        cache("index2", "2", "Unified", "1M");
+
+        let provider = FakeProvider::new(FakeData {
+            files,
+            ..Default::default()
+        });
+
+        assert_eq!(
+            linux_cache_sizes(&provider).pairs(),
+            vec![("l1d", 32), ("l1i", 48), ("l2", 1024)]
+        );
+        assert_eq!(linux_cache_sizes(&FakeProvider::default()).pairs(), vec![]);
+    }
+
+    #[test]
+    fn test_parse_win32_cache_memory() {
+        let record = |level: &str, kind: &str, size: &str| {
+            WmiRecord::from([
+                ("Level".to_string(), level.to_string()),
+                ("CacheType".to_string(), kind.to_string()),
+                ("InstalledSize".to_string(), size.to_string()),
+            ])
+        };
+
+        assert_eq!(
+            parse_win32_cache_memory(&[
+                record("5", "5", "16384"),
+                record("3", "4", "384"),
+                record("3", "3", "256"),
+                record("4", "5", "5120"),
+            ]),
+            CacheSizes {
+                l1d: Some(384),
+                l1i: Some(256),
+                l2: Some(5120),
+                l3: Some(16384),
+            }
+        );
+    }
+}
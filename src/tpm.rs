@@ -0,0 +1,118 @@
+#[cfg(target_os = "linux")]
+use tss_esapi::abstraction::ek;
+#[cfg(target_os = "linux")]
+use tss_esapi::interface_types::algorithm::AsymmetricAlgorithm;
+#[cfg(target_os = "linux")]
+use tss_esapi::structures::Public;
+#[cfg(target_os = "linux")]
+use tss_esapi::tcti_ldr::{DeviceConfig, TctiNameConf};
+#[cfg(target_os = "linux")]
+use tss_esapi::Context;
+
+use crate::hash::hash_value;
+use crate::wmi;
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// The TPM resource manager of the kernel, usable by the members of the `tss` group.
+#[cfg(target_os = "linux")]
+const RESOURCE_MANAGER: &str = "/dev/tpmrm0";
+
+/// Reads the public key of the Endorsement Key, or None without a TPM or without the permission
+/// to open it. The TCTI is taken from `TPM2TOOLS_TCTI`, `TCTI` or `TEST_TCTI` if set.
+///
+/// The EK is created from the default RSA 2048 template of the TCG EK Credential Profile, or the
+/// ECC NIST P-256 one if the TPM has no RSA, which derives the same key from the endorsement seed
+/// every time, so it does not have to be persisted.
+#[cfg(target_os = "linux")]
+pub(crate) fn read_endorsement_key() -> Option<Vec<u8>> {
+    let tcti = TctiNameConf::from_environment_variable().unwrap_or_else(|_| {
+        TctiNameConf::Device(RESOURCE_MANAGER.parse::<DeviceConfig>().unwrap_or_default())
+    });
+    let mut context = Context::new(tcti).ok()?;
+
+    [AsymmetricAlgorithm::Rsa, AsymmetricAlgorithm::Ecc]
+        .into_iter()
+        .find_map(|algorithm| {
+            let handle = ek::create_ek_object(&mut context, algorithm, None).ok()?;
+            let public = context.read_public(handle);
+            let _ = context.flush_context(handle.into());
+
+            match public.ok()?.0 {
+                Public::Rsa { unique, .. } => Some(unique.value().to_vec()),
+                Public::Ecc { unique, .. } => {
+                    Some([unique.x().value(), unique.y().value()].concat())
+                }
+                _ => None,
+            }
+        })
+}
+
+/// Reads the public key of the Endorsement Key, or a digest of it, or None without a TPM or
+/// without the permission to use it.
+///
+/// On Linux the public key is read through the TPM Software Stack. On Windows the hex SHA-256
+/// digest of the public key is read with `Get-TpmEndorsementKeyInfo`, which requires administrator
+/// rights. The TPM Base Services API is not called directly, as it is only reachable through
+/// unsafe FFI.
+pub(crate) fn endorsement_key(provider: &dyn HardwareProvider) -> Option<Vec<u8>> {
+    if cfg!(target_os = "windows") {
+        let command = "Get-TpmEndorsementKeyInfo -HashAlgorithm Sha256 | Format-List PublicKeyHash";
+
+        provider
+            .run_command("powershell", &["-NoProfile", "-Command", command])
+            .and_then(|output| {
+                wmi::parse_format_list(&output)
+                    .first()?
+                    .get("PublicKeyHash")
+                    .map(|hash| hash.to_lowercase().into_bytes())
+            })
+            .filter(|hash| !hash.is_empty())
+    } else {
+        provider.tpm_endorsement_key().filter(|key| !key.is_empty())
+    }
+}
+
+impl IdentifierTypeDataList {
+    /// Collects a hash of the public part of the TPM Endorsement Key, or nothing without a TPM, so
+    /// that `try_collect_with` and `Identifier::build_with_optional_fallback` can skip it.
+    pub(crate) fn collect_tpm(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        match endorsement_key(provider) {
+            Some(key) => {
+                let mut block = IdentifierTypeDataList::new(IdentifierType::TPM);
+                block.push("ek", &hash_value(key));
+                vec![block]
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierOptions, UniqueIdError};
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_tpm() {
+        let key = vec![0xc5; 256];
+        let provider = FakeProvider::new(FakeData {
+            tpm_endorsement_key: Some(key.clone()),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::TPM)
+                .build_with(&provider, &IdentifierOptions::default()),
+            format!("TPM(ek={})", hash_value(key))
+        );
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::TPM)
+                .try_collect_with(&FakeProvider::default(), &IdentifierOptions::default()),
+            Err(UniqueIdError::SourceUnavailable(IdentifierType::TPM))
+        );
+    }
+}
@@ -0,0 +1,403 @@
+use std::fmt::Display;
+
+use crate::wmi::{self, WmiRecord};
+use crate::{
+    HardwareProvider, IdentifierBuilder, IdentifierType, IdentifierTypeDataList, SysinfoProvider,
+};
+
+/// The CPUID leaf returning the feature flags, including the hypervisor present bit.
+const SIGNATURE_LEAF: u32 = 1;
+/// The leaf 1 ECX bit set by hypervisors.
+const HYPERVISOR_BIT: u32 = 1 << 31;
+/// The CPUID leaf returning the hypervisor vendor signature.
+const HYPERVISOR_VENDOR_LEAF: u32 = 0x4000_0000;
+/// The Hyper-V CPUID leaf returning the partition privileges.
+const HYPERV_FEATURES_LEAF: u32 = 0x4000_0003;
+/// The Hyper-V privilege only granted to the root partition, which runs on the hardware.
+const HYPERV_CREATE_PARTITIONS: u32 = 1 << 0;
+
+/// The directory exposing the SMBIOS strings on Linux.
+const LINUX_DMI_DIR: &str = "/sys/class/dmi/id";
+
+/// Enum representing the hypervisors and container runtimes detected by `is_virtualized`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VirtKind {
+    /// The Linux Kernel-based Virtual Machine.
+    Kvm,
+    /// QEMU without KVM acceleration.
+    Qemu,
+    /// VMware Workstation, Fusion or ESXi.
+    Vmware,
+    /// Microsoft Hyper-V, including Azure.
+    HyperV,
+    /// The Xen hypervisor.
+    Xen,
+    /// Oracle VirtualBox.
+    VirtualBox,
+    /// A hypervisor not recognized by the crate.
+    OtherHypervisor,
+    /// A Docker container.
+    Docker,
+    /// A Podman container.
+    Podman,
+    /// An LXC container.
+    Lxc,
+    /// A container of another runtime, such as containerd in Kubernetes.
+    OtherContainer,
+}
+
+impl VirtKind {
+    /// Returns the name of the hypervisor or container runtime, as emitted in `VIRT(t=...)`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VirtKind::Kvm => "kvm",
+            VirtKind::Qemu => "qemu",
+            VirtKind::Vmware => "vmware",
+            VirtKind::HyperV => "hyperv",
+            VirtKind::Xen => "xen",
+            VirtKind::VirtualBox => "virtualbox",
+            VirtKind::OtherHypervisor => "hypervisor",
+            VirtKind::Docker => "docker",
+            VirtKind::Podman => "podman",
+            VirtKind::Lxc => "lxc",
+            VirtKind::OtherContainer => "container",
+        }
+    }
+}
+
+impl Display for VirtKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Detects a container from the files container runtimes leave behind.
+pub(crate) fn container(provider: &dyn HardwareProvider) -> Option<VirtKind> {
+    if provider.read_file("/.dockerenv").is_some() {
+        return Some(VirtKind::Docker);
+    }
+
+    if provider.read_file("/run/.containerenv").is_some() {
+        return Some(VirtKind::Podman);
+    }
+
+    // Written by systemd and most runtimes. (e.g. docker, podman, lxc)
+    if let Some(name) = provider.read_to_string("/run/systemd/container") {
+        return Some(match name.trim() {
+            "docker" => VirtKind::Docker,
+            "podman" => VirtKind::Podman,
+            "lxc" | "lxc-libvirt" => VirtKind::Lxc,
+            _ => VirtKind::OtherContainer,
+        });
+    }
+
+    // Only meaningful with cgroup v1, cgroup v2 namespaces show `0::/` inside a container.
+    let cgroup = provider.read_to_string("/proc/1/cgroup")?;
+
+    if cgroup.contains("/docker") {
+        Some(VirtKind::Docker)
+    } else if cgroup.contains("/lxc") {
+        Some(VirtKind::Lxc)
+    } else if cgroup.contains("/kubepods") || cgroup.contains("/containerd") {
+        Some(VirtKind::OtherContainer)
+    } else {
+        None
+    }
+}
+
+/// Detects a hypervisor from its CPUID vendor signature. The root partition of Hyper-V, used by
+/// Windows with virtualization-based security, runs on the hardware and is not reported.
+fn cpuid_hypervisor(provider: &dyn HardwareProvider) -> Option<VirtKind> {
+    let features = provider.cpuid(SIGNATURE_LEAF, 0)?;
+
+    if features.ecx & HYPERVISOR_BIT == 0 {
+        return None;
+    }
+
+    let vendor = provider.cpuid(HYPERVISOR_VENDOR_LEAF, 0)?;
+    let signature: Vec<u8> = [vendor.ebx, vendor.ecx, vendor.edx]
+        .iter()
+        .flat_map(|register| register.to_le_bytes())
+        .collect();
+
+    Some(match &signature[..] {
+        b"KVMKVMKVM\0\0\0" => VirtKind::Kvm,
+        b"TCGTCGTCGTCG" => VirtKind::Qemu,
+        b"VMwareVMware" => VirtKind::Vmware,
+        b"XenVMMXenVMM" => VirtKind::Xen,
+        b"VBoxVBoxVBox" => VirtKind::VirtualBox,
+        b"Microsoft Hv" => {
+            let root = provider
+                .cpuid(HYPERV_FEATURES_LEAF, 0)
+                .is_some_and(|privileges| privileges.ebx & HYPERV_CREATE_PARTITIONS != 0);
+
+            match root {
+                true => return None,
+                false => VirtKind::HyperV,
+            }
+        }
+        _ => VirtKind::OtherHypervisor,
+    })
+}
+
+/// Maps the SMBIOS system manufacturer and product name to a hypervisor.
+pub(crate) fn dmi_hypervisor(vendor: &str, product: &str) -> Option<VirtKind> {
+    let vendor = vendor.trim().to_lowercase();
+    let product = product.trim().to_lowercase();
+
+    if vendor.starts_with("qemu") {
+        Some(VirtKind::Qemu)
+    } else if vendor.starts_with("vmware") {
+        Some(VirtKind::Vmware)
+    } else if vendor == "xen" {
+        Some(VirtKind::Xen)
+    } else if vendor == "innotek gmbh" || product == "virtualbox" {
+        Some(VirtKind::VirtualBox)
+    } else if vendor == "microsoft corporation" && product == "virtual machine" {
+        Some(VirtKind::HyperV)
+    } else {
+        None
+    }
+}
+
+/// Parses the Win32_ComputerSystem WMI instance into its manufacturer and model.
+fn parse_win32_computer_system(records: &[WmiRecord]) -> (String, String) {
+    let field = |name: &str| {
+        records
+            .first()
+            .and_then(|record| record.get(name).cloned())
+            .unwrap_or_default()
+    };
+
+    (field("Manufacturer"), field("Model"))
+}
+
+/// Detects a hypervisor from the SMBIOS system manufacturer, for CPUs without CPUID or hypervisors
+/// hiding their signature.
+fn dmi(provider: &dyn HardwareProvider) -> Option<VirtKind> {
+    let (vendor, product) = if cfg!(target_os = "windows") {
+        parse_win32_computer_system(&wmi::query(
+            provider,
+            "Win32_ComputerSystem",
+            &["Manufacturer", "Model"],
+        ))
+    } else {
+        let read = |file: &str| {
+            provider
+                .read_to_string(&format!("{}/{}", LINUX_DMI_DIR, file))
+                .unwrap_or_default()
+        };
+
+        (read("sys_vendor"), read("product_name"))
+    };
+
+    dmi_hypervisor(&vendor, &product)
+}
+
+/// Detects whether the process runs inside a container or a virtual machine, see `is_virtualized`.
+/// # Arguments
+/// * `provider` - The HardwareProvider to read the CPUID leaves and system files from.
+pub fn is_virtualized_with(provider: &dyn HardwareProvider) -> Option<VirtKind> {
+    container(provider)
+        .or_else(|| cpuid_hypervisor(provider))
+        .or_else(|| dmi(provider))
+}
+
+/// Detects whether the process runs inside a container or a virtual machine.
+///
+/// Containers are detected first, from `/.dockerenv`, `/run/.containerenv`,
+/// `/run/systemd/container` and `/proc/1/cgroup`, then hypervisors from the CPUID hypervisor leaf,
+/// with the `cpuid` feature, and the SMBIOS system manufacturer. Nothing is written and no
+/// elevated privileges are needed.
+///
+/// Hardware values inside a virtual machine or a container are often identical across tenants, so
+/// callers can require additional components when virtualization is detected.
+/// # Examples
+/// ```
+/// use uniqueid::{is_virtualized, IdentifierBuilder, IdentifierType};
+///
+/// let mut builder = IdentifierBuilder::default();
+/// builder.add(IdentifierType::CPU);
+///
+/// if is_virtualized().is_some() {
+///     builder.add(IdentifierType::MACHINE);
+/// }
+/// ```
+pub fn is_virtualized() -> Option<VirtKind> {
+    is_virtualized_with(&SysinfoProvider::new())
+}
+
+impl IdentifierBuilder {
+    /// Adds a new IdentifierType object only on bare metal, skipping it inside a virtual machine
+    /// or a container, see `is_virtualized`.
+    /// # Arguments
+    /// * `identifier` - The IdentifierType to add on bare metal.
+    /// # Examples
+    /// Using the disk on bare metal, and only the CPU in a virtual machine:
+    /// ```
+    /// use uniqueid::{IdentifierBuilder, IdentifierType};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::CPU);
+    /// builder.add_if_not_virtual(IdentifierType::DISK);
+    /// ```
+    pub fn add_if_not_virtual(&mut self, identifier: IdentifierType) -> &mut Self {
+        self.add_if_not_virtual_with(&SysinfoProvider::new(), identifier)
+    }
+
+    /// Adds a new IdentifierType object only if no virtualization is detected from the given
+    /// provider, see `IdentifierBuilder::add_if_not_virtual`.
+    /// # Arguments
+    /// * `provider` - The HardwareProvider to detect virtualization from.
+    /// * `identifier` - The IdentifierType to add on bare metal.
+    pub fn add_if_not_virtual_with(
+        &mut self,
+        provider: &dyn HardwareProvider,
+        identifier: IdentifierType,
+    ) -> &mut Self {
+        if is_virtualized_with(provider).is_none() {
+            self.add(identifier);
+        }
+        self
+    }
+}
+
+impl IdentifierTypeDataList {
+    /// Collects the detected hypervisor or container runtime, or `none` on bare metal.
+    pub(crate) fn collect_virt(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        let kind = is_virtualized_with(provider);
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::VIRT);
+        block.push("t", kind.as_ref().map_or("none", VirtKind::as_str));
+
+        vec![block]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CpuidLeaf, FakeData, FakeProvider, IdentifierOptions};
+
+    fn leaf(leaf: u32, ebx: u32, ecx: u32, edx: u32) -> CpuidLeaf {
+        CpuidLeaf {
+            leaf,
+            ebx,
+            ecx,
+            edx,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the CPUID leaves of a hypervisor with the given 12 byte signature.
+    fn hypervisor(signature: &[u8; 12]) -> Vec<CpuidLeaf> {
+        let register = |i: usize| u32::from_le_bytes(signature[i..i + 4].try_into().unwrap());
+
+        vec![
+            leaf(SIGNATURE_LEAF, 0, HYPERVISOR_BIT, 0),
+            leaf(
+                HYPERVISOR_VENDOR_LEAF,
+                register(0),
+                register(4),
+                register(8),
+            ),
+        ]
+    }
+
+    fn build(data: FakeData) -> String {
+        IdentifierTypeDataList::new(IdentifierType::VIRT)
+            .build_with(&FakeProvider::new(data), &IdentifierOptions::default())
+    }
+
+    #[test]
+    fn test_cpuid_hypervisor() {
+        let kvm = FakeData {
+            cpuid: hypervisor(b"KVMKVMKVM\0\0\0"),
+            ..Default::default()
+        };
+        let mut hyperv_root = hypervisor(b"Microsoft Hv");
+        hyperv_root.push(leaf(HYPERV_FEATURES_LEAF, HYPERV_CREATE_PARTITIONS, 0, 0));
+
+        assert_eq!(build(kvm), "VIRT(t=kvm)");
+        assert_eq!(
+            is_virtualized_with(&FakeProvider::new(FakeData {
+                cpuid: hypervisor(b"Microsoft Hv"),
+                ..Default::default()
+            })),
+            Some(VirtKind::HyperV)
+        );
+        assert_eq!(
+            is_virtualized_with(&FakeProvider::new(FakeData {
+                cpuid: hyperv_root,
+                ..Default::default()
+            })),
+            None
+        );
+        assert_eq!(build(FakeData::default()), "VIRT(t=none)");
+    }
+
+    #[test]
+    fn test_container() {
+        let file = |path: &str, contents: &str| (path.to_string(), contents.as_bytes().to_vec());
+
+        let docker = FakeData {
+            files: [file("/.dockerenv", "")].into(),
+            cpuid: hypervisor(b"KVMKVMKVM\0\0\0"),
+            ..Default::default()
+        };
+        let kubernetes = FakeData {
+            files: [file(
+                "/proc/1/cgroup",
+                "12:memory:/kubepods/burstable/pod1f2e3d4c/0a1b2c3d\n",
+            )]
+            .into(),
+            ..Default::default()
+        };
+
+        assert_eq!(build(docker), "VIRT(t=docker)");
+        assert_eq!(build(kubernetes), "VIRT(t=container)");
+    }
+
+    #[test]
+    fn test_dmi_hypervisor() {
+        assert_eq!(
+            dmi_hypervisor("QEMU", "Standard PC (Q35 + ICH9, 2009)"),
+            Some(VirtKind::Qemu)
+        );
+        assert_eq!(
+            dmi_hypervisor("VMware, Inc.", "VMware7,1"),
+            Some(VirtKind::Vmware)
+        );
+        assert_eq!(
+            dmi_hypervisor("Microsoft Corporation", "Virtual Machine"),
+            Some(VirtKind::HyperV)
+        );
+        assert_eq!(
+            dmi_hypervisor("Microsoft Corporation", "Surface Laptop 5"),
+            None
+        );
+        assert_eq!(dmi_hypervisor("", ""), None);
+    }
+
+    #[test]
+    fn test_add_if_not_virtual() {
+        let kvm = FakeProvider::new(FakeData {
+            cpuid: hypervisor(b"KVMKVMKVM\0\0\0"),
+            ..Default::default()
+        });
+
+        let mut builder = IdentifierBuilder::default();
+        builder
+            .add(IdentifierType::CPU)
+            .add_if_not_virtual_with(&kvm, IdentifierType::DISK)
+            .add_if_not_virtual_with(&FakeProvider::default(), IdentifierType::RAM);
+
+        let types: Vec<_> = builder.data.iter().map(|i| i.identifier.clone()).collect();
+
+        assert_eq!(types, vec![IdentifierType::CPU, IdentifierType::RAM]);
+    }
+}
@@ -0,0 +1,110 @@
+use crate::wmi::{self, WmiRecord};
+use crate::{HardwareProvider, IdentifierType, IdentifierTypeDataList};
+
+/// Parses the serial number of the volume mounted at the system drive from the Win32_LogicalDisk
+/// WMI instances, as 8 lowercase hex digits. (e.g. A1B2C3D4 -> a1b2c3d4)
+pub(crate) fn parse_volume_serial(records: &[WmiRecord], system_drive: &str) -> Option<String> {
+    let serial = records
+        .iter()
+        .find(|record| {
+            record
+                .get("DeviceID")
+                .is_some_and(|id| id.eq_ignore_ascii_case(system_drive))
+        })?
+        .get("VolumeSerialNumber")?;
+
+    (serial.len() == 8 && serial.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| serial.to_lowercase())
+}
+
+/// Reads the serial number of the volume holding `%SystemRoot%`, assigned when the volume is
+/// formatted.
+fn system_volume_serial(provider: &dyn HardwareProvider) -> Option<String> {
+    let os = wmi::query(provider, "Win32_OperatingSystem", &["SystemDrive"]);
+    let system_drive = os.first()?.get("SystemDrive")?;
+
+    parse_volume_serial(
+        &wmi::query(
+            provider,
+            "Win32_LogicalDisk",
+            &["DeviceID", "VolumeSerialNumber"],
+        ),
+        system_drive,
+    )
+}
+
+impl IdentifierTypeDataList {
+    /// Collects the serial number of the system volume as 8 lowercase hex digits, or nothing if it
+    /// can not be queried, such as inside a sandbox.
+    pub(crate) fn collect_volume(
+        &self,
+        provider: &dyn HardwareProvider,
+    ) -> Vec<IdentifierTypeDataList> {
+        let serial = match system_volume_serial(provider) {
+            Some(serial) => serial,
+            None => return Vec::new(),
+        };
+
+        let mut block = IdentifierTypeDataList::new(IdentifierType::VOLUME);
+        block.push("s", &serial);
+
+        vec![block]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider, IdentifierOptions, SysinfoProvider, UniqueIdError};
+
+    fn command(query: &str, output: &str) -> (String, String) {
+        (
+            format!("powershell -NoProfile -Command {}", query),
+            output.to_string(),
+        )
+    }
+
+    #[test]
+    fn test_volume() {
+        let provider = FakeProvider::new(FakeData {
+            commands: [
+                command(
+                    "Get-CimInstance Win32_OperatingSystem | Format-List SystemDrive",
+                    "\r\n\r\nSystemDrive : C:\r\n\r\n",
+                ),
+                command(
+                    "Get-CimInstance Win32_LogicalDisk | Format-List DeviceID,VolumeSerialNumber",
+                    "\r\n\r\nDeviceID           : C:\r\nVolumeSerialNumber : A1B2C3D4\r\n\r\n\
+                     DeviceID           : D:\r\nVolumeSerialNumber : \r\n\r\n",
+                ),
+            ]
+            .into(),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::VOLUME)
+                .build_with(&provider, &IdentifierOptions::default()),
+            "VOLUME(s=a1b2c3d4)"
+        );
+        assert_eq!(
+            IdentifierTypeDataList::new(IdentifierType::VOLUME)
+                .try_collect_with(&FakeProvider::default(), &IdentifierOptions::default()),
+            Err(UniqueIdError::SourceUnavailable(IdentifierType::VOLUME))
+        );
+    }
+
+    #[test]
+    fn test_volume_on_system() {
+        let blocks = IdentifierTypeDataList::new(IdentifierType::VOLUME)
+            .collect_with(&SysinfoProvider::new(), &IdentifierOptions::default());
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].data[0].key, "s");
+        assert_eq!(blocks[0].data[0].value.len(), 8);
+        assert!(blocks[0].data[0]
+            .value
+            .chars()
+            .all(|c| c.is_ascii_hexdigit()));
+    }
+}
@@ -0,0 +1,156 @@
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::{HardwareProvider, IdentifierBuilder, IdentifierDiff, RawComponent, SysinfoProvider};
+
+/// Periodically rebuilds an Identifier on a background thread and reports when it changes.
+/// The watcher is stopped when it is dropped or when `stop` is called.
+pub struct IdentifierWatcher {
+    stop: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl IdentifierWatcher {
+    /// Spawns a new IdentifierWatcher reading from a shared sysinfo System.
+    /// # Arguments
+    /// * `builder` - The IdentifierBuilder describing the Identifier to watch.
+    /// * `interval` - The time between two rebuilds.
+    /// * `callback` - Called with the differences every time the Identifier changed.
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use uniqueid::{IdentifierBuilder, IdentifierType, IdentifierWatcher};
+    ///
+    /// let mut builder = IdentifierBuilder::default();
+    /// builder.add(IdentifierType::DISK);
+    ///
+    /// let watcher = IdentifierWatcher::spawn(builder, Duration::from_secs(60), |diff| {
+    ///     println!("{:?}", diff);
+    /// });
+    ///
+    /// watcher.stop();
+    /// ```
+    pub fn spawn<F>(builder: IdentifierBuilder, interval: Duration, callback: F) -> Self
+    where
+        F: FnMut(IdentifierDiff) + Send + 'static,
+    {
+        Self::spawn_with(
+            builder,
+            Arc::new(SysinfoProvider::new()),
+            interval,
+            callback,
+        )
+    }
+
+    /// Spawns a new IdentifierWatcher reading from the given provider.
+    /// The provider is refreshed before every rebuild.
+    pub fn spawn_with<F>(
+        builder: IdentifierBuilder,
+        provider: Arc<dyn HardwareProvider>,
+        interval: Duration,
+        mut callback: F,
+    ) -> Self
+    where
+        F: FnMut(IdentifierDiff) + Send + 'static,
+    {
+        let identifier = builder.build();
+        let mut last: Vec<RawComponent> = identifier.get_raw_components_with(provider.as_ref());
+
+        let (stop, receiver) = mpsc::channel();
+
+        let handle = thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = receiver.recv_timeout(interval) {
+                provider.refresh();
+
+                let current = identifier.get_raw_components_with(provider.as_ref());
+                let diff = IdentifierDiff::new(&last, &current);
+
+                if !diff.is_empty() {
+                    last = current;
+                    callback(diff);
+                }
+            }
+        });
+
+        IdentifierWatcher {
+            stop: Some(stop),
+            handle: Some(handle),
+        }
+    }
+
+    /// Stops the IdentifierWatcher and waits for the background thread to exit.
+    pub fn stop(self) {}
+}
+
+impl Drop for IdentifierWatcher {
+    fn drop(&mut self) {
+        // Dropping the sender wakes up the background thread.
+        self.stop.take();
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DiskInfo, FakeData, FakeProvider, IdentifierType};
+
+    #[test]
+    fn test_watcher_reports_changes() {
+        let provider = Arc::new(FakeProvider::new(FakeData {
+            total_memory: 1024,
+            disks: vec![DiskInfo {
+                mount_point: "/".to_string(),
+                total_space: 100,
+                removable: false,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }));
+
+        let mut builder = IdentifierBuilder::default();
+        builder.add(IdentifierType::RAM);
+        builder.add(IdentifierType::DISK);
+
+        let (sender, receiver) = mpsc::channel();
+        let watcher = IdentifierWatcher::spawn_with(
+            builder,
+            provider.clone(),
+            Duration::from_millis(10),
+            move |diff| sender.send(diff).unwrap(),
+        );
+
+        thread::sleep(Duration::from_millis(50));
+        assert!(receiver.try_recv().is_err());
+
+        provider.update(|data| {
+            data.disks.push(DiskInfo {
+                mount_point: "/home".to_string(),
+                total_space: 200,
+                removable: false,
+                ..Default::default()
+            })
+        });
+
+        let diff = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].0, IdentifierType::DISK);
+
+        provider.update(|data| data.total_memory = 2048);
+
+        let diff = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].0, IdentifierType::RAM);
+
+        watcher.stop();
+
+        provider.update(|data| data.total_memory = 4096);
+        thread::sleep(Duration::from_millis(50));
+        assert!(receiver.try_recv().is_err());
+    }
+}
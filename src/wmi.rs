@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+
+use crate::HardwareProvider;
+
+/// A single WMI instance as (property, value) pairs.
+pub(crate) type WmiRecord = BTreeMap<String, String>;
+
+/// Parses the `Format-List` output of PowerShell into one record per instance.
+pub(crate) fn parse_format_list(output: &str) -> Vec<WmiRecord> {
+    output
+        .replace('\r', "")
+        .split("\n\n")
+        .filter(|record| !record.trim().is_empty())
+        .map(|record| {
+            record
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+                .collect()
+        })
+        .collect()
+}
+
+/// Queries the given properties of every instance of a WMI class through PowerShell.
+/// Returns an empty list if PowerShell is not available.
+pub(crate) fn query(
+    provider: &dyn HardwareProvider,
+    class: &str,
+    properties: &[&str],
+) -> Vec<WmiRecord> {
+    run(
+        provider,
+        format!(
+            "Get-CimInstance {} | Format-List {}",
+            class,
+            properties.join(",")
+        ),
+    )
+}
+
+/// Queries the given properties of every instance of a WMI class of another namespace than
+/// `root/cimv2`. (e.g. root/wmi)
+pub(crate) fn query_namespace(
+    provider: &dyn HardwareProvider,
+    namespace: &str,
+    class: &str,
+    properties: &[&str],
+) -> Vec<WmiRecord> {
+    run(
+        provider,
+        format!(
+            "Get-CimInstance -Namespace {} {} | Format-List {}",
+            namespace,
+            class,
+            properties.join(",")
+        ),
+    )
+}
+
+/// Runs a PowerShell command printing `Format-List` output and parses it.
+fn run(provider: &dyn HardwareProvider, command: String) -> Vec<WmiRecord> {
+    provider
+        .run_command("powershell", &["-NoProfile", "-Command", &command])
+        .map(|output| parse_format_list(&output))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FakeData, FakeProvider};
+
+    #[test]
+    fn test_query() {
+        let provider = FakeProvider::new(FakeData {
+            commands: [(
+                "powershell -NoProfile -Command Get-CimInstance Win32_BIOS | Format-List Version"
+                    .to_string(),
+                "\r\n\r\nVersion : ALASKA - 1072009\r\n\r\n\r\n".to_string(),
+            )]
+            .into(),
+            ..Default::default()
+        });
+
+        let records = query(&provider, "Win32_BIOS", &["Version"]);
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["Version"], "ALASKA - 1072009");
+        assert!(query(&provider, "Win32_Processor", &["Name"]).is_empty());
+    }
+}
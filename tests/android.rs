@@ -0,0 +1,40 @@
+#![cfg(all(feature = "android", target_os = "android"))]
+
+use uniqueid::{IdentifierOptions, IdentifierType, IdentifierTypeDataList, SysinfoProvider};
+
+fn collect(identifier: IdentifierType) -> Vec<IdentifierTypeDataList> {
+    let mut options = IdentifierOptions::default();
+    options.disk_mount_info = true;
+
+    IdentifierTypeDataList::new(identifier).collect_with(&SysinfoProvider::new(), &options)
+}
+
+fn value<'a>(block: &'a IdentifierTypeDataList, key: &str) -> Option<&'a str> {
+    block
+        .data
+        .iter()
+        .find(|item| item.key == key)
+        .map(|item| item.value.as_str())
+}
+
+#[test]
+fn test_device_properties_on_device() {
+    let cpu = collect(IdentifierType::CPU);
+
+    assert!(value(&cpu[0], "dm").is_some_and(|model| !model.is_empty()));
+    assert!(value(&cpu[0], "dv").is_some_and(|manufacturer| !manufacturer.is_empty()));
+
+    // e.g. google/panther/panther:14/UQ1A.240205.004/11269751:user/release-keys
+    let os = collect(IdentifierType::OS);
+
+    assert!(value(&os[0], "f").is_some_and(|fingerprint| fingerprint.contains('/')));
+}
+
+#[test]
+fn test_no_emulated_storage_on_device() {
+    for disk in collect(IdentifierType::DISK) {
+        let mount_point = value(&disk, "mp").unwrap_or_default();
+
+        assert!(!mount_point.contains("emulated"), "{}", mount_point);
+    }
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "cli")]
+
+use assert_cmd::Command;
+
+const HARDWARE: &str = "tests/fixtures/hardware.json";
+const HARDWARE_UPGRADED: &str = "tests/fixtures/hardware_upgraded.json";
+const SNAPSHOT: &str = "tests/fixtures/snapshot.json";
+
+fn uniqueid(hardware: &str) -> Command {
+    let mut command = Command::cargo_bin("uniqueid").unwrap();
+    command.env("UNIQUEID_FAKE_HARDWARE", hardware);
+    command
+}
+
+#[test]
+fn test_verify_hash() {
+    let output = uniqueid(HARDWARE)
+        .args(["--name", "test"])
+        .output()
+        .unwrap();
+    let hash = String::from_utf8(output.stdout).unwrap();
+
+    uniqueid(HARDWARE)
+        .args(["verify", hash.trim(), "--name", "test"])
+        .assert()
+        .code(0)
+        .stdout("match\n");
+
+    uniqueid(HARDWARE_UPGRADED)
+        .args(["verify", hash.trim(), "--name", "test"])
+        .assert()
+        .code(1)
+        .stdout("mismatch\n");
+}
+
+#[test]
+fn test_verify_snapshot() {
+    uniqueid(HARDWARE)
+        .args(["verify", SNAPSHOT])
+        .assert()
+        .code(0)
+        .stdout("match\n");
+
+    uniqueid(HARDWARE_UPGRADED)
+        .args(["verify", SNAPSHOT])
+        .assert()
+        .code(1)
+        .stdout("mismatch\n+ DISK(t=2000000000000)\n");
+}
+
+#[test]
+fn test_diff_json() {
+    let output = uniqueid(HARDWARE_UPGRADED)
+        .args(["diff", SNAPSHOT, "--json"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["match"], false);
+    assert_eq!(json["diff"]["added"][0][0], "DISK");
+    assert_eq!(json["diff"]["added"][0][1]["t"], "2000000000000");
+
+    uniqueid(HARDWARE)
+        .args(["diff", SNAPSHOT, "--json"])
+        .assert()
+        .code(0);
+}
+
+#[test]
+fn test_errors() {
+    uniqueid(HARDWARE)
+        .args(["diff", "tests/fixtures/missing.json"])
+        .assert()
+        .code(2);
+
+    uniqueid(HARDWARE).args(["verify"]).assert().code(2);
+
+    uniqueid("tests/fixtures/missing.json")
+        .args(["verify", SNAPSHOT])
+        .assert()
+        .code(2);
+
+    uniqueid(HARDWARE).args(["diff", HARDWARE]).assert().code(2);
+}
@@ -0,0 +1,33 @@
+#![cfg(all(feature = "tpm", target_os = "linux"))]
+
+use uniqueid::{
+    IdentifierOptions, IdentifierType, IdentifierTypeDataList, SysinfoProvider, UniqueIdError,
+};
+
+/// Returns true if the system exposes the TPM resource manager to the current user.
+fn has_tpm() -> bool {
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tpmrm0")
+        .is_ok()
+}
+
+#[test]
+fn test_tpm_on_system() {
+    let result = IdentifierTypeDataList::new(IdentifierType::TPM)
+        .try_collect_with(&SysinfoProvider::new(), &IdentifierOptions::default());
+
+    if !has_tpm() {
+        assert_eq!(
+            result,
+            Err(UniqueIdError::SourceUnavailable(IdentifierType::TPM))
+        );
+        return;
+    }
+
+    let blocks = result.expect("the TPM is readable, so the EK should be collected");
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].data[0].key, "ek");
+    assert_eq!(blocks[0].data[0].value.len(), 64);
+}